@@ -0,0 +1,64 @@
+//! Compares `Bytes::as_num_of_pages` with the division it replaced.
+//!
+//! Run with `cargo bench --bench page_conversion`.
+
+use os_units::Bytes;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use x86_64::structures::paging::{PageSize, Size2MiB, Size4KiB};
+
+const LENGTHS: usize = 1 << 20;
+const ROUNDS: usize = 20;
+
+fn lengths() -> Vec<usize> {
+    let mut x: usize = 0x2545_f491;
+
+    (0..LENGTHS)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x >> 16
+        })
+        .collect()
+}
+
+fn best_of(f: impl Fn() -> usize) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn bench<T: PageSize>(lengths: &[usize]) {
+    // Hidden from the optimizer so that the division is not turned into a shift.
+    let size = black_box(T::SIZE as usize);
+
+    let division = best_of(|| lengths.iter().map(|&b| b.div_ceil(size)).sum());
+    let shift = best_of(|| {
+        lengths
+            .iter()
+            .map(|&b| Bytes::new(b).as_num_of_pages::<T>().as_usize())
+            .sum()
+    });
+
+    println!(
+        "{}: division {:?}, as_num_of_pages {:?} for {} lengths",
+        T::SIZE_AS_DEBUG_STR,
+        division,
+        shift,
+        lengths.len()
+    );
+}
+
+fn main() {
+    let lengths = black_box(lengths());
+
+    bench::<Size4KiB>(&lengths);
+    bench::<Size2MiB>(&lengths);
+}
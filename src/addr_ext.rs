@@ -0,0 +1,257 @@
+use crate::Bytes;
+use core::convert::TryFrom;
+use x86_64::PhysAddr;
+use x86_64::VirtAddr;
+
+/// Offsetting [`VirtAddr`] and [`PhysAddr`] by [`Bytes`].
+///
+/// The results follow the rules of `VirtAddr::try_new` and `PhysAddr::try_new` of the `x86_64`
+/// crate. In particular, a virtual address which goes past the end of the lower half is sign
+/// extended into the higher half, as `VirtAddr + u64` does, while any other non-canonical result
+/// is rejected.
+///
+/// ```rust
+/// use os_units::{AddrExt, Bytes};
+/// use x86_64::VirtAddr;
+///
+/// let a = VirtAddr::new(0x1000);
+///
+/// assert_eq!(a.checked_add_bytes(Bytes::new(0x1000)), Some(VirtAddr::new(0x2000)));
+/// assert_eq!(a.checked_sub_bytes(Bytes::new(0x1001)), None);
+/// ```
+pub trait AddrExt: Sized {
+    /// Adds `bytes` to the address, returning [`None`] if the result is not a valid address.
+    fn checked_add_bytes(self, bytes: Bytes) -> Option<Self>;
+
+    /// Subtracts `bytes` from the address, returning [`None`] if the result is not a valid
+    /// address.
+    fn checked_sub_bytes(self, bytes: Bytes) -> Option<Self>;
+
+    /// Adds `bytes` to the address.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the result is not a valid address.
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    fn add_bytes(self, bytes: Bytes) -> Self {
+        self.checked_add_bytes(bytes)
+            .expect("The address is out of range.")
+    }
+
+    /// Subtracts `bytes` from the address.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the result is not a valid address.
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    fn sub_bytes(self, bytes: Bytes) -> Self {
+        self.checked_sub_bytes(bytes)
+            .expect("The address is out of range.")
+    }
+}
+impl AddrExt for VirtAddr {
+    fn checked_add_bytes(self, bytes: Bytes) -> Option<Self> {
+        let addr = self
+            .as_u64()
+            .checked_add(u64::try_from(bytes.as_usize()).ok()?)?;
+
+        Self::try_new(addr).ok()
+    }
+
+    fn checked_sub_bytes(self, bytes: Bytes) -> Option<Self> {
+        let addr = self
+            .as_u64()
+            .checked_sub(u64::try_from(bytes.as_usize()).ok()?)?;
+
+        Self::try_new(addr).ok()
+    }
+}
+impl AddrExt for PhysAddr {
+    fn checked_add_bytes(self, bytes: Bytes) -> Option<Self> {
+        let addr = self
+            .as_u64()
+            .checked_add(u64::try_from(bytes.as_usize()).ok()?)?;
+
+        Self::try_new(addr).ok()
+    }
+
+    fn checked_sub_bytes(self, bytes: Bytes) -> Option<Self> {
+        let addr = self
+            .as_u64()
+            .checked_sub(u64::try_from(bytes.as_usize()).ok()?)?;
+
+        Self::try_new(addr).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AddrExt;
+    use crate::Bytes;
+    use x86_64::{PhysAddr, VirtAddr};
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn virt_add_bytes() {
+        let a = VirtAddr::new(0x1000).add_bytes(Bytes::new(0x234));
+
+        assert_eq!(a, VirtAddr::new(0x1234));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn virt_sub_bytes() {
+        let a = VirtAddr::new(0x1234).sub_bytes(Bytes::new(0x234));
+
+        assert_eq!(a, VirtAddr::new(0x1000));
+    }
+
+    #[test]
+    fn virt_add_up_to_end_of_lower_half() {
+        let a = VirtAddr::new(0x7fff_ffff_f000).checked_add_bytes(Bytes::new(0xfff));
+
+        assert_eq!(a, Some(VirtAddr::new(0x7fff_ffff_ffff)));
+    }
+
+    #[test]
+    fn virt_add_past_end_of_lower_half_is_sign_extended() {
+        let a = VirtAddr::new(0x7fff_ffff_f000).checked_add_bytes(Bytes::new(0x1000));
+
+        assert_eq!(a, Some(VirtAddr::new(0xffff_8000_0000_0000)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn virt_add_to_end_of_gap_is_sign_extended() {
+        let a = VirtAddr::new(0x7fff_ffff_f000).checked_add_bytes(Bytes::new(0x8000_0000_0fff));
+
+        assert_eq!(a, Some(VirtAddr::new(0xffff_ffff_ffff_ffff)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn virt_add_over_gap_to_start_of_higher_half() {
+        let a =
+            VirtAddr::new(0x7fff_ffff_f000).checked_add_bytes(Bytes::new(0xffff_0000_0000_1000));
+
+        assert_eq!(a, Some(VirtAddr::new(0xffff_8000_0000_0000)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn virt_add_far_past_lower_half() {
+        let a = VirtAddr::new(0x7fff_ffff_f000).checked_add_bytes(Bytes::new(0x1_0000_0000_0000));
+
+        assert_eq!(a, None);
+    }
+
+    #[test]
+    fn virt_add_past_top() {
+        let a = VirtAddr::new(0xffff_ffff_ffff_f000).checked_add_bytes(Bytes::new(0x1000));
+
+        assert_eq!(a, None);
+    }
+
+    #[test]
+    fn virt_sub_below_start_of_higher_half() {
+        let a = VirtAddr::new(0xffff_8000_0000_0000).checked_sub_bytes(Bytes::new(1));
+
+        assert_eq!(a, None);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn virt_sub_to_start_of_gap_is_sign_extended() {
+        let a = VirtAddr::new(0xffff_8000_0000_0000)
+            .checked_sub_bytes(Bytes::new(0xffff_0000_0000_0000));
+
+        assert_eq!(a, Some(VirtAddr::new(0xffff_8000_0000_0000)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn virt_sub_to_end_of_gap_is_sign_extended() {
+        let a = VirtAddr::new(0xffff_8000_0000_0000)
+            .checked_sub_bytes(Bytes::new(0xffff_0000_0000_0001 - 0x8000_0000_0000));
+
+        assert_eq!(a, Some(VirtAddr::new(0xffff_ffff_ffff_ffff)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn virt_sub_into_gap_is_sign_extended() {
+        let a = VirtAddr::new(0xffff_8000_0000_1000)
+            .checked_sub_bytes(Bytes::new(0xffff_0000_0000_0000));
+
+        assert_eq!(a, Some(VirtAddr::new(0xffff_8000_0000_1000)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn virt_sub_over_gap_to_end_of_lower_half() {
+        let a = VirtAddr::new(0xffff_8000_0000_0000)
+            .checked_sub_bytes(Bytes::new(0xffff_0000_0000_0001));
+
+        assert_eq!(a, Some(VirtAddr::new(0x7fff_ffff_ffff)));
+    }
+
+    #[test]
+    fn virt_sub_below_zero() {
+        let a = VirtAddr::new(0).checked_sub_bytes(Bytes::new(1));
+
+        assert_eq!(a, None);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic(expected = "The address is out of range.")]
+    fn virt_add_bytes_panics() {
+        let _ = VirtAddr::new(0xffff_ffff_ffff_ffff).add_bytes(Bytes::new(1));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn phys_add_bytes() {
+        let a = PhysAddr::new(0x1000).add_bytes(Bytes::new(0x234));
+
+        assert_eq!(a, PhysAddr::new(0x1234));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn phys_sub_bytes() {
+        let a = PhysAddr::new(0x1234).sub_bytes(Bytes::new(0x234));
+
+        assert_eq!(a, PhysAddr::new(0x1000));
+    }
+
+    #[test]
+    fn phys_add_up_to_top() {
+        let a = PhysAddr::new(0xf_ffff_ffff_f000).checked_add_bytes(Bytes::new(0xfff));
+
+        assert_eq!(a, Some(PhysAddr::new(0xf_ffff_ffff_ffff)));
+    }
+
+    #[test]
+    fn phys_add_past_top() {
+        let a = PhysAddr::new(0xf_ffff_ffff_f000).checked_add_bytes(Bytes::new(0x1000));
+
+        assert_eq!(a, None);
+    }
+
+    #[test]
+    fn phys_sub_below_zero() {
+        let a = PhysAddr::new(0).checked_sub_bytes(Bytes::new(1));
+
+        assert_eq!(a, None);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic(expected = "The address is out of range.")]
+    fn phys_sub_bytes_panics() {
+        let _ = PhysAddr::new(0).sub_bytes(Bytes::new(1));
+    }
+}
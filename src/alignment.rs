@@ -0,0 +1,184 @@
+use crate::Bytes;
+use core::convert::TryFrom;
+use x86_64::structures::paging::PageSize;
+
+/// An alignment in bytes, which is always a power of two.
+///
+/// ```rust
+/// use os_units::{Alignment, Bytes};
+///
+/// let align = Alignment::new(0x10).unwrap();
+///
+/// assert_eq!(align.align_up(Bytes::new(0x11)), Some(Bytes::new(0x20)));
+/// assert_eq!(align.align_down(Bytes::new(0x11)), Bytes::new(0x10));
+/// assert_eq!(Alignment::new(0x18), None);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Alignment(usize);
+impl Alignment {
+    /// The alignment of 1 byte, i.e. no alignment.
+    pub const ONE: Self = Self(1);
+
+    /// Creates a new alignment of `align` bytes.
+    ///
+    /// This method returns [`None`] if `align` is not a power of two.
+    #[must_use]
+    pub const fn new(align: usize) -> Option<Self> {
+        if align.is_power_of_two() {
+            Some(Self(align))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the alignment of `T`.
+    #[must_use]
+    pub const fn of<T>() -> Self {
+        Self(align_of::<T>())
+    }
+
+    /// Returns the alignment of the pages of size `T`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `T::SIZE` does not fit in `usize`.
+    #[must_use]
+    pub fn of_page<T: PageSize>() -> Self {
+        Self(usize::try_from(T::SIZE).expect("the page size does not fit in `usize`"))
+    }
+
+    /// Returns the alignment as [`Bytes`].
+    #[must_use]
+    pub const fn as_bytes(self) -> Bytes {
+        Bytes::new(self.0)
+    }
+
+    /// Returns the alignment as `usize`.
+    #[must_use]
+    pub const fn as_usize(self) -> usize {
+        self.0
+    }
+
+    /// Rounds `bytes` up to a multiple of the alignment.
+    ///
+    /// This method returns [`None`] if the result does not fit in `usize`.
+    #[must_use]
+    pub const fn align_up(self, bytes: Bytes) -> Option<Bytes> {
+        match bytes.as_usize().checked_add(self.0 - 1) {
+            Some(b) => Some(Bytes::new(b & !(self.0 - 1))),
+            None => None,
+        }
+    }
+
+    /// Rounds `bytes` down to a multiple of the alignment.
+    #[must_use]
+    pub const fn align_down(self, bytes: Bytes) -> Bytes {
+        Bytes::new(bytes.as_usize() & !(self.0 - 1))
+    }
+
+    /// Returns `true` if `bytes` is a multiple of the alignment.
+    #[must_use]
+    pub const fn is_aligned(self, bytes: Bytes) -> bool {
+        bytes.as_usize() & (self.0 - 1) == 0
+    }
+}
+impl Default for Alignment {
+    fn default() -> Self {
+        Self::ONE
+    }
+}
+impl TryFrom<Bytes> for Alignment {
+    type Error = Bytes;
+
+    /// Returns `bytes` itself as the error if it is not a power of two.
+    fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
+        Self::new(bytes.as_usize()).ok_or(bytes)
+    }
+}
+impl From<Alignment> for Bytes {
+    fn from(align: Alignment) -> Self {
+        align.as_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Alignment;
+    use crate::Bytes;
+    use core::convert::TryFrom;
+    use x86_64::structures::paging::{Size1GiB, Size4KiB};
+
+    #[test]
+    fn new() {
+        assert_eq!(Alignment::new(1), Some(Alignment::ONE));
+        assert_eq!(
+            Alignment::new(0x1000).map(Alignment::as_usize),
+            Some(0x1000)
+        );
+        assert_eq!(Alignment::new(0), None);
+        assert_eq!(Alignment::new(3), None);
+        assert_eq!(Alignment::new(usize::MAX), None);
+    }
+
+    #[test]
+    fn of() {
+        assert_eq!(Alignment::of::<u32>().as_usize(), 4);
+        assert_eq!(Alignment::of::<u8>(), Alignment::ONE);
+    }
+
+    #[test]
+    fn of_page() {
+        assert_eq!(
+            Alignment::of_page::<Size4KiB>().as_bytes(),
+            Bytes::new(0x1000)
+        );
+        assert_eq!(Alignment::of_page::<Size1GiB>().as_usize(), 0x4000_0000);
+    }
+
+    #[test]
+    fn align_up() {
+        let a = Alignment::new(0x1000).unwrap();
+
+        assert_eq!(a.align_up(Bytes::zero()), Some(Bytes::zero()));
+        assert_eq!(a.align_up(Bytes::new(1)), Some(Bytes::new(0x1000)));
+        assert_eq!(a.align_up(Bytes::new(0x1000)), Some(Bytes::new(0x1000)));
+        assert_eq!(a.align_up(Bytes::new(0x1001)), Some(Bytes::new(0x2000)));
+    }
+
+    #[test]
+    fn align_up_overflow() {
+        let a = Alignment::new(0x1000).unwrap();
+
+        assert_eq!(a.align_up(Bytes::new(usize::MAX)), None);
+        assert_eq!(
+            a.align_up(Bytes::new(usize::MAX - 0xfff)),
+            Some(Bytes::new(usize::MAX - 0xfff))
+        );
+    }
+
+    #[test]
+    fn align_down() {
+        let a = Alignment::new(0x1000).unwrap();
+
+        assert_eq!(a.align_down(Bytes::new(0xfff)), Bytes::zero());
+        assert_eq!(a.align_down(Bytes::new(0x1fff)), Bytes::new(0x1000));
+    }
+
+    #[test]
+    fn is_aligned() {
+        let a = Alignment::new(8).unwrap();
+
+        assert!(a.is_aligned(Bytes::zero()));
+        assert!(a.is_aligned(Bytes::new(0x18)));
+        assert!(!a.is_aligned(Bytes::new(0x1c)));
+    }
+
+    #[test]
+    fn try_from_bytes() {
+        assert_eq!(
+            Alignment::try_from(Bytes::new(0x40)),
+            Ok(Alignment::new(0x40).unwrap())
+        );
+        assert_eq!(Alignment::try_from(Bytes::new(0x30)), Err(Bytes::new(0x30)));
+    }
+}
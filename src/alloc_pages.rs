@@ -0,0 +1,163 @@
+use crate::NumOfPages;
+use alloc::alloc::{self as global, Layout};
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+use core::slice;
+use x86_64::structures::paging::PageSize;
+
+/// Allocates a buffer of `count` pages aligned to the page size with the global allocator.
+///
+/// The contents of the buffer are unspecified. Currently they are zeros, because a `[u8]` must
+/// not expose uninitialized memory, but use [`alloc_pages_zeroed`] if you rely on it.
+///
+/// This function returns [`None`] if `count` is zero, the size does not fit in `isize`, or the
+/// allocator fails.
+///
+/// ```rust
+/// use os_units::{alloc_pages, NumOfPages};
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// let buf = alloc_pages(NumOfPages::<Size4KiB>::new(2)).unwrap();
+///
+/// assert_eq!(buf.len(), 0x2000);
+/// assert_eq!(buf.as_ptr() as usize % 0x1000, 0);
+/// ```
+#[must_use]
+pub fn alloc_pages<T: PageSize>(count: NumOfPages<T>) -> Option<PageBuffer<T>> {
+    alloc_pages_zeroed(count)
+}
+
+/// Like [`alloc_pages`], but the buffer is guaranteed to be filled with zeros.
+#[must_use]
+pub fn alloc_pages_zeroed<T: PageSize>(count: NumOfPages<T>) -> Option<PageBuffer<T>> {
+    let layout = count.as_layout().filter(|l| l.size() > 0)?;
+
+    // SAFETY: The size of `layout` is not zero.
+    let ptr = NonNull::new(unsafe { global::alloc_zeroed(layout) })?;
+
+    Some(PageBuffer {
+        ptr,
+        layout,
+        _marker: PhantomData,
+    })
+}
+
+/// An owned, page-aligned buffer returned by [`alloc_pages`] and [`alloc_pages_zeroed`].
+///
+/// This type is used instead of `Box<[u8]>` because a `Box<[u8]>` would be deallocated with the
+/// alignment of `u8`, not the alignment the buffer was allocated with.
+pub struct PageBuffer<T: PageSize> {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    _marker: PhantomData<fn() -> T>,
+}
+impl<T: PageSize> PageBuffer<T> {
+    /// Returns the number of the pages of the buffer.
+    #[must_use]
+    pub fn num_of_pages(&self) -> NumOfPages<T> {
+        // Not larger than the size of the buffer, so it fits in `usize`.
+        #[allow(clippy::cast_possible_truncation)]
+        NumOfPages::new((self.layout.size() as u64 / T::SIZE) as usize)
+    }
+
+    /// Returns the layout the buffer was allocated with.
+    #[must_use]
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+}
+impl<T: PageSize> Deref for PageBuffer<T> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: `ptr` points to `layout.size()` initialized bytes owned by `self`.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.layout.size()) }
+    }
+}
+impl<T: PageSize> DerefMut for PageBuffer<T> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: `ptr` points to `layout.size()` initialized bytes owned by `self`.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.layout.size()) }
+    }
+}
+impl<T: PageSize> Drop for PageBuffer<T> {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` was allocated with `layout` by the global allocator.
+        unsafe { global::dealloc(self.ptr.as_ptr(), self.layout) }
+    }
+}
+impl<T: PageSize> fmt::Debug for PageBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PageBuffer")
+            .field("ptr", &self.ptr)
+            .field("layout", &self.layout)
+            .finish()
+    }
+}
+// SAFETY: `PageBuffer` owns its memory exclusively, like `Box<[u8]>`.
+unsafe impl<T: PageSize> Send for PageBuffer<T> {}
+// SAFETY: `PageBuffer` only gives out `&[u8]` through `&self`, like `Box<[u8]>`.
+unsafe impl<T: PageSize> Sync for PageBuffer<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{alloc_pages, alloc_pages_zeroed};
+    use crate::NumOfPages;
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    #[test]
+    fn alloc_4kib() {
+        let b = alloc_pages(NumOfPages::<Size4KiB>::new(3)).unwrap();
+
+        assert_eq!(b.len(), 0x3000);
+        assert_eq!(b.as_ptr() as usize % 0x1000, 0);
+        assert_eq!(b.num_of_pages(), NumOfPages::new(3));
+        assert_eq!(b.layout().align(), 0x1000);
+    }
+
+    #[test]
+    fn alloc_2mib() {
+        let b = alloc_pages(NumOfPages::<Size2MiB>::new(1)).unwrap();
+
+        assert_eq!(b.len(), 0x20_0000);
+        assert_eq!(b.as_ptr() as usize % 0x20_0000, 0);
+        assert_eq!(b.num_of_pages(), NumOfPages::new(1));
+    }
+
+    #[test]
+    fn zeroed() {
+        let b = alloc_pages_zeroed(NumOfPages::<Size4KiB>::new(2)).unwrap();
+
+        assert!(b.iter().all(|&x| x == 0));
+    }
+
+    #[test]
+    fn zeroed_2mib() {
+        let b = alloc_pages_zeroed(NumOfPages::<Size2MiB>::new(1)).unwrap();
+
+        assert_eq!(b.as_ptr() as usize % 0x20_0000, 0);
+        assert!(b.iter().all(|&x| x == 0));
+    }
+
+    #[test]
+    fn writable() {
+        let mut b = alloc_pages(NumOfPages::<Size4KiB>::new(1)).unwrap();
+
+        b[0xfff] = 0xaa;
+
+        assert_eq!(b[0xfff], 0xaa);
+    }
+
+    #[test]
+    fn zero_pages() {
+        assert!(alloc_pages(NumOfPages::<Size4KiB>::zero()).is_none());
+        assert!(alloc_pages_zeroed(NumOfPages::<Size4KiB>::zero()).is_none());
+    }
+
+    #[test]
+    fn too_large() {
+        assert!(alloc_pages(NumOfPages::<Size4KiB>::new(usize::MAX)).is_none());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::ByteRegion;
+use crate::Bytes;
+use crate::NumOfPages;
+use arbitrary::size_hint;
+use arbitrary::Arbitrary;
+use arbitrary::Result;
+use arbitrary::Unstructured;
+use x86_64::structures::paging::PageSize;
+
+/// Generates values biased toward edge cases: zero, multiples of each page size, and values near
+/// `usize::MAX`.
+impl<'a> Arbitrary<'a> for Bytes {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let v = match u.int_in_range(0_u8..=7)? {
+            0 => 0,
+            1 => usize::MAX - usize::from(u.arbitrary::<u8>()?),
+            2 => u.arbitrary::<usize>()? & !0xfff,
+            3 => u.arbitrary::<usize>()? & !0x1f_ffff,
+            4 => u.arbitrary::<usize>()? & !0x3fff_ffff,
+            _ => u.arbitrary()?,
+        };
+
+        Ok(Self::new(v))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(u8::size_hint(depth), usize::size_hint(depth))
+    }
+}
+
+/// Generates values biased toward edge cases: zero, one, powers of two, and values near the
+/// largest count whose number of bytes fits in `usize`.
+impl<'a, T: PageSize> Arbitrary<'a> for NumOfPages<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let max_convertible = NumOfPages::<T>::max_convertible().as_usize();
+
+        let v = match u.int_in_range(0_u8..=7)? {
+            0 => 0,
+            1 => 1,
+            2 => max_convertible - usize::from(u.arbitrary::<u8>()?),
+            3 => 1 << u.int_in_range(0..=usize::BITS - 1)?,
+            _ => u.arbitrary()?,
+        };
+
+        Ok(Self::new(v))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(u8::size_hint(depth), usize::size_hint(depth))
+    }
+}
+
+/// Generates the start like [`Bytes`], and a size which does not make the end overflow.
+impl<'a> Arbitrary<'a> for ByteRegion {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let start = Bytes::arbitrary(u)?;
+        let size = Bytes::arbitrary(u)?.min(Bytes::new(usize::MAX - start.as_usize()));
+
+        Ok(Self::new(start, size).expect("The end of the region overflows."))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(Bytes::size_hint(depth), Bytes::size_hint(depth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ByteRegion;
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use arbitrary::{Arbitrary, Unstructured};
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    /// Generates deterministic pseudo-random input, like a fuzzer's corpus.
+    fn corpus(seed: u64, len: usize) -> Vec<u8> {
+        let mut x = seed;
+
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x.to_le_bytes()[0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bytes_from_empty_input() {
+        let mut u = Unstructured::new(&[]);
+
+        assert_eq!(Bytes::arbitrary(&mut u).unwrap(), Bytes::zero());
+    }
+
+    #[test]
+    fn bytes_size_hint() {
+        let (lower, upper) = Bytes::size_hint(0);
+
+        assert!(lower >= 1);
+        // `arbitrary` consumes 8 bytes for `usize` on every target.
+        assert_eq!(upper, Some(1 + size_of::<u64>()));
+    }
+
+    #[test]
+    fn fuzz_bytes() {
+        let data = corpus(0x1234_5678, 0x1_0000);
+        let mut u = Unstructured::new(&data);
+        let mut zero = false;
+        let mut near_max = false;
+        let mut page_multiple = false;
+
+        while !u.is_empty() {
+            let b = Bytes::arbitrary(&mut u).unwrap();
+
+            zero |= b == Bytes::zero();
+            near_max |= b.as_usize() > usize::MAX - 0x100;
+            page_multiple |= b.as_usize() % 0x1000 == 0;
+
+            let pages = b.as_num_of_pages::<Size4KiB>();
+            assert!(pages.as_usize() <= b.as_usize() / 0x1000 + 1);
+        }
+
+        assert!(zero && near_max && page_multiple);
+    }
+
+    #[test]
+    fn fuzz_num_of_pages() {
+        let data = corpus(0x9abc_def0, 0x1_0000);
+        let mut u = Unstructured::new(&data);
+        let max_convertible = usize::MAX / 0x20_0000;
+        let mut near_max = false;
+
+        while !u.is_empty() {
+            let n = NumOfPages::<Size2MiB>::arbitrary(&mut u).unwrap();
+
+            if n.as_usize() <= max_convertible {
+                assert_eq!(
+                    n.checked_as_bytes().unwrap().as_usize() / 0x20_0000,
+                    n.as_usize()
+                );
+            }
+
+            near_max |= n.as_usize() <= max_convertible && n.as_usize() > max_convertible - 0x100;
+        }
+
+        assert!(near_max);
+    }
+
+    #[test]
+    fn fuzz_byte_region() {
+        let data = corpus(0x0fed_cba9, 0x1_0000);
+        let mut u = Unstructured::new(&data);
+        let mut empty = false;
+
+        while !u.is_empty() {
+            let r = ByteRegion::arbitrary(&mut u).unwrap();
+
+            assert_eq!(r.end().checked_sub(r.size()), Some(r.start()));
+            assert!(r.contains_region(r));
+
+            empty |= r.is_empty();
+        }
+
+        assert!(empty);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::Bytes;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// [`Bytes`] which can be shared between threads, like [`AtomicUsize`].
+///
+/// This type has the same in-memory representation as [`AtomicUsize`]. The arithmetic operations
+/// wrap around on overflow, as those of [`AtomicUsize`] do.
+///
+/// ```rust
+/// use core::sync::atomic::Ordering;
+/// use os_units::{AtomicBytes, Bytes};
+///
+/// static ALLOCATED: AtomicBytes = AtomicBytes::new(Bytes::zero());
+///
+/// ALLOCATED.fetch_add(Bytes::new(0x1000), Ordering::Relaxed);
+///
+/// assert_eq!(ALLOCATED.load(Ordering::Relaxed), Bytes::new(0x1000));
+/// ```
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct AtomicBytes(AtomicUsize);
+impl AtomicBytes {
+    /// Creates a new instance.
+    #[must_use]
+    pub const fn new(bytes: Bytes) -> Self {
+        Self(AtomicUsize::new(bytes.as_usize()))
+    }
+
+    /// Consumes the atomic and returns the contained value.
+    #[must_use]
+    pub fn into_inner(self) -> Bytes {
+        Bytes::new(self.0.into_inner())
+    }
+
+    /// Loads the value. See [`AtomicUsize::load`].
+    #[must_use]
+    pub fn load(&self, order: Ordering) -> Bytes {
+        Bytes::new(self.0.load(order))
+    }
+
+    /// Stores `bytes`. See [`AtomicUsize::store`].
+    pub fn store(&self, bytes: Bytes, order: Ordering) {
+        self.0.store(bytes.as_usize(), order);
+    }
+
+    /// Stores `bytes` and returns the previous value. See [`AtomicUsize::swap`].
+    pub fn swap(&self, bytes: Bytes, order: Ordering) -> Bytes {
+        Bytes::new(self.0.swap(bytes.as_usize(), order))
+    }
+
+    /// Adds `bytes` and returns the previous value. See [`AtomicUsize::fetch_add`].
+    pub fn fetch_add(&self, bytes: Bytes, order: Ordering) -> Bytes {
+        Bytes::new(self.0.fetch_add(bytes.as_usize(), order))
+    }
+
+    /// Subtracts `bytes` and returns the previous value. See [`AtomicUsize::fetch_sub`].
+    pub fn fetch_sub(&self, bytes: Bytes, order: Ordering) -> Bytes {
+        Bytes::new(self.0.fetch_sub(bytes.as_usize(), order))
+    }
+
+    /// Stores the maximum of the current value and `bytes`, and returns the previous value. See
+    /// [`AtomicUsize::fetch_max`].
+    pub fn fetch_max(&self, bytes: Bytes, order: Ordering) -> Bytes {
+        Bytes::new(self.0.fetch_max(bytes.as_usize(), order))
+    }
+
+    /// Stores the minimum of the current value and `bytes`, and returns the previous value. See
+    /// [`AtomicUsize::fetch_min`].
+    pub fn fetch_min(&self, bytes: Bytes, order: Ordering) -> Bytes {
+        Bytes::new(self.0.fetch_min(bytes.as_usize(), order))
+    }
+
+    /// Stores `new` if the current value is `current`. See [`AtomicUsize::compare_exchange`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the current value if it is not `current`.
+    pub fn compare_exchange(
+        &self,
+        current: Bytes,
+        new: Bytes,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Bytes, Bytes> {
+        self.0
+            .compare_exchange(current.as_usize(), new.as_usize(), success, failure)
+            .map(Bytes::new)
+            .map_err(Bytes::new)
+    }
+
+    /// Updates the value with `f` until it succeeds or `f` returns [`None`]. See
+    /// [`AtomicUsize::fetch_update`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the current value if `f` returns [`None`].
+    pub fn fetch_update<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<Bytes, Bytes>
+    where
+        F: FnMut(Bytes) -> Option<Bytes>,
+    {
+        self.0
+            .fetch_update(set_order, fetch_order, |b| {
+                f(Bytes::new(b)).map(Bytes::as_usize)
+            })
+            .map(Bytes::new)
+            .map_err(Bytes::new)
+    }
+}
+impl From<Bytes> for AtomicBytes {
+    fn from(bytes: Bytes) -> Self {
+        Self::new(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AtomicBytes;
+    use crate::Bytes;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn load_store() {
+        let a = AtomicBytes::new(Bytes::new(3));
+
+        a.store(Bytes::new(5), Ordering::SeqCst);
+
+        assert_eq!(a.load(Ordering::SeqCst), Bytes::new(5));
+        assert_eq!(a.into_inner(), Bytes::new(5));
+    }
+
+    #[test]
+    fn swap() {
+        let a = AtomicBytes::new(Bytes::new(3));
+
+        assert_eq!(a.swap(Bytes::new(5), Ordering::SeqCst), Bytes::new(3));
+        assert_eq!(a.load(Ordering::SeqCst), Bytes::new(5));
+    }
+
+    #[test]
+    fn fetch_add_sub() {
+        let a = AtomicBytes::new(Bytes::new(3));
+
+        assert_eq!(a.fetch_add(Bytes::new(5), Ordering::SeqCst), Bytes::new(3));
+        assert_eq!(a.fetch_sub(Bytes::new(2), Ordering::SeqCst), Bytes::new(8));
+        assert_eq!(a.load(Ordering::SeqCst), Bytes::new(6));
+    }
+
+    #[test]
+    fn fetch_add_wraps() {
+        let a = AtomicBytes::new(Bytes::new(usize::MAX));
+
+        a.fetch_add(Bytes::new(2), Ordering::SeqCst);
+
+        assert_eq!(a.load(Ordering::SeqCst), Bytes::new(1));
+    }
+
+    #[test]
+    fn fetch_max_min() {
+        let a = AtomicBytes::new(Bytes::new(3));
+
+        assert_eq!(a.fetch_max(Bytes::new(5), Ordering::SeqCst), Bytes::new(3));
+        assert_eq!(a.fetch_max(Bytes::new(4), Ordering::SeqCst), Bytes::new(5));
+        assert_eq!(a.fetch_min(Bytes::new(2), Ordering::SeqCst), Bytes::new(5));
+        assert_eq!(a.load(Ordering::SeqCst), Bytes::new(2));
+    }
+
+    #[test]
+    fn compare_exchange() {
+        let a = AtomicBytes::new(Bytes::new(3));
+
+        let r = a.compare_exchange(
+            Bytes::new(3),
+            Bytes::new(5),
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+        assert_eq!(r, Ok(Bytes::new(3)));
+
+        let r = a.compare_exchange(
+            Bytes::new(3),
+            Bytes::new(7),
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+        assert_eq!(r, Err(Bytes::new(5)));
+    }
+
+    #[test]
+    fn fetch_update() {
+        let a = AtomicBytes::new(Bytes::new(3));
+
+        let r = a.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |b| {
+            b.as_usize().checked_sub(2).map(Bytes::new)
+        });
+        assert_eq!(r, Ok(Bytes::new(3)));
+
+        let r = a.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |b| {
+            b.as_usize().checked_sub(2).map(Bytes::new)
+        });
+        assert_eq!(r, Err(Bytes::new(1)));
+    }
+
+    #[test]
+    fn concurrent_increments() {
+        let a = Arc::new(AtomicBytes::new(Bytes::zero()));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let a = Arc::clone(&a);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        a.fetch_add(Bytes::new(0x10), Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect();
+
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        assert_eq!(a.load(Ordering::SeqCst), Bytes::new(8 * 1000 * 0x10));
+    }
+
+    #[test]
+    fn concurrent_max() {
+        let a = Arc::new(AtomicBytes::new(Bytes::zero()));
+
+        let handles: Vec<_> = (1..=8)
+            .map(|i| {
+                let a = Arc::clone(&a);
+                thread::spawn(move || {
+                    a.fetch_max(Bytes::new(i * 0x1000), Ordering::Relaxed);
+                })
+            })
+            .collect();
+
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        assert_eq!(a.load(Ordering::SeqCst), Bytes::new(0x8000));
+    }
+
+    #[test]
+    fn same_size_as_atomic_usize() {
+        assert_eq!(size_of::<AtomicBytes>(), size_of::<AtomicUsize>());
+    }
+}
@@ -0,0 +1,294 @@
+use crate::NumOfPages;
+use core::fmt;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use x86_64::structures::paging::PageSize;
+
+/// [`NumOfPages`] which can be shared between threads, like [`AtomicUsize`].
+///
+/// This type has the same in-memory representation as [`AtomicUsize`]. The arithmetic operations
+/// wrap around on overflow, as those of [`AtomicUsize`] do.
+///
+/// ```rust
+/// use core::sync::atomic::Ordering;
+/// use os_units::{AtomicNumOfPages, NumOfPages};
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// static FREE_DMA: AtomicNumOfPages<Size4KiB> = AtomicNumOfPages::new(NumOfPages::zero());
+/// static FREE_NORMAL: AtomicNumOfPages<Size4KiB> = AtomicNumOfPages::new(NumOfPages::zero());
+///
+/// FREE_DMA.fetch_add(NumOfPages::new(16), Ordering::Relaxed);
+/// FREE_NORMAL.fetch_add(NumOfPages::new(0x100), Ordering::Relaxed);
+///
+/// assert_eq!(FREE_DMA.load(Ordering::Relaxed), NumOfPages::new(16));
+/// assert_eq!(FREE_NORMAL.load(Ordering::Relaxed), NumOfPages::new(0x100));
+/// ```
+#[repr(transparent)]
+pub struct AtomicNumOfPages<T: PageSize> {
+    num_of_pages: AtomicUsize,
+    _marker: PhantomData<fn() -> T>,
+}
+impl<T: PageSize> AtomicNumOfPages<T> {
+    /// Creates a new instance.
+    #[must_use]
+    pub const fn new(num_of_pages: NumOfPages<T>) -> Self {
+        Self {
+            num_of_pages: AtomicUsize::new(num_of_pages.as_usize()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Consumes the atomic and returns the contained value.
+    #[must_use]
+    pub fn into_inner(self) -> NumOfPages<T> {
+        NumOfPages::new(self.num_of_pages.into_inner())
+    }
+
+    /// Loads the value. See [`AtomicUsize::load`].
+    #[must_use]
+    pub fn load(&self, order: Ordering) -> NumOfPages<T> {
+        NumOfPages::new(self.num_of_pages.load(order))
+    }
+
+    /// Stores `num_of_pages`. See [`AtomicUsize::store`].
+    pub fn store(&self, num_of_pages: NumOfPages<T>, order: Ordering) {
+        self.num_of_pages.store(num_of_pages.as_usize(), order);
+    }
+
+    /// Stores `num_of_pages` and returns the previous value. See [`AtomicUsize::swap`].
+    pub fn swap(&self, num_of_pages: NumOfPages<T>, order: Ordering) -> NumOfPages<T> {
+        NumOfPages::new(self.num_of_pages.swap(num_of_pages.as_usize(), order))
+    }
+
+    /// Adds `num_of_pages` and returns the previous value. See [`AtomicUsize::fetch_add`].
+    pub fn fetch_add(&self, num_of_pages: NumOfPages<T>, order: Ordering) -> NumOfPages<T> {
+        NumOfPages::new(self.num_of_pages.fetch_add(num_of_pages.as_usize(), order))
+    }
+
+    /// Subtracts `num_of_pages` and returns the previous value. See [`AtomicUsize::fetch_sub`].
+    pub fn fetch_sub(&self, num_of_pages: NumOfPages<T>, order: Ordering) -> NumOfPages<T> {
+        NumOfPages::new(self.num_of_pages.fetch_sub(num_of_pages.as_usize(), order))
+    }
+
+    /// Stores the maximum of the current value and `num_of_pages`, and returns the previous value.
+    /// See [`AtomicUsize::fetch_max`].
+    pub fn fetch_max(&self, num_of_pages: NumOfPages<T>, order: Ordering) -> NumOfPages<T> {
+        NumOfPages::new(self.num_of_pages.fetch_max(num_of_pages.as_usize(), order))
+    }
+
+    /// Stores the minimum of the current value and `num_of_pages`, and returns the previous value.
+    /// See [`AtomicUsize::fetch_min`].
+    pub fn fetch_min(&self, num_of_pages: NumOfPages<T>, order: Ordering) -> NumOfPages<T> {
+        NumOfPages::new(self.num_of_pages.fetch_min(num_of_pages.as_usize(), order))
+    }
+
+    /// Stores `new` if the current value is `current`. See [`AtomicUsize::compare_exchange`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the current value if it is not `current`.
+    pub fn compare_exchange(
+        &self,
+        current: NumOfPages<T>,
+        new: NumOfPages<T>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<NumOfPages<T>, NumOfPages<T>> {
+        self.num_of_pages
+            .compare_exchange(current.as_usize(), new.as_usize(), success, failure)
+            .map(NumOfPages::new)
+            .map_err(NumOfPages::new)
+    }
+
+    /// Updates the value with `f` until it succeeds or `f` returns [`None`]. See
+    /// [`AtomicUsize::fetch_update`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the current value if `f` returns [`None`].
+    pub fn fetch_update<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<NumOfPages<T>, NumOfPages<T>>
+    where
+        F: FnMut(NumOfPages<T>) -> Option<NumOfPages<T>>,
+    {
+        self.num_of_pages
+            .fetch_update(set_order, fetch_order, |n| {
+                f(NumOfPages::new(n)).map(NumOfPages::as_usize)
+            })
+            .map(NumOfPages::new)
+            .map_err(NumOfPages::new)
+    }
+}
+impl<T: PageSize> From<NumOfPages<T>> for AtomicNumOfPages<T> {
+    fn from(num_of_pages: NumOfPages<T>) -> Self {
+        Self::new(num_of_pages)
+    }
+}
+impl<T: PageSize> fmt::Debug for AtomicNumOfPages<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AtomicNumOfPages")
+            .field(&self.num_of_pages)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AtomicNumOfPages;
+    use crate::NumOfPages;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    #[test]
+    fn load_store() {
+        let a = AtomicNumOfPages::<Size4KiB>::new(NumOfPages::new(3));
+
+        a.store(NumOfPages::new(5), Ordering::SeqCst);
+
+        assert_eq!(a.load(Ordering::SeqCst), NumOfPages::new(5));
+        assert_eq!(a.into_inner(), NumOfPages::new(5));
+    }
+
+    #[test]
+    fn fetch_add_sub_return_previous() {
+        let a = AtomicNumOfPages::<Size2MiB>::new(NumOfPages::new(3));
+
+        assert_eq!(
+            a.fetch_add(NumOfPages::new(5), Ordering::SeqCst),
+            NumOfPages::new(3)
+        );
+        assert_eq!(
+            a.fetch_sub(NumOfPages::new(2), Ordering::SeqCst),
+            NumOfPages::new(8)
+        );
+        assert_eq!(a.load(Ordering::SeqCst), NumOfPages::new(6));
+    }
+
+    #[test]
+    fn swap_max_min() {
+        let a = AtomicNumOfPages::<Size4KiB>::new(NumOfPages::new(3));
+
+        assert_eq!(
+            a.swap(NumOfPages::new(4), Ordering::SeqCst),
+            NumOfPages::new(3)
+        );
+        assert_eq!(
+            a.fetch_max(NumOfPages::new(6), Ordering::SeqCst),
+            NumOfPages::new(4)
+        );
+        assert_eq!(
+            a.fetch_min(NumOfPages::new(1), Ordering::SeqCst),
+            NumOfPages::new(6)
+        );
+        assert_eq!(a.load(Ordering::SeqCst), NumOfPages::new(1));
+    }
+
+    #[test]
+    fn compare_exchange() {
+        let a = AtomicNumOfPages::<Size4KiB>::new(NumOfPages::new(3));
+
+        let r = a.compare_exchange(
+            NumOfPages::new(3),
+            NumOfPages::new(5),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+        assert_eq!(r, Ok(NumOfPages::new(3)));
+
+        let r = a.compare_exchange(
+            NumOfPages::new(3),
+            NumOfPages::new(7),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        );
+        assert_eq!(r, Err(NumOfPages::new(5)));
+    }
+
+    #[test]
+    fn fetch_update() {
+        let a = AtomicNumOfPages::<Size4KiB>::new(NumOfPages::new(3));
+
+        let take_two = |n: NumOfPages<Size4KiB>| n.as_usize().checked_sub(2).map(NumOfPages::new);
+
+        assert_eq!(
+            a.fetch_update(Ordering::SeqCst, Ordering::SeqCst, take_two),
+            Ok(NumOfPages::new(3))
+        );
+        assert_eq!(
+            a.fetch_update(Ordering::SeqCst, Ordering::SeqCst, take_two),
+            Err(NumOfPages::new(1))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "there is no such thing as a release load")]
+    fn load_passes_ordering_through() {
+        let a = AtomicNumOfPages::<Size4KiB>::new(NumOfPages::new(3));
+
+        let _ = a.load(Ordering::Release);
+    }
+
+    #[test]
+    #[should_panic(expected = "there is no such thing as an acquire store")]
+    fn store_passes_ordering_through() {
+        let a = AtomicNumOfPages::<Size4KiB>::new(NumOfPages::new(3));
+
+        a.store(NumOfPages::new(5), Ordering::Acquire);
+    }
+
+    #[test]
+    #[should_panic(expected = "there is no such thing as a release failure ordering")]
+    fn compare_exchange_passes_failure_ordering_through() {
+        let a = AtomicNumOfPages::<Size4KiB>::new(NumOfPages::new(3));
+
+        let _ = a.compare_exchange(
+            NumOfPages::new(3),
+            NumOfPages::new(5),
+            Ordering::SeqCst,
+            Ordering::Release,
+        );
+    }
+
+    #[test]
+    fn concurrent_updates() {
+        let a = Arc::new(AtomicNumOfPages::<Size4KiB>::new(NumOfPages::new(8000)));
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let a = Arc::clone(&a);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        if i % 2 == 0 {
+                            a.fetch_add(NumOfPages::new(3), Ordering::Relaxed);
+                        } else {
+                            a.fetch_sub(NumOfPages::new(1), Ordering::Relaxed);
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for h in handles {
+            h.join().unwrap();
+        }
+
+        assert_eq!(
+            a.load(Ordering::SeqCst),
+            NumOfPages::new(8000 + 4 * 3000 - 4 * 1000)
+        );
+    }
+
+    #[test]
+    fn same_size_as_atomic_usize() {
+        assert_eq!(
+            size_of::<AtomicNumOfPages<Size4KiB>>(),
+            size_of::<AtomicUsize>()
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use crate::Bytes;
+use crate::ConversionError;
+use core::convert::TryFrom;
+use core::fmt;
+#[cfg(not(feature = "no-panic"))]
+use {
+    core::iter::Sum,
+    core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+};
+
+macro_rules! binary_unit {
+    ($name:ident, $shift:expr, $unit:expr, $example:expr) => {
+        #[doc = concat!("A number of ", $unit, ", for the fields which carry the unit in the type.")]
+        ///
+        /// The conversions to and from [`Bytes`] are [`TryFrom`] in both directions, because
+        /// converting into [`Bytes`] may overflow `usize` and converting from [`Bytes`] may lose
+        /// the bytes below the unit.
+        ///
+        /// ```rust
+        /// use core::convert::TryFrom;
+        #[doc = concat!("use os_units::{Bytes, ", stringify!($name), "};")]
+        ///
+        #[doc = concat!("let n = ", stringify!($name), "::new(3);")]
+        ///
+        #[doc = concat!("assert_eq!(Bytes::try_from(n), Ok(Bytes::new(", $example, ")));")]
+        #[doc = concat!("assert_eq!(", stringify!($name), "::try_from(Bytes::new(", $example, ")), Ok(n));")]
+        #[doc = concat!("assert_eq!(n.to_string(), \"3 ", $unit, "\");")]
+        /// ```
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        pub struct $name(usize);
+        impl $name {
+            /// Creates a new instance with the given value.
+            #[must_use]
+            pub const fn new(n: usize) -> Self {
+                Self(n)
+            }
+
+            /// Returns an instance with the value 0.
+            #[must_use]
+            pub const fn zero() -> Self {
+                Self(0)
+            }
+
+            /// Returns the value as `usize`.
+            #[must_use]
+            pub const fn as_usize(self) -> usize {
+                self.0
+            }
+        }
+        #[cfg(not(feature = "no-panic"))]
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+        #[cfg(not(feature = "no-panic"))]
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+        #[cfg(not(feature = "no-panic"))]
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+        #[cfg(not(feature = "no-panic"))]
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+        #[cfg(not(feature = "no-panic"))]
+        impl Mul<usize> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: usize) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+        #[cfg(not(feature = "no-panic"))]
+        impl MulAssign<usize> for $name {
+            fn mul_assign(&mut self, rhs: usize) {
+                *self = *self * rhs;
+            }
+        }
+        #[cfg(not(feature = "no-panic"))]
+        impl Div<usize> for $name {
+            type Output = Self;
+
+            fn div(self, rhs: usize) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+        #[cfg(not(feature = "no-panic"))]
+        impl DivAssign<usize> for $name {
+            fn div_assign(&mut self, rhs: usize) {
+                *self = *self / rhs;
+            }
+        }
+        #[cfg(not(feature = "no-panic"))]
+        impl Sum for $name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::zero(), Add::add)
+            }
+        }
+        impl TryFrom<$name> for Bytes {
+            type Error = ConversionError;
+
+            /// Returns [`ConversionError::Overflow`] if the bytes do not fit in `usize`.
+            fn try_from(n: $name) -> Result<Self, Self::Error> {
+                n.0.checked_mul(1 << $shift)
+                    .map(Bytes::new)
+                    .ok_or(ConversionError::Overflow)
+            }
+        }
+        impl TryFrom<Bytes> for $name {
+            type Error = ConversionError;
+
+            /// Returns [`ConversionError::NotAMultiple`] if `bytes` is not a multiple of the unit.
+            fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
+                let remainder = bytes.as_usize() & ((1 << $shift) - 1);
+
+                if remainder == 0 {
+                    Ok(Self(bytes.as_usize() >> $shift))
+                } else {
+                    Err(ConversionError::NotAMultiple {
+                        remainder: Bytes::new(remainder),
+                    })
+                }
+            }
+        }
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.0, $unit)
+            }
+        }
+    };
+}
+
+binary_unit!(Kibibytes, 10, "KiB", "0xc00");
+binary_unit!(Mebibytes, 20, "MiB", "0x30_0000");
+binary_unit!(Gibibytes, 30, "GiB", "0xc000_0000");
+
+#[cfg(test)]
+mod tests {
+    use super::{Gibibytes, Kibibytes, Mebibytes};
+    use crate::{Bytes, ConversionError};
+    use core::convert::TryFrom;
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn arithmetic() {
+        let mut k = Kibibytes::new(3) + Kibibytes::new(5);
+
+        assert_eq!(k, Kibibytes::new(8));
+        assert_eq!(k - Kibibytes::new(2), Kibibytes::new(6));
+        assert_eq!(k * 3, Kibibytes::new(24));
+        assert_eq!(k / 3, Kibibytes::new(2));
+
+        k += Kibibytes::new(1);
+        k -= Kibibytes::new(3);
+        k *= 2;
+        k /= 4;
+
+        assert_eq!(k, Kibibytes::new(3));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sum() {
+        let total: Mebibytes = [1, 2, 3].iter().map(|&n| Mebibytes::new(n)).sum();
+
+        assert_eq!(total, Mebibytes::new(6));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Kibibytes::new(4)), "4 KiB");
+        assert_eq!(format!("{}", Mebibytes::new(0)), "0 MiB");
+        assert_eq!(format!("{}", Gibibytes::new(16)), "16 GiB");
+    }
+
+    #[test]
+    fn into_bytes() {
+        assert_eq!(Bytes::try_from(Kibibytes::new(1)), Ok(Bytes::new(0x400)));
+        assert_eq!(
+            Bytes::try_from(Mebibytes::new(2)),
+            Ok(Bytes::new(0x20_0000))
+        );
+        assert_eq!(
+            Bytes::try_from(Gibibytes::new(1)),
+            Ok(Bytes::new(0x4000_0000))
+        );
+        assert_eq!(Bytes::try_from(Gibibytes::zero()), Ok(Bytes::zero()));
+    }
+
+    #[test]
+    fn into_bytes_overflow() {
+        assert_eq!(
+            Bytes::try_from(Kibibytes::new(usize::MAX >> 9)),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(
+            Bytes::try_from(Kibibytes::new(usize::MAX >> 10)),
+            Ok(Bytes::new(usize::MAX & !0x3ff))
+        );
+        assert_eq!(
+            Bytes::try_from(Gibibytes::new(usize::MAX >> 29)),
+            Err(ConversionError::Overflow)
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn into_bytes_overflow_32bit() {
+        assert_eq!(
+            Bytes::try_from(Gibibytes::new(4)),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(
+            Bytes::try_from(Mebibytes::new(4096)),
+            Err(ConversionError::Overflow)
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn into_bytes_4gib_64bit() {
+        assert_eq!(
+            Bytes::try_from(Gibibytes::new(4)),
+            Ok(Bytes::new(0x1_0000_0000))
+        );
+    }
+
+    #[test]
+    fn from_bytes() {
+        assert_eq!(
+            Kibibytes::try_from(Bytes::new(0x1000)),
+            Ok(Kibibytes::new(4))
+        );
+        assert_eq!(
+            Gibibytes::try_from(Bytes::new(0x8000_0000)),
+            Ok(Gibibytes::new(2))
+        );
+        assert_eq!(Mebibytes::try_from(Bytes::zero()), Ok(Mebibytes::zero()));
+    }
+
+    #[test]
+    fn from_bytes_not_a_multiple() {
+        assert_eq!(
+            Kibibytes::try_from(Bytes::new(0x1001)),
+            Err(ConversionError::NotAMultiple {
+                remainder: Bytes::new(1)
+            })
+        );
+        assert_eq!(
+            Mebibytes::try_from(Bytes::new(0x10_0400)),
+            Err(ConversionError::NotAMultiple {
+                remainder: Bytes::new(0x400)
+            })
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for n in [0, 1, 0x3ff, 0x400, 0xfff] {
+            let k = Kibibytes::new(n);
+            let m = Mebibytes::new(n);
+
+            assert_eq!(Kibibytes::try_from(Bytes::try_from(k).unwrap()), Ok(k));
+            assert_eq!(Mebibytes::try_from(Bytes::try_from(m).unwrap()), Ok(m));
+        }
+
+        let g = Gibibytes::new(usize::MAX >> 30);
+
+        assert_eq!(Gibibytes::try_from(Bytes::try_from(g).unwrap()), Ok(g));
+    }
+}
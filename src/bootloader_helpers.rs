@@ -0,0 +1,181 @@
+//! Conversions from the memory map of the [`bootloader_api`](https://docs.rs/bootloader_api)
+//! crate.
+//!
+//! [`Bytes`], [`NumOfPages<Size4KiB>`], and [`PhysRegion`] can be created from a
+//! [`MemoryRegion`]. The addresses of a region are `u64`, so the conversions are fallible and fail
+//! with [`ConversionError::Overflow`] instead of truncating.
+//!
+//! ```rust
+//! use bootloader_api::info::{MemoryRegion, MemoryRegionKind};
+//! use core::convert::TryFrom;
+//! use os_units::{bootloader_helpers, Bytes, NumOfPages};
+//! use x86_64::structures::paging::Size4KiB;
+//!
+//! let regions = [
+//!     MemoryRegion { start: 0x1000, end: 0x9000, kind: MemoryRegionKind::Usable },
+//!     MemoryRegion { start: 0x9000, end: 0xa000, kind: MemoryRegionKind::Bootloader },
+//! ];
+//!
+//! assert_eq!(Bytes::try_from(regions[0]), Ok(Bytes::new(0x8000)));
+//! assert_eq!(NumOfPages::<Size4KiB>::try_from(regions[0]), Ok(NumOfPages::new(8)));
+//! assert_eq!(bootloader_helpers::usable_bytes(&regions), Some(Bytes::new(0x8000)));
+//! ```
+
+use crate::Bytes;
+use crate::ConversionError;
+use crate::NumOfPages;
+use crate::PhysRegion;
+use crate::RegionError;
+use bootloader_api::info::{MemoryRegion, MemoryRegionKind};
+use core::convert::TryFrom;
+use x86_64::structures::paging::{PageSize, Size4KiB};
+use x86_64::PhysAddr;
+
+/// Returns the sum of the sizes of the [`MemoryRegionKind::Usable`] regions.
+///
+/// `MemoryRegions` of the boot information can be passed directly as it dereferences to a slice.
+///
+/// This function returns [`None`] if the size of a region or the sum does not fit in `usize`.
+#[must_use]
+pub fn usable_bytes(regions: &[MemoryRegion]) -> Option<Bytes> {
+    regions
+        .iter()
+        .filter(|r| r.kind == MemoryRegionKind::Usable)
+        .try_fold(Bytes::zero(), |sum, r| {
+            sum.as_usize()
+                .checked_add(Bytes::try_from(*r).ok()?.as_usize())
+                .map(Bytes::new)
+        })
+}
+
+/// The size of the region. A region whose end is below its start has no bytes.
+impl TryFrom<MemoryRegion> for Bytes {
+    type Error = ConversionError;
+
+    fn try_from(r: MemoryRegion) -> Result<Self, Self::Error> {
+        Self::try_from(r.end.saturating_sub(r.start))
+    }
+}
+/// The number of the whole frames in the region. A partial frame at either end is not counted.
+impl TryFrom<MemoryRegion> for NumOfPages<Size4KiB> {
+    type Error = ConversionError;
+
+    fn try_from(r: MemoryRegion) -> Result<Self, Self::Error> {
+        let start = r.start.div_ceil(Size4KiB::SIZE);
+        let end = r.end / Size4KiB::SIZE;
+
+        Ok(Self::new(usize::try_from(end.saturating_sub(start))?))
+    }
+}
+/// Fails with [`RegionError::InvalidAddress`] if either end is not a valid physical address,
+/// and with [`RegionError::EndBelowStart`] if the end is below the start.
+impl TryFrom<MemoryRegion> for PhysRegion {
+    type Error = RegionError;
+
+    fn try_from(r: MemoryRegion) -> Result<Self, Self::Error> {
+        let start = PhysAddr::try_new(r.start).map_err(|_| RegionError::InvalidAddress)?;
+        let end = PhysAddr::try_new(r.end).map_err(|_| RegionError::InvalidAddress)?;
+
+        Self::from_start_end(start, end).ok_or(RegionError::EndBelowStart)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::usable_bytes;
+    use crate::{Bytes, NumOfPages, PhysRegion, RegionError};
+    use bootloader_api::info::{MemoryRegion, MemoryRegionKind};
+    use core::convert::TryFrom;
+    use x86_64::structures::paging::Size4KiB;
+    use x86_64::PhysAddr;
+
+    fn region(start: u64, end: u64, kind: MemoryRegionKind) -> MemoryRegion {
+        MemoryRegion { start, end, kind }
+    }
+
+    #[test]
+    fn bytes() {
+        let r = region(0x1000, 0x3800, MemoryRegionKind::Usable);
+
+        assert_eq!(Bytes::try_from(r), Ok(Bytes::new(0x2800)));
+    }
+
+    #[test]
+    fn bytes_of_reversed_region() {
+        let r = region(0x3000, 0x1000, MemoryRegionKind::Usable);
+
+        assert_eq!(Bytes::try_from(r), Ok(Bytes::zero()));
+    }
+
+    #[test]
+    fn num_of_pages() {
+        let r = region(0x1000, 0x3000, MemoryRegionKind::Usable);
+
+        assert_eq!(NumOfPages::<Size4KiB>::try_from(r), Ok(NumOfPages::new(2)));
+    }
+
+    #[test]
+    fn num_of_pages_ignores_partial_frames() {
+        let r = region(0x1800, 0x4800, MemoryRegionKind::Usable);
+
+        assert_eq!(NumOfPages::<Size4KiB>::try_from(r), Ok(NumOfPages::new(2)));
+    }
+
+    #[test]
+    fn num_of_pages_within_one_frame() {
+        let r = region(0x1800, 0x1900, MemoryRegionKind::Usable);
+
+        assert_eq!(NumOfPages::<Size4KiB>::try_from(r), Ok(NumOfPages::new(0)));
+    }
+
+    #[test]
+    fn phys_region() {
+        let r = PhysRegion::try_from(region(0x1000, 0x3000, MemoryRegionKind::Usable));
+        let expected = PhysRegion::new(PhysAddr::new(0x1000), Bytes::new(0x2000)).unwrap();
+
+        assert_eq!(r, Ok(expected));
+    }
+
+    #[test]
+    fn phys_region_out_of_range() {
+        let r = PhysRegion::try_from(region(0x1000, 1 << 52, MemoryRegionKind::Usable));
+
+        assert_eq!(r, Err(RegionError::InvalidAddress));
+    }
+
+    #[test]
+    fn phys_region_reversed() {
+        let r = PhysRegion::try_from(region(0x3000, 0x1000, MemoryRegionKind::Usable));
+
+        assert_eq!(r, Err(RegionError::EndBelowStart));
+    }
+
+    #[test]
+    fn usable_bytes_of_map() {
+        let map = [
+            region(0, 0x1000, MemoryRegionKind::Bootloader),
+            region(0x1000, 0x9f000, MemoryRegionKind::Usable),
+            region(0x9f000, 0x10_0000, MemoryRegionKind::UnknownBios(2)),
+            region(0x10_0000, 0x20_0000, MemoryRegionKind::Usable),
+            region(0x20_0000, 0x30_0000, MemoryRegionKind::UnknownUefi(3)),
+        ];
+
+        assert_eq!(usable_bytes(&map), Some(Bytes::new(0x9e000 + 0x10_0000)));
+    }
+
+    #[test]
+    fn usable_bytes_of_empty_map() {
+        assert_eq!(usable_bytes(&[]), Some(Bytes::zero()));
+    }
+
+    #[test]
+    fn usable_bytes_overflow() {
+        let map = [
+            region(0, 1 << 63, MemoryRegionKind::Usable),
+            region(1 << 63, u64::MAX, MemoryRegionKind::Usable),
+            region(0, 0x1000, MemoryRegionKind::Usable),
+        ];
+
+        assert_eq!(usable_bytes(&map), None);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{Alignment, ByteRegion, Bytes};
+
+/// A bump-pointer cursor which carves blocks out of a [`ByteRegion`].
+///
+/// This type only computes offsets, and never accesses memory. Add the returned offsets to the
+/// base address of the memory to allocate from.
+///
+/// ```rust
+/// use os_units::{Alignment, BumpOffset, ByteRegion, Bytes};
+///
+/// let region = ByteRegion::new(Bytes::new(0x1001), Bytes::new(0x1fff)).unwrap();
+/// let mut bump = BumpOffset::new(region);
+///
+/// let page = Alignment::new(0x1000).unwrap();
+///
+/// assert_eq!(bump.alloc(Bytes::new(0x10), page), Some(Bytes::new(0x2000)));
+/// assert_eq!(bump.alloc(Bytes::new(0x10), Alignment::ONE), Some(Bytes::new(0x2010)));
+/// assert_eq!(bump.alloc(Bytes::new(0x10), page), None);
+/// assert_eq!(bump.remaining(), Bytes::new(0xfe0));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BumpOffset {
+    region: ByteRegion,
+    current: Bytes,
+}
+impl BumpOffset {
+    /// Creates a new cursor at the start of `region`.
+    #[must_use]
+    pub const fn new(region: ByteRegion) -> Self {
+        Self {
+            region,
+            current: region.start(),
+        }
+    }
+
+    /// Returns the region to allocate from.
+    #[must_use]
+    pub const fn region(&self) -> ByteRegion {
+        self.region
+    }
+
+    /// Returns the offset where the next block is searched from.
+    #[must_use]
+    pub const fn current(&self) -> Bytes {
+        self.current
+    }
+
+    /// Returns the bytes between the cursor and the end of the region.
+    ///
+    /// The next allocation may get fewer bytes than this because of the alignment padding.
+    #[must_use]
+    pub const fn remaining(&self) -> Bytes {
+        Bytes::new(self.region.end().as_usize() - self.current.as_usize())
+    }
+
+    /// Carves `size` bytes aligned to `align`, and returns the offset of the block.
+    ///
+    /// The padding before the block is skipped and never reused. A zero-sized request returns
+    /// the aligned offset and only moves the cursor past the padding.
+    ///
+    /// This method returns [`None`] and leaves the cursor unchanged if the block does not fit in
+    /// the rest of the region.
+    pub fn alloc(&mut self, size: Bytes, align: Alignment) -> Option<Bytes> {
+        let start = align.align_up(self.current)?;
+        let end = start.as_usize().checked_add(size.as_usize())?;
+
+        if end > self.region.end().as_usize() {
+            return None;
+        }
+
+        self.current = Bytes::new(end);
+
+        Some(start)
+    }
+
+    /// Moves the cursor back to the start of the region, making all the bytes available again.
+    pub fn reset(&mut self) {
+        self.current = self.region.start();
+    }
+}
+impl From<ByteRegion> for BumpOffset {
+    fn from(region: ByteRegion) -> Self {
+        Self::new(region)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BumpOffset;
+    use crate::{Alignment, ByteRegion, Bytes};
+
+    fn bump(start: usize, end: usize) -> BumpOffset {
+        BumpOffset::new(ByteRegion::from_start_end(Bytes::new(start), Bytes::new(end)).unwrap())
+    }
+
+    fn align(a: usize) -> Alignment {
+        Alignment::new(a).unwrap()
+    }
+
+    #[test]
+    fn alloc_unaligned() {
+        let mut b = bump(0, 0x100);
+
+        assert_eq!(b.alloc(Bytes::new(3), Alignment::ONE), Some(Bytes::zero()));
+        assert_eq!(b.alloc(Bytes::new(5), Alignment::ONE), Some(Bytes::new(3)));
+        assert_eq!(b.current(), Bytes::new(8));
+        assert_eq!(b.remaining(), Bytes::new(0xf8));
+    }
+
+    #[test]
+    fn alloc_pads_to_alignment() {
+        let mut b = bump(0, 0x100);
+
+        b.alloc(Bytes::new(1), Alignment::ONE);
+
+        assert_eq!(b.alloc(Bytes::new(8), align(8)), Some(Bytes::new(8)));
+        assert_eq!(b.alloc(Bytes::new(1), align(0x10)), Some(Bytes::new(0x10)));
+        assert_eq!(b.alloc(Bytes::new(1), align(0x10)), Some(Bytes::new(0x20)));
+        assert_eq!(b.current(), Bytes::new(0x21));
+    }
+
+    #[test]
+    fn alloc_aligned_start_needs_no_padding() {
+        let mut b = bump(0x40, 0x100);
+
+        assert_eq!(
+            b.alloc(Bytes::new(0x10), align(0x40)),
+            Some(Bytes::new(0x40))
+        );
+    }
+
+    #[test]
+    fn alloc_exact_fit() {
+        let mut b = bump(0, 0x100);
+
+        assert_eq!(
+            b.alloc(Bytes::new(0x100), Alignment::ONE),
+            Some(Bytes::zero())
+        );
+        assert_eq!(b.remaining(), Bytes::zero());
+        assert_eq!(b.alloc(Bytes::new(1), Alignment::ONE), None);
+    }
+
+    #[test]
+    fn alloc_exhausted() {
+        let mut b = bump(0, 0x100);
+
+        b.alloc(Bytes::new(0xf0), Alignment::ONE);
+
+        assert_eq!(b.alloc(Bytes::new(0x11), Alignment::ONE), None);
+        assert_eq!(b.current(), Bytes::new(0xf0));
+        assert_eq!(
+            b.alloc(Bytes::new(0x10), Alignment::ONE),
+            Some(Bytes::new(0xf0))
+        );
+    }
+
+    #[test]
+    fn alloc_padding_exhausts() {
+        let mut b = bump(0, 0x100);
+
+        b.alloc(Bytes::new(1), Alignment::ONE);
+
+        assert_eq!(b.alloc(Bytes::new(1), align(0x100)), None);
+        assert_eq!(b.current(), Bytes::new(1));
+    }
+
+    #[test]
+    fn alloc_zero_sized() {
+        let mut b = bump(0, 0x100);
+
+        b.alloc(Bytes::new(1), Alignment::ONE);
+
+        assert_eq!(b.alloc(Bytes::zero(), align(0x10)), Some(Bytes::new(0x10)));
+        assert_eq!(b.alloc(Bytes::zero(), align(0x10)), Some(Bytes::new(0x10)));
+        assert_eq!(b.current(), Bytes::new(0x10));
+    }
+
+    #[test]
+    fn alloc_zero_sized_at_end() {
+        let mut b = bump(0, 0x100);
+
+        b.alloc(Bytes::new(0x100), Alignment::ONE);
+
+        assert_eq!(
+            b.alloc(Bytes::zero(), Alignment::ONE),
+            Some(Bytes::new(0x100))
+        );
+        assert_eq!(b.alloc(Bytes::zero(), align(0x200)), None);
+    }
+
+    #[test]
+    fn alloc_does_not_overflow() {
+        let mut b = bump(usize::MAX - 0xf, usize::MAX);
+
+        assert_eq!(b.alloc(Bytes::new(1), align(0x20)), None);
+        assert_eq!(b.alloc(Bytes::new(usize::MAX), Alignment::ONE), None);
+        assert_eq!(
+            b.alloc(Bytes::new(0xf), Alignment::ONE),
+            Some(Bytes::new(usize::MAX - 0xf))
+        );
+    }
+
+    #[test]
+    fn reset() {
+        let mut b = bump(0x10, 0x100);
+
+        b.alloc(Bytes::new(0x80), Alignment::ONE);
+        b.reset();
+
+        assert_eq!(b.current(), Bytes::new(0x10));
+        assert_eq!(b.remaining(), Bytes::new(0xf0));
+        assert_eq!(
+            b.alloc(Bytes::new(0x10), Alignment::ONE),
+            Some(Bytes::new(0x10))
+        );
+    }
+}
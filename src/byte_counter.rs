@@ -0,0 +1,199 @@
+use crate::AtomicBytes;
+use crate::Bytes;
+use core::sync::atomic::Ordering;
+
+/// A counter of bytes in use which also tracks the peak.
+///
+/// All the methods take `&self`, so a counter can be shared between threads.
+///
+/// ```rust
+/// use os_units::{ByteCounter, Bytes};
+///
+/// static HEAP: ByteCounter = ByteCounter::new();
+///
+/// HEAP.add(Bytes::new(0x3000));
+/// HEAP.sub(Bytes::new(0x2000));
+///
+/// assert_eq!(HEAP.current(), Bytes::new(0x1000));
+/// assert_eq!(HEAP.peak(), Bytes::new(0x3000));
+/// ```
+#[derive(Debug)]
+pub struct ByteCounter {
+    current: AtomicBytes,
+    peak: AtomicBytes,
+}
+impl ByteCounter {
+    /// Creates a new counter with zero bytes in use.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            current: AtomicBytes::new(Bytes::zero()),
+            peak: AtomicBytes::new(Bytes::zero()),
+        }
+    }
+
+    /// Adds `bytes` to the current value, saturating at `usize::MAX`, and updates the peak.
+    ///
+    /// This method returns the new current value. The peak is at least this value when this
+    /// method returns.
+    pub fn add(&self, bytes: Bytes) -> Bytes {
+        let new = self.update(|c| c.saturating_add(bytes.as_usize()));
+
+        self.peak.fetch_max(new, Ordering::AcqRel);
+
+        new
+    }
+
+    /// Subtracts `bytes` from the current value, saturating at zero.
+    ///
+    /// This method returns the new current value.
+    pub fn sub(&self, bytes: Bytes) -> Bytes {
+        self.update(|c| c.saturating_sub(bytes.as_usize()))
+    }
+
+    /// Returns the current value.
+    #[must_use]
+    pub fn current(&self) -> Bytes {
+        self.current.load(Ordering::Acquire)
+    }
+
+    /// Returns the largest value the counter has reached since it was created or
+    /// [`reset_peak`](Self::reset_peak) was called.
+    ///
+    /// The peak is updated right after the current value, so it may lag behind an
+    /// [`add`](Self::add) running on another thread.
+    #[must_use]
+    pub fn peak(&self) -> Bytes {
+        self.peak.load(Ordering::Acquire)
+    }
+
+    /// Sets the peak to the current value, and returns the previous peak.
+    pub fn reset_peak(&self) -> Bytes {
+        let previous = self.peak.swap(self.current(), Ordering::AcqRel);
+
+        // `add` on another thread may have raised the peak between the load and the swap.
+        self.peak.fetch_max(self.current(), Ordering::AcqRel);
+
+        previous
+    }
+
+    fn update(&self, mut f: impl FnMut(usize) -> usize) -> Bytes {
+        let result = self
+            .current
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |c| {
+                Some(Bytes::new(f(c.as_usize())))
+            });
+
+        // The closure always returns `Some`.
+        let previous = match result {
+            Ok(b) | Err(b) => b,
+        };
+
+        Bytes::new(f(previous.as_usize()))
+    }
+}
+impl Default for ByteCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteCounter;
+    use crate::Bytes;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn add_sub() {
+        let c = ByteCounter::new();
+
+        assert_eq!(c.add(Bytes::new(0x3000)), Bytes::new(0x3000));
+        assert_eq!(c.sub(Bytes::new(0x1000)), Bytes::new(0x2000));
+        assert_eq!(c.current(), Bytes::new(0x2000));
+        assert_eq!(c.peak(), Bytes::new(0x3000));
+    }
+
+    #[test]
+    fn sub_saturates_at_zero() {
+        let c = ByteCounter::new();
+
+        c.add(Bytes::new(0x1000));
+
+        assert_eq!(c.sub(Bytes::new(0x2000)), Bytes::zero());
+        assert_eq!(c.current(), Bytes::zero());
+    }
+
+    #[test]
+    fn add_saturates_at_max() {
+        let c = ByteCounter::new();
+
+        c.add(Bytes::new(usize::MAX));
+
+        assert_eq!(c.add(Bytes::new(1)), Bytes::new(usize::MAX));
+        assert_eq!(c.peak(), Bytes::new(usize::MAX));
+    }
+
+    #[test]
+    fn peak_keeps_high_water_mark() {
+        let c = ByteCounter::new();
+
+        c.add(Bytes::new(0x1000));
+        c.add(Bytes::new(0x4000));
+        c.sub(Bytes::new(0x5000));
+        c.add(Bytes::new(0x2000));
+
+        assert_eq!(c.peak(), Bytes::new(0x5000));
+    }
+
+    #[test]
+    fn reset_peak() {
+        let c = ByteCounter::new();
+
+        c.add(Bytes::new(0x5000));
+        c.sub(Bytes::new(0x3000));
+
+        assert_eq!(c.reset_peak(), Bytes::new(0x5000));
+        assert_eq!(c.peak(), Bytes::new(0x2000));
+
+        c.add(Bytes::new(0x1000));
+
+        assert_eq!(c.peak(), Bytes::new(0x3000));
+    }
+
+    #[test]
+    fn concurrent_peak_is_at_least_every_observed_current() {
+        let c = Arc::new(ByteCounter::new());
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let c = Arc::clone(&c);
+                thread::spawn(move || {
+                    let mut max_seen = Bytes::zero();
+
+                    for j in 0..1000 {
+                        let size = Bytes::new((i + 1) * 0x10 + j % 7);
+                        let current = c.add(size);
+
+                        assert!(c.peak() >= current);
+                        max_seen = max_seen.max(current).max(c.current());
+
+                        c.sub(size);
+                    }
+
+                    max_seen
+                })
+            })
+            .collect();
+
+        let max_seen = handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .max()
+            .unwrap();
+
+        assert!(c.peak() >= max_seen);
+        assert_eq!(c.current(), Bytes::zero());
+    }
+}
@@ -0,0 +1,609 @@
+use crate::paging;
+use crate::Bytes;
+use x86_64::structures::paging::PageSize;
+
+/// A half-open region of bytes `start..start + size`, e.g. an offset range in a physical memory
+/// window or in a file.
+///
+/// The end of a region always fits in `usize`. The byte at [`ByteRegion::end`] is not a part of
+/// the region, so two regions where one ends at the start of the other touch but do not overlap.
+///
+/// ```rust
+/// use os_units::{ByteRegion, Bytes};
+///
+/// let a = ByteRegion::new(Bytes::new(0x1000), Bytes::new(0x1000)).unwrap();
+/// let b = ByteRegion::new(Bytes::new(0x2000), Bytes::new(0x1000)).unwrap();
+///
+/// assert_eq!(a.end(), Bytes::new(0x2000));
+/// assert!(!a.contains(Bytes::new(0x2000)));
+/// assert!(!a.overlaps(b));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ByteRegion {
+    start: Bytes,
+    size: Bytes,
+}
+impl ByteRegion {
+    /// Creates a new region starting at `start` with `size` bytes.
+    ///
+    /// This method returns [`None`] if the end of the region does not fit in `usize`.
+    #[must_use]
+    pub const fn new(start: Bytes, size: Bytes) -> Option<Self> {
+        if start.as_usize().checked_add(size.as_usize()).is_some() {
+            Some(Self { start, size })
+        } else {
+            None
+        }
+    }
+
+    /// Creates a new region `start..end`.
+    ///
+    /// This method returns [`None`] if `end < start`.
+    #[must_use]
+    pub const fn from_start_end(start: Bytes, end: Bytes) -> Option<Self> {
+        if end.as_usize() >= start.as_usize() {
+            Some(Self {
+                start,
+                size: Bytes::new(end.as_usize() - start.as_usize()),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the first byte of the region.
+    #[must_use]
+    pub const fn start(self) -> Bytes {
+        self.start
+    }
+
+    /// Returns the size of the region.
+    #[must_use]
+    pub const fn size(self) -> Bytes {
+        self.size
+    }
+
+    /// Returns the exclusive end of the region.
+    ///
+    /// This never overflows as the constructors reject such regions.
+    #[must_use]
+    pub const fn end(self) -> Bytes {
+        Bytes::new(self.start.as_usize() + self.size.as_usize())
+    }
+
+    /// Returns `true` if the region has no bytes.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.size.as_usize() == 0
+    }
+
+    /// Returns `true` if `start <= offset < end`.
+    #[must_use]
+    pub fn contains(self, offset: Bytes) -> bool {
+        self.start <= offset && offset < self.end()
+    }
+
+    /// Returns `true` if every byte of `other` is in `self`.
+    ///
+    /// An empty `other` is contained if its start is in `start..=end`.
+    #[must_use]
+    pub fn contains_region(self, other: Self) -> bool {
+        self.start <= other.start && other.end() <= self.end()
+    }
+
+    /// Returns `true` if `self` and `other` share at least one byte.
+    ///
+    /// Touching regions, e.g. `0..4` and `4..8`, and empty regions never overlap.
+    #[must_use]
+    pub fn overlaps(self, other: Self) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.start < other.end()
+            && other.start < self.end()
+    }
+
+    /// Returns the bytes shared by `self` and `other`, or [`None`] if they do not overlap.
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        if self.overlaps(other) {
+            Self::from_start_end(self.start.max(other.start), self.end().min(other.end()))
+        } else {
+            None
+        }
+    }
+
+    /// Removes the bytes of `hole` from `self`, returning the pieces below and above the hole.
+    ///
+    /// A piece is [`None`] if it has no bytes. The hole is half-open as well: the byte at
+    /// `hole.end()` survives in the upper piece, and the byte at `hole.start() - 1` survives in
+    /// the lower piece. If `hole` does not overlap `self`, `self` is returned as the first piece.
+    ///
+    /// ```rust
+    /// use os_units::{ByteRegion, Bytes};
+    ///
+    /// let free = ByteRegion::from_start_end(Bytes::new(0), Bytes::new(0x4000)).unwrap();
+    /// let hole = ByteRegion::from_start_end(Bytes::new(0x1000), Bytes::new(0x2000)).unwrap();
+    ///
+    /// assert_eq!(
+    ///     free.subtract(hole),
+    ///     (
+    ///         ByteRegion::from_start_end(Bytes::new(0), Bytes::new(0x1000)),
+    ///         ByteRegion::from_start_end(Bytes::new(0x2000), Bytes::new(0x4000)),
+    ///     )
+    /// );
+    /// ```
+    #[must_use]
+    pub fn subtract(self, hole: Self) -> (Option<Self>, Option<Self>) {
+        if !self.overlaps(hole) {
+            return (Some(self).filter(|r| !r.is_empty()), None);
+        }
+
+        let lower = Self::from_start_end(self.start, hole.start).filter(|r| !r.is_empty());
+        let upper = Self::from_start_end(hole.end(), self.end()).filter(|r| !r.is_empty());
+
+        (lower, upper)
+    }
+
+    /// Merges `self` and `other` into one region if they overlap or touch, i.e. if one ends at
+    /// the start of the other.
+    ///
+    /// This method returns [`None`] if there is at least one byte between the two regions. An
+    /// empty region is merged if its start is in `start..=end` of the other.
+    #[must_use]
+    pub fn union_if_adjacent(self, other: Self) -> Option<Self> {
+        if self.start <= other.end() && other.start <= self.end() {
+            Self::from_start_end(self.start.min(other.start), self.end().max(other.end()))
+        } else {
+            None
+        }
+    }
+
+    /// Expands the region to the page boundaries by rounding the start down and the end up to
+    /// multiples of `T::SIZE`. This is the region to map so that every byte of `self` is
+    /// accessible.
+    ///
+    /// This method returns [`None`] if the rounded-up end or `T::SIZE` does not fit in `usize`.
+    #[must_use]
+    pub fn expand_to_pages<T: PageSize>(self) -> Option<Self> {
+        let page_size = paging::page_size::<T>()?;
+        let start = self.start.as_usize() / page_size * page_size;
+        let end = self.end().as_usize().checked_next_multiple_of(page_size)?;
+
+        Self::from_start_end(Bytes::new(start), Bytes::new(end))
+    }
+
+    /// Shrinks the region to the page-aligned interior by rounding the start up and the end down
+    /// to multiples of `T::SIZE`. This is the region which can be carved out of `self` as whole
+    /// pages.
+    ///
+    /// This method returns [`None`] if no whole page remains.
+    #[must_use]
+    pub fn shrink_to_pages<T: PageSize>(self) -> Option<Self> {
+        let page_size = paging::page_size::<T>()?;
+        let start = self.start.as_usize().checked_next_multiple_of(page_size)?;
+        let end = self.end().as_usize() / page_size * page_size;
+
+        if start < end {
+            Self::from_start_end(Bytes::new(start), Bytes::new(end))
+        } else {
+            None
+        }
+    }
+}
+
+/// Sorts `regions` by their start and merges the overlapping or touching ones in place, without
+/// allocating.
+///
+/// This function returns the number of the merged regions, which are stored at the beginning of
+/// `regions`. The contents of the remaining slots are unspecified. Empty regions are dropped.
+///
+/// ```rust
+/// use os_units::{coalesce_regions, ByteRegion, Bytes};
+///
+/// let r = |s, e| ByteRegion::from_start_end(Bytes::new(s), Bytes::new(e)).unwrap();
+/// let mut regions = [r(0x3000, 0x4000), r(0, 0x1000), r(0x1000, 0x2000)];
+///
+/// let len = coalesce_regions(&mut regions);
+///
+/// assert_eq!(regions[..len], [r(0, 0x2000), r(0x3000, 0x4000)]);
+/// ```
+pub fn coalesce_regions(regions: &mut [ByteRegion]) -> usize {
+    regions.sort_unstable_by_key(|r| r.start);
+
+    let mut len = 0;
+
+    for i in 0..regions.len() {
+        let r = regions[i];
+
+        if r.is_empty() {
+            continue;
+        }
+
+        if len > 0 {
+            if let Some(merged) = regions[len - 1].union_if_adjacent(r) {
+                regions[len - 1] = merged;
+                continue;
+            }
+        }
+
+        regions[len] = r;
+        len += 1;
+    }
+
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::coalesce_regions;
+    use super::ByteRegion;
+    use crate::Bytes;
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    fn region(start: usize, end: usize) -> ByteRegion {
+        ByteRegion::from_start_end(Bytes::new(start), Bytes::new(end)).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        let r = ByteRegion::new(Bytes::new(0x1000), Bytes::new(0x800)).unwrap();
+
+        assert_eq!(r.start(), Bytes::new(0x1000));
+        assert_eq!(r.size(), Bytes::new(0x800));
+        assert_eq!(r.end(), Bytes::new(0x1800));
+    }
+
+    #[test]
+    fn new_end_overflows() {
+        let r = ByteRegion::new(Bytes::new(usize::MAX), Bytes::new(1));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn new_end_at_max() {
+        let r = ByteRegion::new(Bytes::new(usize::MAX - 1), Bytes::new(1)).unwrap();
+
+        assert_eq!(r.end(), Bytes::new(usize::MAX));
+    }
+
+    #[test]
+    fn from_start_end_reversed() {
+        let r = ByteRegion::from_start_end(Bytes::new(4), Bytes::new(3));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn is_empty() {
+        assert!(region(3, 3).is_empty());
+        assert!(!region(3, 4).is_empty());
+    }
+
+    #[test]
+    fn contains_start() {
+        assert!(region(4, 8).contains(Bytes::new(4)));
+    }
+
+    #[test]
+    fn contains_last_byte() {
+        assert!(region(4, 8).contains(Bytes::new(7)));
+    }
+
+    #[test]
+    fn does_not_contain_end() {
+        assert!(!region(4, 8).contains(Bytes::new(8)));
+    }
+
+    #[test]
+    fn does_not_contain_before_start() {
+        assert!(!region(4, 8).contains(Bytes::new(3)));
+    }
+
+    #[test]
+    fn empty_contains_nothing() {
+        assert!(!region(4, 4).contains(Bytes::new(4)));
+    }
+
+    #[test]
+    fn contains_region_itself() {
+        assert!(region(4, 8).contains_region(region(4, 8)));
+    }
+
+    #[test]
+    fn contains_region_inner() {
+        assert!(region(4, 8).contains_region(region(5, 7)));
+    }
+
+    #[test]
+    fn contains_region_sticking_out() {
+        assert!(!region(4, 8).contains_region(region(5, 9)));
+        assert!(!region(4, 8).contains_region(region(3, 7)));
+    }
+
+    #[test]
+    fn contains_empty_region_at_end() {
+        assert!(region(4, 8).contains_region(region(8, 8)));
+        assert!(!region(4, 8).contains_region(region(9, 9)));
+    }
+
+    #[test]
+    fn overlaps() {
+        assert!(region(4, 8).overlaps(region(7, 9)));
+        assert!(region(7, 9).overlaps(region(4, 8)));
+    }
+
+    #[test]
+    fn touching_regions_do_not_overlap() {
+        assert!(!region(4, 8).overlaps(region(8, 12)));
+        assert!(!region(8, 12).overlaps(region(4, 8)));
+    }
+
+    #[test]
+    fn disjoint_regions_do_not_overlap() {
+        assert!(!region(4, 8).overlaps(region(9, 12)));
+    }
+
+    #[test]
+    fn empty_region_does_not_overlap() {
+        assert!(!region(4, 8).overlaps(region(5, 5)));
+        assert!(!region(5, 5).overlaps(region(5, 5)));
+    }
+
+    #[test]
+    fn intersection() {
+        assert_eq!(region(4, 8).intersection(region(6, 12)), Some(region(6, 8)));
+    }
+
+    #[test]
+    fn intersection_inner() {
+        assert_eq!(region(4, 8).intersection(region(5, 6)), Some(region(5, 6)));
+    }
+
+    #[test]
+    fn intersection_one_byte() {
+        assert_eq!(region(4, 8).intersection(region(7, 9)), Some(region(7, 8)));
+    }
+
+    #[test]
+    fn intersection_of_touching_regions() {
+        assert_eq!(region(4, 8).intersection(region(8, 12)), None);
+    }
+
+    #[test]
+    fn expand_inside_one_page() {
+        let r = region(0x1100, 0x1200).expand_to_pages::<Size4KiB>();
+
+        assert_eq!(r, Some(region(0x1000, 0x2000)));
+    }
+
+    #[test]
+    fn expand_spanning_boundaries() {
+        let r = region(0x1100, 0x3001).expand_to_pages::<Size4KiB>();
+
+        assert_eq!(r, Some(region(0x1000, 0x4000)));
+    }
+
+    #[test]
+    fn expand_aligned() {
+        let r = region(0x20_0000, 0x40_0000).expand_to_pages::<Size2MiB>();
+
+        assert_eq!(r, Some(region(0x20_0000, 0x40_0000)));
+    }
+
+    #[test]
+    fn expand_end_overflows() {
+        let r = region(0x1000, usize::MAX).expand_to_pages::<Size4KiB>();
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn shrink_inside_one_page() {
+        let r = region(0x1100, 0x1200).shrink_to_pages::<Size4KiB>();
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn shrink_across_one_boundary() {
+        let r = region(0x1100, 0x2100).shrink_to_pages::<Size4KiB>();
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn shrink_spanning_boundaries() {
+        let r = region(0x1100, 0x3001).shrink_to_pages::<Size4KiB>();
+
+        assert_eq!(r, Some(region(0x2000, 0x3000)));
+    }
+
+    #[test]
+    fn shrink_aligned() {
+        let r = region(0x1000, 0x3000).shrink_to_pages::<Size4KiB>();
+
+        assert_eq!(r, Some(region(0x1000, 0x3000)));
+    }
+
+    #[test]
+    fn shrink_start_overflows() {
+        let r = region(usize::MAX - 1, usize::MAX).shrink_to_pages::<Size4KiB>();
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn subtract_hole_at_start() {
+        let r = region(4, 8).subtract(region(4, 6));
+
+        assert_eq!(r, (None, Some(region(6, 8))));
+    }
+
+    #[test]
+    fn subtract_hole_at_end() {
+        let r = region(4, 8).subtract(region(6, 8));
+
+        assert_eq!(r, (Some(region(4, 6)), None));
+    }
+
+    #[test]
+    fn subtract_hole_in_middle() {
+        let r = region(4, 8).subtract(region(5, 7));
+
+        assert_eq!(r, (Some(region(4, 5)), Some(region(7, 8))));
+    }
+
+    #[test]
+    fn subtract_hole_covering_everything() {
+        let r = region(4, 8).subtract(region(0, 12));
+
+        assert_eq!(r, (None, None));
+    }
+
+    #[test]
+    fn subtract_hole_sticking_out() {
+        assert_eq!(
+            region(4, 8).subtract(region(2, 6)),
+            (None, Some(region(6, 8)))
+        );
+        assert_eq!(
+            region(4, 8).subtract(region(6, 10)),
+            (Some(region(4, 6)), None)
+        );
+    }
+
+    #[test]
+    fn subtract_disjoint_hole() {
+        assert_eq!(
+            region(4, 8).subtract(region(10, 12)),
+            (Some(region(4, 8)), None)
+        );
+        assert_eq!(
+            region(4, 8).subtract(region(0, 2)),
+            (Some(region(4, 8)), None)
+        );
+    }
+
+    #[test]
+    fn subtract_touching_hole() {
+        assert_eq!(
+            region(4, 8).subtract(region(8, 10)),
+            (Some(region(4, 8)), None)
+        );
+        assert_eq!(
+            region(4, 8).subtract(region(2, 4)),
+            (Some(region(4, 8)), None)
+        );
+    }
+
+    #[test]
+    fn subtract_empty_hole() {
+        let r = region(4, 8).subtract(region(6, 6));
+
+        assert_eq!(r, (Some(region(4, 8)), None));
+    }
+
+    #[test]
+    fn subtract_from_empty() {
+        let r = region(4, 4).subtract(region(0, 12));
+
+        assert_eq!(r, (None, None));
+    }
+
+    #[test]
+    fn union_overlapping() {
+        assert_eq!(
+            region(4, 8).union_if_adjacent(region(6, 10)),
+            Some(region(4, 10))
+        );
+    }
+
+    #[test]
+    fn union_touching() {
+        assert_eq!(
+            region(4, 8).union_if_adjacent(region(8, 10)),
+            Some(region(4, 10))
+        );
+        assert_eq!(
+            region(8, 10).union_if_adjacent(region(4, 8)),
+            Some(region(4, 10))
+        );
+    }
+
+    #[test]
+    fn union_inner() {
+        assert_eq!(
+            region(4, 8).union_if_adjacent(region(5, 6)),
+            Some(region(4, 8))
+        );
+    }
+
+    #[test]
+    fn union_with_gap() {
+        assert_eq!(region(4, 8).union_if_adjacent(region(9, 10)), None);
+    }
+
+    #[test]
+    fn union_with_empty() {
+        assert_eq!(
+            region(4, 8).union_if_adjacent(region(8, 8)),
+            Some(region(4, 8))
+        );
+        assert_eq!(region(4, 8).union_if_adjacent(region(9, 9)), None);
+    }
+
+    #[test]
+    fn coalesce_shuffled_overlapping() {
+        let mut regions = [
+            region(20, 30),
+            region(0, 5),
+            region(25, 40),
+            region(4, 10),
+            region(50, 60),
+            region(10, 12),
+        ];
+
+        let len = coalesce_regions(&mut regions);
+
+        assert_eq!(
+            regions[..len],
+            [region(0, 12), region(20, 40), region(50, 60)]
+        );
+    }
+
+    #[test]
+    fn coalesce_already_merged() {
+        let mut regions = [region(0, 5), region(6, 10), region(20, 30)];
+
+        let len = coalesce_regions(&mut regions);
+
+        assert_eq!(
+            regions[..len],
+            [region(0, 5), region(6, 10), region(20, 30)]
+        );
+    }
+
+    #[test]
+    fn coalesce_nested() {
+        let mut regions = [region(2, 3), region(0, 10), region(4, 12)];
+
+        let len = coalesce_regions(&mut regions);
+
+        assert_eq!(regions[..len], [region(0, 12)]);
+    }
+
+    #[test]
+    fn coalesce_drops_empty_regions() {
+        let mut regions = [region(3, 3), region(5, 8), region(20, 20)];
+
+        let len = coalesce_regions(&mut regions);
+
+        assert_eq!(regions[..len], [region(5, 8)]);
+    }
+
+    #[test]
+    fn coalesce_empty_slice() {
+        assert_eq!(coalesce_regions(&mut []), 0);
+    }
+}
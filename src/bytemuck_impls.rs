@@ -0,0 +1,95 @@
+use crate::Bytes;
+use crate::NumOfPages;
+use bytemuck::Pod;
+use bytemuck::TransparentWrapper;
+use bytemuck::Zeroable;
+use x86_64::structures::paging::PageSize;
+
+// SAFETY: `Bytes` is `repr(transparent)` over `usize`, which is `Zeroable`.
+unsafe impl Zeroable for Bytes {}
+// SAFETY: `Bytes` is `repr(transparent)` over `usize`, which is `Pod`.
+unsafe impl Pod for Bytes {}
+
+// SAFETY: `NumOfPages` is `repr(transparent)` over `usize`. The only other field is a
+// `PhantomData`, which is a zero-sized type.
+unsafe impl<T: PageSize> Zeroable for NumOfPages<T> {}
+// SAFETY: Same as above. `PhantomData` has no padding and holds no value.
+unsafe impl<T: PageSize + 'static> Pod for NumOfPages<T> {}
+// SAFETY: `NumOfPages` is `repr(transparent)` over `usize`.
+unsafe impl<T: PageSize> TransparentWrapper<usize> for NumOfPages<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use bytemuck::{TransparentWrapper, Zeroable};
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    #[test]
+    fn zeroed() {
+        assert_eq!(Bytes::zeroed(), Bytes::zero());
+    }
+
+    #[test]
+    fn cast_slice_round_trip() {
+        let sizes = [Bytes::new(0x1000), Bytes::new(3)];
+        let raw: &[u8] = bytemuck::cast_slice(&sizes);
+
+        assert_eq!(raw.len(), 2 * size_of::<usize>());
+        assert_eq!(bytemuck::cast_slice::<u8, Bytes>(raw), &sizes);
+    }
+
+    #[test]
+    fn cast_from_usize() {
+        let raw = [4096_usize, 3];
+
+        assert_eq!(
+            bytemuck::cast_slice::<usize, Bytes>(&raw),
+            &[Bytes::new(4096), Bytes::new(3)]
+        );
+    }
+
+    #[test]
+    fn cast_from_unaligned_bytes() {
+        let aligned = [0_usize; 2];
+        let raw: &[u8] = bytemuck::cast_slice(&aligned);
+
+        assert!(bytemuck::try_cast_slice::<u8, Bytes>(&raw[1..=size_of::<usize>()]).is_err());
+    }
+
+    #[test]
+    fn num_of_pages_zeroed() {
+        assert_eq!(NumOfPages::<Size4KiB>::zeroed(), NumOfPages::zero());
+    }
+
+    #[test]
+    fn num_of_pages_cast_slice_round_trip() {
+        let pages = [NumOfPages::<Size2MiB>::new(512), NumOfPages::new(1)];
+        let raw: &[u8] = bytemuck::cast_slice(&pages);
+
+        assert_eq!(raw.len(), 2 * size_of::<usize>());
+        assert_eq!(
+            bytemuck::cast_slice::<u8, NumOfPages<Size2MiB>>(raw),
+            &pages
+        );
+    }
+
+    #[test]
+    fn num_of_pages_wrap() {
+        let n: NumOfPages<Size4KiB> = NumOfPages::wrap(3);
+
+        assert_eq!(n, NumOfPages::new(3));
+        assert_eq!(NumOfPages::peel(n), 3);
+    }
+
+    #[test]
+    fn num_of_pages_wrap_slice() {
+        let raw = [1_usize, 2, 3];
+        let pages: &[NumOfPages<Size4KiB>] = NumOfPages::wrap_slice(&raw);
+
+        assert_eq!(
+            pages,
+            &[NumOfPages::new(1), NumOfPages::new(2), NumOfPages::new(3)]
+        );
+    }
+}
@@ -1,21 +1,64 @@
+use crate::bytes_per_second;
+use crate::paging;
+use crate::paging::PageSizeExt;
+use crate::pow2;
+use crate::BytesPerSecond;
+use crate::ConversionError;
+use crate::DistributeError;
+use crate::NonZeroBytes;
 use crate::NumOfPages;
+use crate::NumOfPagesRange;
+use crate::ParseBytesError;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
 use core::fmt;
-use core::ops::Add;
-use core::ops::AddAssign;
-use core::ops::Div;
-use core::ops::DivAssign;
-use core::ops::Mul;
-use core::ops::MulAssign;
-use core::ops::Sub;
-use core::ops::SubAssign;
+use core::num::NonZeroUsize;
+use core::str::FromStr;
+use core::time::Duration;
 use x86_64::structures::paging::PageSize;
 use x86_64::PhysAddr;
 use x86_64::VirtAddr;
+#[cfg(not(feature = "no-panic"))]
+use {
+    crate::overflow,
+    core::iter::Sum,
+    core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+};
 
 #[repr(transparent)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
 /// A struct representing byte size.
+///
+/// This type is `repr(transparent)` over `usize`: it has the same size, alignment, and ABI as
+/// `usize`, and thus as `size_t` of C on the targets where `size_t` has the same width as
+/// `uintptr_t`. It can be passed across an FFI boundary directly. [`Bytes::into_raw`] and
+/// [`Bytes::from_raw`] are provided for FFI shims which take `usize`.
+///
+/// ```rust
+/// use os_units::Bytes;
+///
+/// #[no_mangle]
+/// pub extern "C" fn round_up_to_page(len: usize) -> usize {
+///     let len = Bytes::from_raw(len);
+///     let pages = len.as_num_of_pages::<x86_64::structures::paging::Size4KiB>();
+///
+///     pages.checked_as_bytes().map_or(0, Bytes::into_raw)
+/// }
+///
+/// assert_eq!(round_up_to_page(1), 4096);
+/// ```
 pub struct Bytes(usize);
+const _: () = assert!(size_of::<Bytes>() == size_of::<usize>());
+const _: () = assert!(align_of::<Bytes>() == align_of::<usize>());
 impl Bytes {
     /// Creates a new instance with given value.
     #[must_use]
@@ -23,178 +66,2409 @@ impl Bytes {
         Self(bytes)
     }
 
+    /// Creates a new instance from a raw value passed across an FFI boundary. This is the same as
+    /// [`Bytes::new`].
+    #[must_use]
+    pub const fn from_raw(bytes: usize) -> Self {
+        Self::new(bytes)
+    }
+
+    /// Returns the raw value to be passed across an FFI boundary. This is the same as
+    /// [`Bytes::as_usize`].
+    #[must_use]
+    pub const fn into_raw(self) -> usize {
+        self.0
+    }
+
     /// Equivalent to `Bytes::new(0)`.
     #[must_use]
     pub const fn zero() -> Self {
         Self::new(0)
     }
 
-    /// Returns the value.
-    #[must_use]
-    pub const fn as_usize(self) -> usize {
-        self.0
+    /// Returns the value.
+    #[must_use]
+    pub const fn as_usize(self) -> usize {
+        self.0
+    }
+
+    /// `self + rhs` which can be called in `const` contexts, where the [`Add`] implementation
+    /// cannot be used until const traits are stabilized.
+    ///
+    /// Like [`Add`], this panics on overflow if the overflow checks are enabled, and wraps around
+    /// otherwise. In a `const` item, an overflow is a compile error.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// const A: Bytes = Bytes::new(3);
+    /// const B: Bytes = Bytes::new(4);
+    /// const TOTAL: Bytes = A.const_add(B);
+    ///
+    /// assert_eq!(TOTAL, A + B);
+    /// ```
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    pub const fn const_add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+
+    /// `self - rhs` which can be called in `const` contexts. See [`Bytes::const_add`].
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    pub const fn const_sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+
+    /// `self * rhs` which can be called in `const` contexts. See [`Bytes::const_add`].
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    pub const fn const_mul(self, rhs: usize) -> Self {
+        Self(self.0 * rhs)
+    }
+
+    /// `self / rhs` which can be called in `const` contexts. See [`Bytes::const_add`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `rhs` is zero.
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    pub const fn const_div(self, rhs: usize) -> Self {
+        Self(self.0 / rhs)
+    }
+
+    /// Returns `self + rhs`, or [`None`] on overflow.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::new(3).checked_add(Bytes::new(4)), Some(Bytes::new(7)));
+    /// assert_eq!(Bytes::new(usize::MAX).checked_add(Bytes::new(1)), None);
+    /// ```
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(b) => Some(Self(b)),
+            None => None,
+        }
+    }
+
+    /// Returns `self - rhs`, or [`None`] if `rhs` is larger than `self`.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::new(4).checked_sub(Bytes::new(3)), Some(Bytes::new(1)));
+    /// assert_eq!(Bytes::new(3).checked_sub(Bytes::new(4)), None);
+    /// ```
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(b) => Some(Self(b)),
+            None => None,
+        }
+    }
+
+    /// Returns `self * rhs`, or [`None`] on overflow.
+    #[must_use]
+    pub const fn checked_mul(self, rhs: usize) -> Option<Self> {
+        match self.0.checked_mul(rhs) {
+            Some(b) => Some(Self(b)),
+            None => None,
+        }
+    }
+
+    /// Returns `self / rhs`, or [`None`] if `rhs` is zero.
+    #[must_use]
+    pub const fn checked_div(self, rhs: usize) -> Option<Self> {
+        match self.0.checked_div(rhs) {
+            Some(b) => Some(Self(b)),
+            None => None,
+        }
+    }
+
+    /// Returns how many times `rhs` fits in `self`, or [`None`] if `rhs` does not divide `self`
+    /// evenly or is zero.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::new(0x3000).exact_div(Bytes::new(0x1000)), Some(3));
+    /// assert_eq!(Bytes::new(0x3001).exact_div(Bytes::new(0x1000)), None);
+    /// assert_eq!(Bytes::new(0x3000).exact_div(Bytes::zero()), None);
+    /// ```
+    #[must_use]
+    pub const fn exact_div(self, rhs: Self) -> Option<usize> {
+        match self.0.checked_rem(rhs.0) {
+            Some(0) => Some(self.0 / rhs.0),
+            _ => None,
+        }
+    }
+
+    /// Returns `self + rhs`, saturating at `usize::MAX`.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Returns `self - rhs`, saturating at zero.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Returns `self * rhs`, saturating at `usize::MAX`.
+    #[must_use]
+    pub const fn saturating_mul(self, rhs: usize) -> Self {
+        Self(self.0.saturating_mul(rhs))
+    }
+
+    /// Returns `self + rhs`, panicking on overflow regardless of the overflow checks and the
+    /// features.
+    ///
+    /// This method is not available with the `no-panic` feature.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the sum does not fit in `usize`, with both operands in the message.
+    ///
+    /// ```rust,should_panic
+    /// use os_units::Bytes;
+    ///
+    /// // Panics with "Bytes::strict_add overflowed: 18446744073709551615 bytes + 1 bytes".
+    /// let _ = Bytes::new(usize::MAX).strict_add(Bytes::new(1));
+    /// ```
+    #[cfg(not(feature = "no-panic"))]
+    #[track_caller]
+    #[must_use]
+    pub fn strict_add(self, rhs: Self) -> Self {
+        match self.checked_add(rhs) {
+            Some(b) => b,
+            None => overflow::strict_failed(
+                "Bytes::strict_add",
+                "overflowed",
+                format_args!("{} bytes", self.0),
+                '+',
+                format_args!("{} bytes", rhs.0),
+            ),
+        }
+    }
+
+    /// Returns `self - rhs`, panicking if `rhs` is larger than `self`. See
+    /// [`Bytes::strict_add`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `rhs` is larger than `self`.
+    #[cfg(not(feature = "no-panic"))]
+    #[track_caller]
+    #[must_use]
+    pub fn strict_sub(self, rhs: Self) -> Self {
+        match self.checked_sub(rhs) {
+            Some(b) => b,
+            None => overflow::strict_failed(
+                "Bytes::strict_sub",
+                "overflowed",
+                format_args!("{} bytes", self.0),
+                '-',
+                format_args!("{} bytes", rhs.0),
+            ),
+        }
+    }
+
+    /// Returns `self * rhs`, panicking on overflow. See [`Bytes::strict_add`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the product does not fit in `usize`.
+    #[cfg(not(feature = "no-panic"))]
+    #[track_caller]
+    #[must_use]
+    pub fn strict_mul(self, rhs: usize) -> Self {
+        match self.checked_mul(rhs) {
+            Some(b) => b,
+            None => overflow::strict_failed(
+                "Bytes::strict_mul",
+                "overflowed",
+                format_args!("{} bytes", self.0),
+                '*',
+                format_args!("{rhs}"),
+            ),
+        }
+    }
+
+    /// Returns `self / rhs`, panicking if `rhs` is zero. See [`Bytes::strict_add`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `rhs` is zero.
+    #[cfg(not(feature = "no-panic"))]
+    #[track_caller]
+    #[must_use]
+    pub fn strict_div(self, rhs: usize) -> Self {
+        match self.checked_div(rhs) {
+            Some(b) => b,
+            None => overflow::strict_failed(
+                "Bytes::strict_div",
+                "divided by zero",
+                format_args!("{} bytes", self.0),
+                '/',
+                format_args!("{rhs}"),
+            ),
+        }
+    }
+
+    /// Returns `self + rhs`, wrapping around at `usize::MAX`, e.g. for the offsets in a ring
+    /// buffer.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::new(usize::MAX).wrapping_add(Bytes::new(2)), Bytes::new(1));
+    /// ```
+    #[must_use]
+    pub const fn wrapping_add(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_add(rhs.0))
+    }
+
+    /// Returns `self - rhs`, wrapping around at zero.
+    #[must_use]
+    pub const fn wrapping_sub(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_sub(rhs.0))
+    }
+
+    /// Returns `self + rhs` for a number of bytes held as `usize`, wrapping around at
+    /// `usize::MAX`.
+    ///
+    /// This method is not available with the `strict-operands` feature.
+    #[cfg(not(feature = "strict-operands"))]
+    #[must_use]
+    pub const fn wrapping_add_usize(self, rhs: usize) -> Self {
+        self.wrapping_add(Self(rhs))
+    }
+
+    /// Returns `self - rhs` for a number of bytes held as `usize`, wrapping around at zero.
+    ///
+    /// This method is not available with the `strict-operands` feature.
+    #[cfg(not(feature = "strict-operands"))]
+    #[must_use]
+    pub const fn wrapping_sub_usize(self, rhs: usize) -> Self {
+        self.wrapping_sub(Self(rhs))
+    }
+
+    /// Returns `self * rhs`, wrapping around at `usize::MAX`.
+    #[must_use]
+    pub const fn wrapping_mul(self, rhs: usize) -> Self {
+        Self(self.0.wrapping_mul(rhs))
+    }
+
+    /// `self == other` which can be called in `const` contexts, where the [`PartialEq`]
+    /// implementation cannot be used.
+    #[must_use]
+    pub const fn const_eq(self, other: Self) -> bool {
+        self.0 == other.0
+    }
+
+    /// `self.cmp(&other)` which can be called in `const` contexts, e.g. in a static assertion,
+    /// where the [`Ord`] implementation cannot be used.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// const STACK_SIZE: Bytes = Bytes::new(0x8000);
+    /// const MIN: Bytes = Bytes::new(0x4000);
+    ///
+    /// const _: () = assert!(STACK_SIZE.const_cmp(MIN).is_ge());
+    /// ```
+    #[must_use]
+    pub const fn const_cmp(self, other: Self) -> Ordering {
+        if self.0 < other.0 {
+            Ordering::Less
+        } else if self.0 == other.0 {
+            Ordering::Equal
+        } else {
+            Ordering::Greater
+        }
+    }
+
+    /// Returns the memory representation of the value as a byte array in little-endian byte order.
+    #[must_use]
+    pub const fn to_le_bytes(self) -> [u8; size_of::<usize>()] {
+        self.0.to_le_bytes()
+    }
+
+    /// Returns the memory representation of the value as a byte array in big-endian byte order.
+    #[must_use]
+    pub const fn to_be_bytes(self) -> [u8; size_of::<usize>()] {
+        self.0.to_be_bytes()
+    }
+
+    /// Returns the memory representation of the value as a byte array in native byte order.
+    #[must_use]
+    pub const fn to_ne_bytes(self) -> [u8; size_of::<usize>()] {
+        self.0.to_ne_bytes()
+    }
+
+    /// Creates a new instance from its representation as a byte array in little-endian byte order.
+    #[must_use]
+    pub const fn from_le_bytes(bytes: [u8; size_of::<usize>()]) -> Self {
+        Self(usize::from_le_bytes(bytes))
+    }
+
+    /// Creates a new instance from its representation as a byte array in big-endian byte order.
+    #[must_use]
+    pub const fn from_be_bytes(bytes: [u8; size_of::<usize>()]) -> Self {
+        Self(usize::from_be_bytes(bytes))
+    }
+
+    /// Creates a new instance from its representation as a byte array in native byte order.
+    #[must_use]
+    pub const fn from_ne_bytes(bytes: [u8; size_of::<usize>()]) -> Self {
+        Self(usize::from_ne_bytes(bytes))
+    }
+
+    /// Returns the value as a little-endian `u64`, which has the same width regardless of the
+    /// target.
+    #[must_use]
+    pub const fn to_u64_le_bytes(self) -> [u8; 8] {
+        (self.0 as u64).to_le_bytes()
+    }
+
+    /// Creates a new instance from a little-endian `u64`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`ConversionError::Overflow`] if the value does not fit in `usize`.
+    pub const fn from_u64_le_bytes(bytes: [u8; 8]) -> Result<Self, ConversionError> {
+        let v = u64::from_le_bytes(bytes);
+
+        if v > usize::MAX as u64 {
+            Err(ConversionError::Overflow)
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            Ok(Self(v as usize))
+        }
+    }
+
+    /// Returns the number of bytes from `a` to `b`, or [`None`] if `b < a` or the distance does
+    /// not fit in `usize`.
+    ///
+    /// The non-canonical gap is not counted, so `a.checked_add_bytes(d)` of
+    /// [`AddrExt`](crate::AddrExt) returns `Some(b)` for the result `d`.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    /// use x86_64::VirtAddr;
+    ///
+    /// let start = VirtAddr::new(0x40_0000);
+    /// let end = VirtAddr::new(0x40_3000);
+    ///
+    /// assert_eq!(Bytes::between_virt(start, end), Some(Bytes::new(0x3000)));
+    /// assert_eq!(Bytes::between_virt(end, start), None);
+    /// ```
+    #[must_use]
+    pub fn between_virt(a: VirtAddr, b: VirtAddr) -> Option<Self> {
+        if b < a {
+            None
+        } else {
+            usize::try_from(paging::virt_distance(a, b)).ok().map(Self)
+        }
+    }
+
+    /// Returns the number of bytes from `a` to `b`, or [`None`] if `b < a` or the distance does
+    /// not fit in `usize`.
+    #[must_use]
+    pub fn between_phys(a: PhysAddr, b: PhysAddr) -> Option<Self> {
+        if b < a {
+            None
+        } else {
+            usize::try_from(paging::phys_distance(a, b)).ok().map(Self)
+        }
+    }
+
+    /// Returns the bytes needed to store `bits` bits, i.e. `bits / 8` rounded up.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::for_bits(64), Bytes::new(8));
+    /// assert_eq!(Bytes::for_bits(65), Bytes::new(9));
+    /// ```
+    #[must_use]
+    pub const fn for_bits(bits: usize) -> Self {
+        Self(bits.div_ceil(8))
+    }
+
+    /// Returns the bytes needed to store `bits` bits, rounded up to a multiple of `word`, e.g. for
+    /// a bitmap accessed by `u64`.
+    ///
+    /// This method returns [`None`] if the result does not fit in `usize`.
+    ///
+    /// ```rust
+    /// use os_units::{Bytes, NonZeroBytes};
+    ///
+    /// let word = NonZeroBytes::new(Bytes::new(8)).unwrap();
+    ///
+    /// assert_eq!(Bytes::for_bits_word_aligned(65, word), Some(Bytes::new(16)));
+    /// ```
+    #[must_use]
+    pub const fn for_bits_word_aligned(bits: usize, word: NonZeroBytes) -> Option<Self> {
+        match Self::for_bits(bits)
+            .0
+            .checked_next_multiple_of(word.get().as_usize())
+        {
+            Some(b) => Some(Self(b)),
+            None => None,
+        }
+    }
+
+    /// Converts bytes to the number of physical pages. Note that the number of physical pages will
+    /// be calculated so that the specified bytes will be fit in pages.
+    #[must_use]
+    pub fn as_num_of_pages<T: PageSize>(self) -> NumOfPages<T> {
+        // Computed in `u64` so that a page size which does not fit in `usize` is not truncated.
+        // The quotient is not larger than `self`, so it fits. The page sizes are powers of two, so
+        // the division rounding up is a shift plus one if any bit below the shift is set.
+        let b = self.0 as u64;
+
+        #[allow(clippy::cast_possible_truncation)]
+        NumOfPages::new(((b >> T::SHIFT) + u64::from(b & T::MASK != 0)) as usize)
+    }
+
+    /// Converts bytes to the number of physical pages, failing with
+    /// [`ConversionError::NotAMultiple`] if the bytes are not a multiple of the page size.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if `self` is not a multiple of `T::SIZE`.
+    pub fn as_num_of_pages_exact<T: PageSize>(self) -> Result<NumOfPages<T>, ConversionError> {
+        // See `as_num_of_pages` for the `u64` arithmetic and the shift.
+        let b = self.0 as u64;
+        #[allow(clippy::cast_possible_truncation)]
+        let remainder = (b & T::MASK) as usize;
+
+        if remainder == 0 {
+            #[allow(clippy::cast_possible_truncation)]
+            Ok(NumOfPages::new((b >> T::SHIFT) as usize))
+        } else {
+            Err(ConversionError::NotAMultiple {
+                remainder: Self::new(remainder),
+            })
+        }
+    }
+
+    /// Returns the largest value whose number of pages, rounded up, still fits in `usize` as
+    /// bytes, i.e. `usize::MAX` rounded down to a multiple of `T::SIZE`.
+    ///
+    /// Converting a larger value with [`Bytes::as_num_of_pages`] gives a number of pages whose
+    /// [`NumOfPages::checked_as_bytes`] is [`None`]. This is the bound to validate a length
+    /// against before converting it to pages and back.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// const MAX: Bytes = Bytes::max_for_pages::<Size4KiB>();
+    ///
+    /// assert_eq!(MAX, Bytes::new(usize::MAX & !0xfff));
+    /// assert!(MAX.as_num_of_pages::<Size4KiB>().checked_as_bytes().is_some());
+    /// ```
+    #[must_use]
+    pub const fn max_for_pages<T: PageSize>() -> Self {
+        // Zero if the page size does not fit in `usize`, as no page does.
+        #[allow(clippy::cast_possible_truncation)]
+        Self((usize::MAX as u64 >> T::SHIFT << T::SHIFT) as usize)
+    }
+
+    /// Returns an iterator over the page-aligned offsets `0, T::SIZE, 2 * T::SIZE, ...` of every
+    /// page needed to cover `self` bytes. The last page may be partially covered.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// let offsets: Vec<_> = Bytes::new(0x1001).iter_page_offsets::<Size4KiB>().collect();
+    ///
+    /// assert_eq!(offsets, [Bytes::new(0), Bytes::new(0x1000)]);
+    /// ```
+    #[must_use]
+    pub fn iter_page_offsets<T: PageSize>(
+        self,
+    ) -> impl ExactSizeIterator<Item = Self> + DoubleEndedIterator {
+        NumOfPagesRange::<T>::new(NumOfPages::zero(), self.as_num_of_pages())
+            .map(NumOfPages::to_bytes)
+    }
+
+    /// Returns an iterator which splits `self` into chunks of `max` bytes, followed by the
+    /// remainder if any.
+    ///
+    /// ```rust
+    /// use os_units::{Bytes, NonZeroBytes};
+    ///
+    /// let max = NonZeroBytes::new(Bytes::new(0x1_0000)).unwrap();
+    /// let chunks: Vec<_> = Bytes::new(0x2_8000).chunks(max).collect();
+    ///
+    /// assert_eq!(chunks, [Bytes::new(0x1_0000), Bytes::new(0x1_0000), Bytes::new(0x8000)]);
+    /// ```
+    #[must_use]
+    pub fn chunks(
+        self,
+        max: NonZeroBytes,
+    ) -> impl ExactSizeIterator<Item = Self> + DoubleEndedIterator {
+        let max = max.get().0;
+
+        (0..self.num_chunks_of(max)).map(move |i| Self((self.0 - i * max).min(max)))
+    }
+
+    /// Returns the number of the items [`Bytes::chunks`] yields.
+    #[must_use]
+    pub fn num_chunks(self, max: NonZeroBytes) -> usize {
+        self.num_chunks_of(max.get().0)
+    }
+
+    fn num_chunks_of(self, max: usize) -> usize {
+        self.0.div_ceil(max)
+    }
+
+    /// Returns an iterator which splits `self` into `n` pieces whose sizes differ by at most one
+    /// byte. The remainder of `self / n` is distributed one byte each to the first pieces.
+    ///
+    /// ```rust
+    /// use core::num::NonZeroUsize;
+    /// use os_units::Bytes;
+    ///
+    /// let n = NonZeroUsize::new(3).unwrap();
+    /// let pieces: Vec<_> = Bytes::new(11).split_into(n).collect();
+    ///
+    /// assert_eq!(pieces, [Bytes::new(4), Bytes::new(4), Bytes::new(3)]);
+    /// ```
+    #[must_use]
+    pub fn split_into(
+        self,
+        n: NonZeroUsize,
+    ) -> impl ExactSizeIterator<Item = Self> + DoubleEndedIterator {
+        let n = n.get();
+        let (quotient, remainder) = (self.0 / n, self.0 % n);
+
+        (0..n).map(move |i| Self(quotient + usize::from(i < remainder)))
+    }
+
+    /// Returns an iterator which splits `self` into `n` pieces whose sizes are multiples of
+    /// `T::SIZE` except for the last one.
+    ///
+    /// The whole pages are distributed like [`Bytes::split_into`], so the numbers of pages of the
+    /// pieces differ by at most one. The last piece additionally gets the bytes of the last
+    /// partial page.
+    #[must_use]
+    pub fn split_into_aligned<T: PageSize>(
+        self,
+        n: NonZeroUsize,
+    ) -> impl ExactSizeIterator<Item = Self> + DoubleEndedIterator {
+        let (pages, tail) = paging::div_rem_page_size::<T>(self.0);
+        let last = n.get() - 1;
+
+        Self(pages)
+            .split_into(n)
+            .enumerate()
+            .map(move |(i, pages)| {
+                let piece = NumOfPages::<T>::new(pages.0).to_bytes();
+
+                if i == last {
+                    Self(piece.0 + tail)
+                } else {
+                    piece
+                }
+            })
+    }
+
+    /// Distributes `self` into `out` in proportion to `weights`, without allocating.
+    ///
+    /// Each `out[i]` first gets `self * weights[i] / sum(weights)` rounded down. The bytes left
+    /// over by the rounding, which are fewer than the number of the non-zero weights, are then
+    /// given one byte each to the entries with a non-zero weight, from the front. The outputs
+    /// always sum to `self`, and an entry with a zero weight always gets zero bytes.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// let mut out = [Bytes::zero(); 3];
+    /// Bytes::new(100).distribute_weighted(&[1, 2, 4], &mut out).unwrap();
+    ///
+    /// assert_eq!(out, [Bytes::new(15), Bytes::new(28), Bytes::new(57)]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`DistributeError::LengthMismatch`] if `weights` and `out` have
+    /// different lengths, and [`DistributeError::ZeroTotalWeight`] if the weights sum to zero.
+    /// `out` is left untouched on error.
+    pub fn distribute_weighted(
+        self,
+        weights: &[usize],
+        out: &mut [Self],
+    ) -> Result<(), DistributeError> {
+        if weights.len() != out.len() {
+            return Err(DistributeError::LengthMismatch);
+        }
+
+        let total_weight: u128 = weights.iter().map(|&w| w as u128).sum();
+
+        if total_weight == 0 {
+            return Err(DistributeError::ZeroTotalWeight);
+        }
+
+        let mut distributed = 0;
+
+        for (o, &w) in out.iter_mut().zip(weights) {
+            #[allow(clippy::cast_possible_truncation)]
+            let share = (self.0 as u128 * w as u128 / total_weight) as usize;
+
+            *o = Self(share);
+            distributed += share;
+        }
+
+        let mut leftover = self.0 - distributed;
+
+        for (o, &w) in out.iter_mut().zip(weights) {
+            if leftover == 0 {
+                break;
+            }
+
+            if w != 0 {
+                o.0 += 1;
+                leftover -= 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns an iterator over the power-of-two components of `self` in descending order, i.e.
+    /// the values of the set bits.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// let v: Vec<_> = Bytes::new(0x8600_0000).pow2_decompose().collect();
+    ///
+    /// assert_eq!(
+    ///     v,
+    ///     [
+    ///         Bytes::new(0x8000_0000),
+    ///         Bytes::new(0x400_0000),
+    ///         Bytes::new(0x200_0000),
+    ///     ]
+    /// );
+    /// ```
+    pub fn pow2_decompose(self) -> impl Iterator<Item = Self> {
+        pow2::components(self.0, 1 << (usize::BITS - 1)).map(Self)
+    }
+
+    /// Like [`Bytes::pow2_decompose`], but the components larger than `max` are split into
+    /// chunks of the largest power of two not exceeding `max`. The items are still in descending
+    /// order and sum to `self`.
+    ///
+    /// ```rust
+    /// use os_units::{Bytes, NonZeroBytes};
+    ///
+    /// let max = NonZeroBytes::new(Bytes::new(0x3000)).unwrap();
+    /// let v: Vec<_> = Bytes::new(0x5800).pow2_decompose_max(max).collect();
+    ///
+    /// assert_eq!(
+    ///     v,
+    ///     [Bytes::new(0x2000), Bytes::new(0x2000), Bytes::new(0x1000), Bytes::new(0x800)]
+    /// );
+    /// ```
+    pub fn pow2_decompose_max(self, max: NonZeroBytes) -> impl Iterator<Item = Self> {
+        pow2::components(self.0, 1 << (usize::BITS - 1 - max.get().0.leading_zeros())).map(Self)
+    }
+
+    /// Returns the smallest size class in `classes` which is at least `self`, or [`None`] if
+    /// `self` is larger than every class.
+    ///
+    /// `classes` must be sorted in ascending order. This is checked only with debug assertions.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// let classes = [16, 32, 48, 64].map(Bytes::new);
+    ///
+    /// assert_eq!(Bytes::new(33).round_up_to_class(&classes), Some(Bytes::new(48)));
+    /// assert_eq!(Bytes::new(65).round_up_to_class(&classes), None);
+    /// ```
+    #[must_use]
+    pub fn round_up_to_class(self, classes: &[Self]) -> Option<Self> {
+        self.class_index_for(classes).map(|i| classes[i])
+    }
+
+    /// Returns the index of the smallest size class in `classes` which is at least `self`, or
+    /// [`None`] if `self` is larger than every class.
+    ///
+    /// `classes` must be sorted in ascending order. This is checked only with debug assertions.
+    #[must_use]
+    pub fn class_index_for(self, classes: &[Self]) -> Option<usize> {
+        debug_assert!(
+            classes.windows(2).all(|w| w[0] <= w[1]),
+            "the size classes are not sorted"
+        );
+
+        let i = classes.partition_point(|c| *c < self);
+
+        (i < classes.len()).then_some(i)
+    }
+
+    /// Returns the size after growing `self` by `self * num / den`, but by at least `min_step`
+    /// and to at most `max_total`.
+    ///
+    /// The result is never smaller than `self`, even if `self` already exceeds `max_total`. The
+    /// intermediate values never overflow.
+    ///
+    /// ```rust
+    /// use core::num::NonZeroUsize;
+    /// use os_units::Bytes;
+    ///
+    /// let half = NonZeroUsize::new(2).unwrap();
+    /// let min_step = Bytes::new(0x1_0000);
+    /// let max_total = Bytes::new(0x100_0000);
+    ///
+    /// let grow = |b: usize| Bytes::new(b).grow_by_factor(1, half, min_step, max_total);
+    ///
+    /// assert_eq!(grow(0x8000), Bytes::new(0x1_8000));
+    /// assert_eq!(grow(0x40_0000), Bytes::new(0x60_0000));
+    /// assert_eq!(grow(0xc0_0000), Bytes::new(0x100_0000));
+    /// ```
+    #[must_use]
+    pub fn grow_by_factor(
+        self,
+        num: usize,
+        den: NonZeroUsize,
+        min_step: Self,
+        max_total: Self,
+    ) -> Self {
+        let total = self.0.saturating_add(self.growth_step(num, den, min_step));
+
+        Self(total.min(max_total.0).max(self.0))
+    }
+
+    /// Like [`Bytes::grow_by_factor`], but the result is the number of pages holding the grown
+    /// size.
+    ///
+    /// The grown size is rounded up to the page size, and the cap is rounded down so that the
+    /// pages fit in `max_total`. The result is never smaller than the number of pages holding
+    /// `self`.
+    ///
+    /// ```rust
+    /// use core::num::NonZeroUsize;
+    /// use os_units::{Bytes, NumOfPages};
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// let n = Bytes::new(0x1_0000).grow_by_factor_pages::<Size4KiB>(
+    ///     1,
+    ///     NonZeroUsize::new(3).unwrap(),
+    ///     Bytes::zero(),
+    ///     Bytes::new(0x100_0000),
+    /// );
+    ///
+    /// assert_eq!(n, NumOfPages::new(22));
+    /// ```
+    #[must_use]
+    pub fn grow_by_factor_pages<T: PageSize>(
+        self,
+        num: usize,
+        den: NonZeroUsize,
+        min_step: Self,
+        max_total: Self,
+    ) -> NumOfPages<T> {
+        let total = Self(self.0.saturating_add(self.growth_step(num, den, min_step)));
+        let (max_pages, _) = paging::div_rem_page_size::<T>(max_total.0);
+
+        let pages = total
+            .as_num_of_pages::<T>()
+            .as_usize()
+            .min(max_pages)
+            .max(self.as_num_of_pages::<T>().as_usize());
+
+        NumOfPages::new(pages)
+    }
+
+    fn growth_step(self, num: usize, den: NonZeroUsize, min_step: Self) -> usize {
+        let step = self.0 as u128 * num as u128 / den.get() as u128;
+
+        usize::try_from(step).unwrap_or(usize::MAX).max(min_step.0)
+    }
+
+    /// Returns the rate of transferring `self` in `d`.
+    ///
+    /// This method returns [`None`] if `d` is zero or the rate does not fit in `u64`.
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use os_units::{Bytes, BytesPerSecond};
+    ///
+    /// assert_eq!(
+    ///     Bytes::new(0x1000).per(Duration::from_millis(250)),
+    ///     Some(BytesPerSecond::new(0x4000))
+    /// );
+    /// assert_eq!(Bytes::new(0x1000).per(Duration::ZERO), None);
+    /// ```
+    #[must_use]
+    pub fn per(self, d: Duration) -> Option<BytesPerSecond> {
+        let nanos = d.as_nanos();
+
+        if nanos == 0 {
+            return None;
+        }
+
+        let rate = self.as_usize() as u128 * bytes_per_second::NANOS_PER_SEC / nanos;
+
+        u64::try_from(rate).ok().map(BytesPerSecond::new)
+    }
+
+    /// Returns an object which displays the value in the largest binary unit which divides it
+    /// exactly, e.g. `16 MiB` for `0x100_0000` and `4097 bytes` for `4097`.
+    ///
+    /// The output can be parsed back with [`FromStr`].
+    #[must_use]
+    pub fn display_binary(self) -> impl fmt::Display {
+        Binary(self)
+    }
+
+    /// Returns an object which displays the value with `separator` inserted every three digits,
+    /// e.g. `1,073,741,824 bytes` with `','` or `1_073_741_824 bytes` with `'_'`.
+    #[must_use]
+    pub fn display_grouped(self, separator: char) -> impl fmt::Display {
+        Grouped {
+            bytes: self,
+            separator,
+        }
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+const_op_impl! {
+    impl Add for Bytes {
+        type Output = Bytes;
+
+        fn add(self, rhs: Bytes) -> Self {
+            Self::new(overflow::add("Bytes", self.0, rhs.0))
+        }
+    }
+}
+#[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
+const_op_impl! {
+    impl Add<usize> for Bytes {
+        type Output = Bytes;
+
+        fn add(self, rhs: usize) -> Self::Output {
+            Self::new(overflow::add("Bytes", self.0, rhs))
+        }
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl<T: PageSize> Add<NumOfPages<T>> for Bytes {
+    type Output = Bytes;
+
+    fn add(self, rhs: NumOfPages<T>) -> Self::Output {
+        self + rhs.as_bytes()
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl Add<Bytes> for VirtAddr {
+    type Output = VirtAddr;
+
+    fn add(self, rhs: Bytes) -> Self::Output {
+        self + rhs.as_usize() as u64
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl Add<Bytes> for PhysAddr {
+    type Output = PhysAddr;
+
+    fn add(self, rhs: Bytes) -> Self::Output {
+        self + rhs.as_usize() as u64
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl AddAssign for Bytes {
+    fn add_assign(&mut self, rhs: Bytes) {
+        self.0 = overflow::add("Bytes", self.0, rhs.0);
+    }
+}
+#[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
+impl AddAssign<usize> for Bytes {
+    fn add_assign(&mut self, rhs: usize) {
+        self.0 = overflow::add("Bytes", self.0, rhs);
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl<T: PageSize> AddAssign<NumOfPages<T>> for Bytes {
+    fn add_assign(&mut self, rhs: NumOfPages<T>) {
+        *self += rhs.as_bytes();
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl AddAssign<Bytes> for VirtAddr {
+    fn add_assign(&mut self, rhs: Bytes) {
+        *self += rhs.as_usize() as u64;
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl AddAssign<Bytes> for PhysAddr {
+    fn add_assign(&mut self, rhs: Bytes) {
+        *self += rhs.as_usize() as u64;
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+const_op_impl! {
+    impl Sub for Bytes {
+        type Output = Bytes;
+
+        fn sub(self, rhs: Bytes) -> Self {
+            Self::new(overflow::sub("Bytes", self.0, rhs.0))
+        }
+    }
+}
+#[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
+const_op_impl! {
+    impl Sub<usize> for Bytes {
+        type Output = Bytes;
+
+        fn sub(self, rhs: usize) -> Self::Output {
+            Self::new(overflow::sub("Bytes", self.0, rhs))
+        }
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl<T: PageSize> Sub<NumOfPages<T>> for Bytes {
+    type Output = Bytes;
+
+    fn sub(self, rhs: NumOfPages<T>) -> Self::Output {
+        self - rhs.as_bytes()
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl Sub<Bytes> for VirtAddr {
+    type Output = VirtAddr;
+
+    fn sub(self, rhs: Bytes) -> Self::Output {
+        self - rhs.as_usize() as u64
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl Sub<Bytes> for PhysAddr {
+    type Output = PhysAddr;
+
+    fn sub(self, rhs: Bytes) -> Self::Output {
+        self - rhs.as_usize() as u64
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl SubAssign for Bytes {
+    fn sub_assign(&mut self, rhs: Bytes) {
+        self.0 = overflow::sub("Bytes", self.0, rhs.0);
+    }
+}
+#[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
+impl SubAssign<usize> for Bytes {
+    fn sub_assign(&mut self, rhs: usize) {
+        *self -= Bytes::new(rhs);
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl<T: PageSize> SubAssign<NumOfPages<T>> for Bytes {
+    fn sub_assign(&mut self, rhs: NumOfPages<T>) {
+        *self -= rhs.as_bytes();
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl SubAssign<Bytes> for VirtAddr {
+    fn sub_assign(&mut self, rhs: Bytes) {
+        *self -= rhs.as_usize() as u64;
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl SubAssign<Bytes> for PhysAddr {
+    fn sub_assign(&mut self, rhs: Bytes) {
+        *self -= rhs.as_usize() as u64;
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+const_op_impl! {
+    impl Mul<usize> for Bytes {
+        type Output = Bytes;
+        fn mul(self, rhs: usize) -> Self::Output {
+            Self(overflow::mul("Bytes", self.0, rhs))
+        }
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl MulAssign<usize> for Bytes {
+    fn mul_assign(&mut self, rhs: usize) {
+        *self = *self * rhs;
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+const_op_impl! {
+    impl Div<usize> for Bytes {
+        type Output = Bytes;
+
+        fn div(self, rhs: usize) -> Self::Output {
+            Self(self.0 / rhs)
+        }
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl DivAssign<usize> for Bytes {
+    fn div_assign(&mut self, rhs: usize) {
+        *self = *self / rhs;
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+/// Sums the bytes with the `+` operator. Like [`Add`], this panics on overflow if the overflow
+/// checks are enabled, and wraps around otherwise.
+impl Sum for Bytes {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+/// Sums the bytes with the `+` operator. Like [`Add`], this panics on overflow if the overflow
+/// checks are enabled, and wraps around otherwise.
+impl<'a> Sum<&'a Bytes> for Bytes {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+impl From<usize> for Bytes {
+    fn from(b: usize) -> Self {
+        Self::new(b)
+    }
+}
+/// Compares with a number of bytes held as `usize`, e.g. `len <= MAX_LEN`.
+#[cfg(not(feature = "strict-operands"))]
+impl PartialEq<usize> for Bytes {
+    fn eq(&self, other: &usize) -> bool {
+        self.0 == *other
+    }
+}
+#[cfg(not(feature = "strict-operands"))]
+impl PartialEq<Bytes> for usize {
+    fn eq(&self, other: &Bytes) -> bool {
+        *self == other.0
+    }
+}
+#[cfg(not(feature = "strict-operands"))]
+impl PartialOrd<usize> for Bytes {
+    fn partial_cmp(&self, other: &usize) -> Option<Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+#[cfg(not(feature = "strict-operands"))]
+impl PartialOrd<Bytes> for usize {
+    fn partial_cmp(&self, other: &Bytes) -> Option<Ordering> {
+        self.partial_cmp(&other.0)
+    }
+}
+impl TryFrom<u64> for Bytes {
+    type Error = ConversionError;
+
+    fn try_from(b: u64) -> Result<Self, Self::Error> {
+        Ok(Self::new(usize::try_from(b)?))
+    }
+}
+/// Parses a string like `"4096"`, `"4096 bytes"`, or `"16 MiB"`.
+///
+/// The accepted units are `B`, `byte`, `bytes`, `KiB`, `MiB`, `GiB`, `TiB`, `PiB`, and `EiB`. The
+/// unit may be omitted, and may be preceded by whitespace.
+impl FromStr for Bytes {
+    type Err = ParseBytesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseBytesError::Empty);
+        }
+
+        let digits_end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (digits, unit) = s.split_at(digits_end);
+
+        if digits.is_empty() {
+            return Err(ParseBytesError::InvalidDigit);
+        }
+
+        let unit = unit.trim_start();
+
+        // A fraction like `1.5 MiB` or a second number is a malformed number, not a unit.
+        if unit.starts_with(|c: char| !c.is_ascii_alphabetic()) {
+            return Err(ParseBytesError::InvalidDigit);
+        }
+
+        let multiplier = unit_multiplier(unit)?;
+        let value = digits
+            .bytes()
+            .try_fold(0_usize, |acc, d| {
+                acc.checked_mul(10)?.checked_add(usize::from(d - b'0'))
+            })
+            .ok_or(ParseBytesError::Overflow)?;
+
+        value
+            .checked_mul(multiplier)
+            .map(Self::new)
+            .ok_or(ParseBytesError::Overflow)
+    }
+}
+/// The binary units and their shift amounts, in descending order.
+const BINARY_UNITS: [(&str, u32); 6] = [
+    ("EiB", 60),
+    ("PiB", 50),
+    ("TiB", 40),
+    ("GiB", 30),
+    ("MiB", 20),
+    ("KiB", 10),
+];
+
+fn unit_multiplier(unit: &str) -> Result<usize, ParseBytesError> {
+    let shift = match unit {
+        "" | "B" | "byte" | "bytes" => 0,
+        _ => {
+            BINARY_UNITS
+                .iter()
+                .find(|(name, _)| *name == unit)
+                .ok_or(ParseBytesError::UnknownUnit)?
+                .1
+        }
+    };
+
+    1_usize.checked_shl(shift).ok_or(ParseBytesError::Overflow)
+}
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = if self.0 == 1 { "byte" } else { "bytes" };
+        write!(f, "{} {}", self.0, unit)
+    }
+}
+
+struct Binary(Bytes);
+impl fmt::Display for Binary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0.as_usize();
+        let unit = BINARY_UNITS
+            .iter()
+            .find(|(_, shift)| *shift < usize::BITS && bytes.trailing_zeros() >= *shift);
+
+        match unit {
+            Some((name, shift)) if bytes != 0 => write!(f, "{} {}", bytes >> shift, name),
+            _ => write!(f, "{}", self.0),
+        }
+    }
+}
+
+struct Grouped {
+    bytes: Bytes,
+    separator: char,
+}
+impl Grouped {
+    fn write_digits(&self, f: &mut fmt::Formatter<'_>, n: usize) -> fmt::Result {
+        if n < 1000 {
+            write!(f, "{n}")
+        } else {
+            self.write_digits(f, n / 1000)?;
+            write!(f, "{}{:03}", self.separator, n % 1000)
+        }
+    }
+}
+impl fmt::Display for Grouped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = if self.bytes.0 == 1 { "byte" } else { "bytes" };
+
+        self.write_digits(f, self.bytes.0)?;
+        write!(f, " {unit}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AddrExt;
+    use core::iter;
+    use x86_64::structures::paging::{Size1GiB, Size2MiB, Size4KiB};
+    use x86_64::{PhysAddr, VirtAddr};
+
+    #[test]
+    fn get_value_from_bytes() {
+        let bytes = Bytes::new(334);
+        assert_eq!(bytes.as_usize(), 334);
+    }
+
+    #[test]
+    fn reexport_is_hash_and_from_usize() {
+        use crate::Bytes as Reexported;
+
+        let bytes: Reexported = 0x1000.into();
+        let set: std::collections::HashSet<_> = [bytes, Bytes::new(1), Bytes::from(0x1000)]
+            .iter()
+            .copied()
+            .collect();
+
+        assert_eq!(bytes, Bytes::new(0x1000));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Bytes::new(1)));
+    }
+
+    #[test]
+    fn layout() {
+        assert_eq!(size_of::<Bytes>(), size_of::<usize>());
+        assert_eq!(align_of::<Bytes>(), align_of::<usize>());
+    }
+
+    #[test]
+    fn raw_round_trip() {
+        let b = Bytes::from_raw(0x1000);
+
+        assert_eq!(b, Bytes::new(0x1000));
+        assert_eq!(b.into_raw(), 0x1000);
+    }
+
+    #[test]
+    fn le_bytes() {
+        let b = Bytes::new(0x0102);
+        let raw = b.to_le_bytes();
+
+        assert_eq!(raw[..2], [0x02, 0x01]);
+        assert_eq!(Bytes::from_le_bytes(raw), b);
+    }
+
+    #[test]
+    fn be_bytes() {
+        let b = Bytes::new(0x0102);
+        let raw = b.to_be_bytes();
+
+        assert_eq!(raw[raw.len() - 2..], [0x01, 0x02]);
+        assert_eq!(Bytes::from_be_bytes(raw), b);
+    }
+
+    #[test]
+    fn ne_bytes() {
+        let b = Bytes::new(0x0102);
+
+        assert_eq!(b.to_ne_bytes(), 0x0102_usize.to_ne_bytes());
+        assert_eq!(Bytes::from_ne_bytes(b.to_ne_bytes()), b);
+    }
+
+    #[test]
+    fn u64_le_bytes() {
+        let b = Bytes::new(0x0102);
+        let raw = b.to_u64_le_bytes();
+
+        assert_eq!(raw, [0x02, 0x01, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(Bytes::from_u64_le_bytes(raw), Ok(b));
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    fn u64_le_bytes_overflow() {
+        assert_eq!(
+            Bytes::from_u64_le_bytes([0, 0, 0, 0, 1, 0, 0, 0]),
+            Err(ConversionError::Overflow)
+        );
+    }
+
+    #[test]
+    fn between_virt() {
+        let b = Bytes::between_virt(VirtAddr::new(0x1000), VirtAddr::new(0x1234));
+
+        assert_eq!(b, Some(Bytes::new(0x234)));
+    }
+
+    #[test]
+    fn between_virt_equal() {
+        let b = Bytes::between_virt(VirtAddr::new(0x1000), VirtAddr::new(0x1000));
+
+        assert_eq!(b, Some(Bytes::zero()));
+    }
+
+    #[test]
+    fn between_virt_reversed() {
+        let b = Bytes::between_virt(VirtAddr::new(0x1001), VirtAddr::new(0x1000));
+
+        assert_eq!(b, None);
+    }
+
+    #[test]
+    fn between_virt_across_canonical_gap() {
+        let a = VirtAddr::new(0x7fff_ffff_f000);
+        let b = VirtAddr::new(0xffff_8000_0000_1000);
+        let d = Bytes::between_virt(a, b).unwrap();
+
+        assert_eq!(d, Bytes::new(0x2000));
+        assert_eq!(a.checked_add_bytes(d), Some(b));
+    }
+
+    #[test]
+    fn between_phys() {
+        let b = Bytes::between_phys(PhysAddr::new(0xfee0_0000), PhysAddr::new(0xfee0_1000));
+
+        assert_eq!(b, Some(Bytes::new(0x1000)));
+    }
+
+    #[test]
+    fn between_phys_equal() {
+        let b = Bytes::between_phys(PhysAddr::new(0x1000), PhysAddr::new(0x1000));
+
+        assert_eq!(b, Some(Bytes::zero()));
+    }
+
+    #[test]
+    fn between_phys_reversed() {
+        let b = Bytes::between_phys(PhysAddr::new(0x2000), PhysAddr::new(0x1000));
+
+        assert_eq!(b, None);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn between_phys_64_bit_distance() {
+        let b = Bytes::between_phys(PhysAddr::new(0), PhysAddr::new(0x10_0000_0000));
+
+        assert_eq!(b, Some(Bytes::new(0x10_0000_0000)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn between_phys_64_bit_distance() {
+        let b = Bytes::between_phys(PhysAddr::new(0), PhysAddr::new(0x10_0000_0000));
+
+        assert_eq!(b, None);
+    }
+
+    #[test]
+    fn for_bits() {
+        assert_eq!(Bytes::for_bits(0), Bytes::zero());
+        assert_eq!(Bytes::for_bits(1), Bytes::new(1));
+        assert_eq!(Bytes::for_bits(7), Bytes::new(1));
+        assert_eq!(Bytes::for_bits(8), Bytes::new(1));
+        assert_eq!(Bytes::for_bits(9), Bytes::new(2));
+        assert_eq!(Bytes::for_bits(1000), Bytes::new(125));
+        assert_eq!(Bytes::for_bits(1001), Bytes::new(126));
+    }
+
+    #[test]
+    fn for_bits_near_max() {
+        assert_eq!(Bytes::for_bits(usize::MAX), Bytes::new(usize::MAX / 8 + 1));
+        assert_eq!(
+            Bytes::for_bits(usize::MAX - 6),
+            Bytes::new(usize::MAX / 8 + 1)
+        );
+        assert_eq!(Bytes::for_bits(usize::MAX - 7), Bytes::new(usize::MAX / 8));
+    }
+
+    #[test]
+    fn for_bits_word_aligned() {
+        let word = |n| NonZeroBytes::new(Bytes::new(n)).unwrap();
+
+        assert_eq!(
+            Bytes::for_bits_word_aligned(0, word(8)),
+            Some(Bytes::zero())
+        );
+        assert_eq!(
+            Bytes::for_bits_word_aligned(1, word(8)),
+            Some(Bytes::new(8))
+        );
+        assert_eq!(
+            Bytes::for_bits_word_aligned(64, word(8)),
+            Some(Bytes::new(8))
+        );
+        assert_eq!(
+            Bytes::for_bits_word_aligned(65, word(8)),
+            Some(Bytes::new(16))
+        );
+        assert_eq!(
+            Bytes::for_bits_word_aligned(65, word(1)),
+            Some(Bytes::new(9))
+        );
+        assert_eq!(
+            Bytes::for_bits_word_aligned(100, word(3)),
+            Some(Bytes::new(15))
+        );
+    }
+
+    #[test]
+    fn for_bits_word_aligned_near_max() {
+        let word = |n| NonZeroBytes::new(Bytes::new(n)).unwrap();
+
+        assert_eq!(
+            Bytes::for_bits_word_aligned(usize::MAX, word(8)),
+            Some(Bytes::new(usize::MAX / 8 + 1))
+        );
+        assert_eq!(
+            Bytes::for_bits_word_aligned(1, word(usize::MAX)),
+            Some(Bytes::new(usize::MAX))
+        );
+        assert_eq!(
+            Bytes::for_bits_word_aligned(usize::MAX, word(usize::MAX / 4)),
+            Some(Bytes::new(usize::MAX / 4))
+        );
+    }
+
+    #[test]
+    fn bytes_to_pages() {
+        let bytes = Bytes::new(0x4000_0000);
+        assert_eq!(bytes.as_num_of_pages::<Size4KiB>().as_usize(), 0x4_0000);
+        assert_eq!(bytes.as_num_of_pages::<Size2MiB>().as_usize(), 512);
+        assert_eq!(bytes.as_num_of_pages::<Size1GiB>().as_usize(), 1);
+    }
+
+    /// The values around the multiples of `T::SIZE` and the ends of `usize`, and a pseudo-random
+    /// sweep.
+    fn conversion_inputs<T: PageSize>() -> impl Iterator<Item = usize> {
+        #[allow(clippy::cast_possible_truncation)]
+        let size = T::SIZE as usize;
+        let edges = [0, 1, 2, size - 1, size, size + 1, 2 * size - 1, 2 * size];
+        let top = (0..4)
+            .map(|k| usize::MAX - k)
+            .chain([usize::MAX / size * size]);
+        let mut x: usize = 0x2545_f491;
+        let random = iter::repeat_with(move || {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x
+        });
+
+        IntoIterator::into_iter(edges)
+            .chain(top)
+            .chain(random.take(10_000))
+    }
+
+    fn check_max_for_pages<T: PageSize>() {
+        let max = Bytes::max_for_pages::<T>();
+
+        assert!(max.as_num_of_pages::<T>().checked_as_bytes().is_some());
+        assert_eq!(
+            Bytes::new(max.as_usize() + 1)
+                .as_num_of_pages::<T>()
+                .checked_as_bytes(),
+            None
+        );
+        assert_eq!(max.as_num_of_pages_exact::<T>().map(|_| ()), Ok(()));
+    }
+
+    #[test]
+    fn max_for_pages() {
+        check_max_for_pages::<Size4KiB>();
+        check_max_for_pages::<Size2MiB>();
+        check_max_for_pages::<Size1GiB>();
+        assert_eq!(
+            Bytes::max_for_pages::<Size2MiB>(),
+            Bytes::new(usize::MAX & !0x1f_ffff)
+        );
+    }
+
+    // The implementations before the shifts were introduced.
+    fn check_conversions_match_division<T: PageSize>() {
+        for b in conversion_inputs::<T>() {
+            #[allow(clippy::cast_possible_truncation)]
+            let size = T::SIZE as usize;
+
+            assert_eq!(
+                Bytes::new(b).as_num_of_pages::<T>().as_usize(),
+                b.div_ceil(size),
+                "{b:#x}"
+            );
+            assert_eq!(
+                Bytes::new(b).as_num_of_pages_exact::<T>(),
+                if b % size == 0 {
+                    Ok(NumOfPages::new(b / size))
+                } else {
+                    Err(ConversionError::NotAMultiple {
+                        remainder: Bytes::new(b % size),
+                    })
+                },
+                "{b:#x}"
+            );
+        }
+    }
+
+    #[test]
+    fn conversions_match_division() {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+        enum Size1B {}
+        impl PageSize for Size1B {
+            const SIZE: u64 = 1;
+            const SIZE_AS_DEBUG_STR: &'static str = "1B";
+        }
+
+        check_conversions_match_division::<Size1B>();
+        check_conversions_match_division::<Size4KiB>();
+        check_conversions_match_division::<Size2MiB>();
+        check_conversions_match_division::<Size1GiB>();
+    }
+
+    #[test]
+    fn bytes_to_pages_near_max() {
+        let bytes = Bytes::new(usize::MAX);
+        assert_eq!(
+            bytes.as_num_of_pages::<Size4KiB>().as_usize(),
+            usize::MAX / 0x1000 + 1
+        );
+    }
+
+    #[test]
+    fn iter_page_offsets_exact_multiple() {
+        let v: Vec<_> = Bytes::new(0x3000).iter_page_offsets::<Size4KiB>().collect();
+
+        assert_eq!(v, [Bytes::new(0), Bytes::new(0x1000), Bytes::new(0x2000)]);
+    }
+
+    #[test]
+    fn iter_page_offsets_partial_last_page() {
+        let v: Vec<_> = Bytes::new(0x20_0001)
+            .iter_page_offsets::<Size2MiB>()
+            .collect();
+
+        assert_eq!(v, [Bytes::new(0), Bytes::new(0x20_0000)]);
+    }
+
+    #[test]
+    fn iter_page_offsets_zero() {
+        let mut i = Bytes::zero().iter_page_offsets::<Size4KiB>();
+
+        assert_eq!(i.len(), 0);
+        assert_eq!(i.next(), None);
+    }
+
+    #[test]
+    fn iter_page_offsets_rev() {
+        let v: Vec<_> = Bytes::new(0x2800)
+            .iter_page_offsets::<Size4KiB>()
+            .rev()
+            .collect();
+
+        assert_eq!(v, [Bytes::new(0x2000), Bytes::new(0x1000), Bytes::new(0)]);
+    }
+
+    #[test]
+    fn iter_page_offsets_max() {
+        let mut i = Bytes::new(usize::MAX).iter_page_offsets::<Size4KiB>();
+
+        assert_eq!(i.len(), usize::MAX / 0x1000 + 1);
+        assert_eq!(i.next_back(), Some(Bytes::new(usize::MAX & !0xfff)));
+    }
+
+    fn max_chunk(n: usize) -> NonZeroBytes {
+        NonZeroBytes::new(Bytes::new(n)).unwrap()
+    }
+
+    #[test]
+    fn chunks_exact_multiple() {
+        let v: Vec<_> = Bytes::new(0x2_0000).chunks(max_chunk(0x1_0000)).collect();
+
+        assert_eq!(v, [Bytes::new(0x1_0000), Bytes::new(0x1_0000)]);
+    }
+
+    #[test]
+    fn chunks_with_tail() {
+        let c = Bytes::new(10).chunks(max_chunk(4));
+
+        assert_eq!(c.len(), 3);
+        assert_eq!(
+            c.collect::<Vec<_>>(),
+            [Bytes::new(4), Bytes::new(4), Bytes::new(2)]
+        );
+    }
+
+    #[test]
+    fn chunks_smaller_than_one_chunk() {
+        let v: Vec<_> = Bytes::new(3).chunks(max_chunk(4)).collect();
+
+        assert_eq!(v, [Bytes::new(3)]);
+    }
+
+    #[test]
+    fn chunks_zero() {
+        let mut c = Bytes::zero().chunks(max_chunk(4));
+
+        assert_eq!(c.len(), 0);
+        assert_eq!(c.next(), None);
+    }
+
+    #[test]
+    fn chunks_max() {
+        let mut c = Bytes::new(usize::MAX).chunks(max_chunk(usize::MAX / 2));
+
+        assert_eq!(c.next_back(), Some(Bytes::new(1)));
+    }
+
+    #[test]
+    fn num_chunks() {
+        assert_eq!(Bytes::new(10).num_chunks(max_chunk(4)), 3);
+        assert_eq!(Bytes::new(8).num_chunks(max_chunk(4)), 2);
+        assert_eq!(Bytes::new(3).num_chunks(max_chunk(4)), 1);
+        assert_eq!(Bytes::zero().num_chunks(max_chunk(4)), 0);
+    }
+
+    fn nonzero(n: usize) -> NonZeroUsize {
+        NonZeroUsize::new(n).unwrap()
+    }
+
+    fn assert_split_bounds(pieces: &[Bytes], total: Bytes, max_diff: usize) {
+        let min = pieces.iter().min().unwrap().as_usize();
+        let max = pieces.iter().max().unwrap().as_usize();
+
+        assert_eq!(
+            pieces
+                .iter()
+                .try_fold(Bytes::zero(), |a, &b| a.checked_add(b)),
+            Some(total)
+        );
+        assert!(max - min <= max_diff);
+    }
+
+    #[test]
+    fn split_into_exact() {
+        let v: Vec<_> = Bytes::new(12).split_into(nonzero(3)).collect();
+
+        assert_eq!(v, [Bytes::new(4), Bytes::new(4), Bytes::new(4)]);
+    }
+
+    #[test]
+    fn split_into_with_remainder() {
+        let v: Vec<_> = Bytes::new(0x1_0003).split_into(nonzero(4)).collect();
+
+        assert_eq!(v.len(), 4);
+        assert_split_bounds(&v, Bytes::new(0x1_0003), 1);
+        assert_eq!(v[0], Bytes::new(0x4001));
+        assert_eq!(v[3], Bytes::new(0x4000));
+    }
+
+    #[test]
+    fn split_into_more_pieces_than_bytes() {
+        let v: Vec<_> = Bytes::new(2).split_into(nonzero(4)).collect();
+
+        assert_eq!(
+            v,
+            [Bytes::new(1), Bytes::new(1), Bytes::zero(), Bytes::zero()]
+        );
+    }
+
+    #[test]
+    fn split_into_max() {
+        let v: Vec<_> = Bytes::new(usize::MAX).split_into(nonzero(7)).collect();
+
+        assert_split_bounds(&v, Bytes::new(usize::MAX), 1);
+    }
+
+    #[test]
+    fn split_into_aligned_exact() {
+        let v: Vec<_> = Bytes::new(0x6000)
+            .split_into_aligned::<Size4KiB>(nonzero(3))
+            .collect();
+
+        assert_eq!(
+            v,
+            [Bytes::new(0x2000), Bytes::new(0x2000), Bytes::new(0x2000)]
+        );
+    }
+
+    #[test]
+    fn split_into_aligned_with_partial_page() {
+        let v: Vec<_> = Bytes::new(0x5123)
+            .split_into_aligned::<Size4KiB>(nonzero(3))
+            .collect();
+
+        assert_eq!(
+            v,
+            [Bytes::new(0x2000), Bytes::new(0x2000), Bytes::new(0x1123)]
+        );
+        assert_split_bounds(&v, Bytes::new(0x5123), 0x1000);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn split_into_aligned_pieces_are_aligned() {
+        let v: Vec<_> = Bytes::new(0x1234_5678)
+            .split_into_aligned::<Size2MiB>(nonzero(5))
+            .collect();
+
+        assert_eq!(v.iter().sum::<Bytes>(), Bytes::new(0x1234_5678));
+
+        for b in &v[..4] {
+            assert_eq!(b.as_usize() % 0x20_0000, 0);
+        }
+    }
+
+    #[test]
+    fn distribute_weighted() {
+        let mut out = [Bytes::zero(); 3];
+        Bytes::new(700)
+            .distribute_weighted(&[1, 2, 4], &mut out)
+            .unwrap();
+
+        assert_eq!(out, [Bytes::new(100), Bytes::new(200), Bytes::new(400)]);
+    }
+
+    #[test]
+    fn distribute_weighted_leftover_goes_to_front() {
+        let mut out = [Bytes::zero(); 3];
+        Bytes::new(5)
+            .distribute_weighted(&[1, 1, 1], &mut out)
+            .unwrap();
+
+        assert_eq!(out, [Bytes::new(2), Bytes::new(2), Bytes::new(1)]);
+    }
+
+    #[test]
+    fn distribute_weighted_skips_zero_weights() {
+        let mut out = [Bytes::zero(); 3];
+        Bytes::new(5)
+            .distribute_weighted(&[0, 1, 1], &mut out)
+            .unwrap();
+
+        assert_eq!(out, [Bytes::zero(), Bytes::new(3), Bytes::new(2)]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn distribute_weighted_conserves_total() {
+        let mut out = [Bytes::zero(); 5];
+        let total = Bytes::new(usize::MAX);
+        total
+            .distribute_weighted(&[3, usize::MAX, 0, 7, 1], &mut out)
+            .unwrap();
+
+        assert_eq!(out.iter().sum::<Bytes>(), total);
+        assert_eq!(out[2], Bytes::zero());
+    }
+
+    #[test]
+    fn distribute_weighted_zero_total_weight() {
+        let mut out = [Bytes::new(1); 2];
+        let r = Bytes::new(5).distribute_weighted(&[0, 0], &mut out);
+
+        assert_eq!(r, Err(DistributeError::ZeroTotalWeight));
+        assert_eq!(out, [Bytes::new(1); 2]);
+    }
+
+    #[test]
+    fn distribute_weighted_empty() {
+        let r = Bytes::new(5).distribute_weighted(&[], &mut []);
+
+        assert_eq!(r, Err(DistributeError::ZeroTotalWeight));
+    }
+
+    #[test]
+    fn distribute_weighted_length_mismatch() {
+        let mut out = [Bytes::zero(); 2];
+        let r = Bytes::new(5).distribute_weighted(&[1, 2, 3], &mut out);
+
+        assert_eq!(r, Err(DistributeError::LengthMismatch));
+    }
+
+    #[test]
+    fn pow2_decompose_zero() {
+        assert_eq!(Bytes::zero().pow2_decompose().next(), None);
+    }
+
+    #[test]
+    fn pow2_decompose_power_of_two() {
+        let v: Vec<_> = Bytes::new(0x20_0000).pow2_decompose().collect();
+
+        assert_eq!(v, [Bytes::new(0x20_0000)]);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn pow2_decompose_many_components() {
+        let v: Vec<_> = Bytes::new(0x1_8600_0000).pow2_decompose().collect();
+
+        assert_eq!(
+            v,
+            [
+                Bytes::new(0x1_0000_0000),
+                Bytes::new(0x8000_0000),
+                Bytes::new(0x400_0000),
+                Bytes::new(0x200_0000)
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn pow2_decompose_max_value() {
+        let v: Vec<_> = Bytes::new(usize::MAX).pow2_decompose().collect();
+
+        assert_eq!(v.len(), usize::BITS as usize);
+        assert_eq!(v.iter().sum::<Bytes>(), Bytes::new(usize::MAX));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn pow2_decompose_max_splits_large_components() {
+        let max = NonZeroBytes::new(Bytes::new(0x4000_0000)).unwrap();
+        let v: Vec<_> = Bytes::new(0x1_8600_0000).pow2_decompose_max(max).collect();
+
+        assert_eq!(
+            v,
+            [
+                Bytes::new(0x4000_0000),
+                Bytes::new(0x4000_0000),
+                Bytes::new(0x4000_0000),
+                Bytes::new(0x4000_0000),
+                Bytes::new(0x4000_0000),
+                Bytes::new(0x4000_0000),
+                Bytes::new(0x400_0000),
+                Bytes::new(0x200_0000)
+            ]
+        );
+    }
+
+    #[test]
+    fn pow2_decompose_max_not_power_of_two() {
+        let max = NonZeroBytes::new(Bytes::new(3)).unwrap();
+        let v: Vec<_> = Bytes::new(5).pow2_decompose_max(max).collect();
+
+        assert_eq!(v, [Bytes::new(2), Bytes::new(2), Bytes::new(1)]);
+    }
+
+    #[test]
+    fn per_seconds() {
+        assert_eq!(
+            Bytes::new(3000).per(Duration::from_secs(3)),
+            Some(BytesPerSecond::new(1000))
+        );
+        assert_eq!(
+            Bytes::new(1000).per(Duration::from_secs(3)),
+            Some(BytesPerSecond::new(333))
+        );
+    }
+
+    #[test]
+    fn per_sub_second() {
+        assert_eq!(
+            Bytes::new(1).per(Duration::from_nanos(1)),
+            Some(BytesPerSecond::new(1_000_000_000))
+        );
+        assert_eq!(
+            Bytes::new(0x1000).per(Duration::from_micros(1500)),
+            Some(BytesPerSecond::new(2_730_666))
+        );
+    }
+
+    #[test]
+    fn per_zero() {
+        assert_eq!(Bytes::new(1).per(Duration::ZERO), None);
+        assert_eq!(
+            Bytes::zero().per(Duration::from_secs(1)),
+            Some(BytesPerSecond::new(0))
+        );
+    }
+
+    #[test]
+    fn per_does_not_overflow() {
+        // `usize::MAX * 1_000_000_000` does not fit in `u64`.
+        assert_eq!(
+            Bytes::new(usize::MAX).per(Duration::from_secs(1_000_000_000)),
+            Some(BytesPerSecond::new(usize::MAX as u64 / 1_000_000_000))
+        );
+        assert_eq!(
+            Bytes::new(1).per(Duration::from_secs(u64::MAX)),
+            Some(BytesPerSecond::new(0))
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn per_rate_too_large() {
+        assert_eq!(Bytes::new(usize::MAX).per(Duration::from_nanos(1)), None);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn per_rate_fits_in_u64() {
+        assert_eq!(
+            Bytes::new(usize::MAX).per(Duration::from_nanos(1)),
+            Some(BytesPerSecond::new(usize::MAX as u64 * 1_000_000_000))
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn const_ops_agree_with_operators() {
+        let a = Bytes::new(0x3000);
+        let b = Bytes::new(0x1000);
+
+        assert_eq!(a.const_add(b), a + b);
+        assert_eq!(a.const_sub(b), a - b);
+        assert_eq!(a.const_mul(3), a * 3);
+        assert_eq!(a.const_div(2), a / 2);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn const_ops_in_const_items() {
+        const A: Bytes = Bytes::new(0x1000);
+        const B: Bytes = A.const_mul(4).const_sub(A).const_div(3).const_add(A);
+
+        assert_eq!(B, Bytes::new(0x2000));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn const_add_overflow() {
+        let _ = Bytes::new(usize::MAX).const_add(Bytes::new(1));
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let b = Bytes::new(0x1000);
+
+        assert_eq!(b.checked_add(Bytes::new(1)), Some(Bytes::new(0x1001)));
+        assert_eq!(Bytes::new(usize::MAX).checked_add(Bytes::new(1)), None);
+        assert_eq!(b.checked_sub(b), Some(Bytes::zero()));
+        assert_eq!(b.checked_sub(Bytes::new(0x1001)), None);
+        assert_eq!(b.checked_mul(3), Some(Bytes::new(0x3000)));
+        assert_eq!(b.checked_mul(usize::MAX), None);
+        assert_eq!(b.checked_div(2), Some(Bytes::new(0x800)));
+        assert_eq!(b.checked_div(0), None);
+    }
+
+    #[test]
+    fn exact_div() {
+        assert_eq!(Bytes::new(0x3000).exact_div(Bytes::new(0x1000)), Some(3));
+        assert_eq!(Bytes::zero().exact_div(Bytes::new(0x1000)), Some(0));
+        assert_eq!(
+            Bytes::new(usize::MAX).exact_div(Bytes::new(1)),
+            Some(usize::MAX)
+        );
+        assert_eq!(Bytes::new(0x3001).exact_div(Bytes::new(0x1000)), None);
+        assert_eq!(Bytes::new(0xfff).exact_div(Bytes::new(0x1000)), None);
+        assert_eq!(Bytes::new(0x3000).exact_div(Bytes::zero()), None);
+        assert_eq!(Bytes::zero().exact_div(Bytes::zero()), None);
+    }
+
+    #[test]
+    fn saturating_arithmetic() {
+        let max = Bytes::new(usize::MAX);
+
+        assert_eq!(max.saturating_add(Bytes::new(1)), max);
+        assert_eq!(Bytes::new(3).saturating_add(Bytes::new(4)), Bytes::new(7));
+        assert_eq!(Bytes::new(3).saturating_sub(Bytes::new(4)), Bytes::zero());
+        assert_eq!(Bytes::new(4).saturating_sub(Bytes::new(3)), Bytes::new(1));
+        assert_eq!(Bytes::new(2).saturating_mul(usize::MAX), max);
+        assert_eq!(Bytes::new(2).saturating_mul(3), Bytes::new(6));
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "overflow-checks",
+        not(feature = "no-panic"),
+        target_pointer_width = "64"
+    ))]
+    #[should_panic(expected = "Bytes addition overflowed: 18446744073709551615 + 4096")]
+    fn add_overflow_panics() {
+        let _ = Bytes::new(usize::MAX) + Bytes::new(4096);
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "Bytes addition overflowed")]
+    fn add_assign_overflow_panics() {
+        let mut b = Bytes::new(usize::MAX);
+        b += Bytes::new(1);
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "Bytes addition overflowed")]
+    fn sum_overflow_panics() {
+        let _: Bytes = [Bytes::new(usize::MAX), Bytes::new(1)].iter().sum();
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "Bytes subtraction overflowed: 3 - 4")]
+    fn sub_overflow_panics() {
+        let _ = Bytes::new(3) - Bytes::new(4);
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "overflow-checks",
+        not(any(feature = "no-panic", feature = "strict-operands"))
+    ))]
+    #[should_panic(expected = "Bytes subtraction overflowed: 3 - 4")]
+    fn sub_assign_usize_overflow_panics() {
+        let mut b = Bytes::new(3);
+        b -= 4;
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "Bytes multiplication overflowed")]
+    fn mul_overflow_panics() {
+        let _ = Bytes::new(usize::MAX / 2 + 1) * 2;
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "NumOfPages to Bytes multiplication overflowed")]
+    fn add_num_of_pages_overflow_panics() {
+        let _ = Bytes::zero() + NumOfPages::<Size4KiB>::new(usize::MAX);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn strict_arithmetic() {
+        assert_eq!(Bytes::new(3).strict_add(Bytes::new(4)), Bytes::new(7));
+        assert_eq!(Bytes::new(4).strict_sub(Bytes::new(3)), Bytes::new(1));
+        assert_eq!(Bytes::new(3).strict_mul(4), Bytes::new(12));
+        assert_eq!(Bytes::new(13).strict_div(4), Bytes::new(3));
+    }
+
+    #[test]
+    #[cfg(all(not(feature = "no-panic"), target_pointer_width = "64"))]
+    #[should_panic(
+        expected = "Bytes::strict_add overflowed: 18446744073709551615 bytes + 4096 bytes"
+    )]
+    fn strict_add_overflow() {
+        let _ = Bytes::new(usize::MAX).strict_add(Bytes::new(4096));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic(expected = "Bytes::strict_sub overflowed: 3 bytes - 4 bytes")]
+    fn strict_sub_overflow() {
+        let _ = Bytes::new(3).strict_sub(Bytes::new(4));
+    }
+
+    #[test]
+    #[cfg(all(not(feature = "no-panic"), target_pointer_width = "64"))]
+    #[should_panic(expected = "Bytes::strict_mul overflowed: 9223372036854775808 bytes * 2")]
+    fn strict_mul_overflow() {
+        let _ = Bytes::new(usize::MAX / 2 + 1).strict_mul(2);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic(expected = "Bytes::strict_div divided by zero: 4096 bytes / 0")]
+    fn strict_div_by_zero() {
+        let _ = Bytes::new(4096).strict_div(0);
+    }
+
+    #[test]
+    fn wrapping_arithmetic() {
+        let max = Bytes::new(usize::MAX);
+
+        assert_eq!(max.wrapping_add(Bytes::new(1)), Bytes::zero());
+        assert_eq!(max.wrapping_add(max), Bytes::new(usize::MAX - 1));
+        assert_eq!(Bytes::new(3).wrapping_add(Bytes::new(4)), Bytes::new(7));
+        assert_eq!(Bytes::zero().wrapping_sub(Bytes::new(1)), max);
+        assert_eq!(Bytes::new(3).wrapping_sub(Bytes::new(4)), max);
+        assert_eq!(Bytes::new(4).wrapping_sub(Bytes::new(3)), Bytes::new(1));
+        assert_eq!(
+            Bytes::new(2).wrapping_mul(usize::MAX),
+            Bytes::new(usize::MAX - 1)
+        );
+        assert_eq!(max.wrapping_mul(usize::MAX), Bytes::new(1));
+        assert_eq!(Bytes::new(2).wrapping_mul(3), Bytes::new(6));
+    }
+
+    #[test]
+    fn wrapping_round_trip() {
+        let start = Bytes::new(usize::MAX - 0x10);
+        let step = Bytes::new(0x100);
+
+        assert_eq!(start.wrapping_add(step), Bytes::new(0xef));
+        assert_eq!(start.wrapping_add(step).wrapping_sub(step), start);
+        assert_eq!(Bytes::new(0xef).wrapping_sub(step), start);
     }
 
-    /// Converts bytes to the number of physical pages. Note that the number of physical pages will
-    /// be calculated so that the specified bytes will be fit in pages.
-    #[must_use]
-    pub fn as_num_of_pages<T: PageSize>(self) -> NumOfPages<T> {
-        #[allow(clippy::cast_possible_truncation)]
-        NumOfPages::new((self.0 + T::SIZE as usize - 1) / T::SIZE as usize)
+    #[test]
+    #[cfg(not(feature = "strict-operands"))]
+    fn wrapping_usize_operands() {
+        let max = Bytes::new(usize::MAX);
+
+        assert_eq!(max.wrapping_add_usize(2), Bytes::new(1));
+        assert_eq!(Bytes::new(3).wrapping_add_usize(4), Bytes::new(7));
+        assert_eq!(Bytes::new(1).wrapping_sub_usize(2), max);
+        assert_eq!(Bytes::new(4).wrapping_sub_usize(3), Bytes::new(1));
     }
-}
-impl Add for Bytes {
-    type Output = Bytes;
 
-    fn add(self, rhs: Bytes) -> Self {
-        Self::new(self.0 + rhs.0)
+    #[test]
+    fn checked_in_const_items() {
+        const SUM: Option<Bytes> = Bytes::new(1).checked_add(Bytes::new(2));
+        const DIFF: Bytes = Bytes::new(1).saturating_sub(Bytes::new(2));
+
+        assert_eq!(SUM, Some(Bytes::new(3)));
+        assert_eq!(DIFF, Bytes::zero());
     }
-}
-impl Add<usize> for Bytes {
-    type Output = Bytes;
 
-    fn add(self, rhs: usize) -> Self::Output {
-        Self::new(self.0 + rhs)
+    #[test]
+    #[cfg(not(feature = "strict-operands"))]
+    fn compare_with_usize() {
+        let b = Bytes::new(0x1000);
+
+        assert_eq!(b, 0x1000);
+        assert_ne!(b, 0x1001);
+        assert!(b < 0x1001);
+        assert!(b <= 0x1000);
+        assert!(b > 0xfff);
+        assert!(b >= 0x1000);
+        assert_eq!(b.partial_cmp(&0x1000_usize), Some(Ordering::Equal));
     }
-}
-impl Add<Bytes> for VirtAddr {
-    type Output = VirtAddr;
 
-    fn add(self, rhs: Bytes) -> Self::Output {
-        self + rhs.as_usize()
+    #[test]
+    #[cfg(not(feature = "strict-operands"))]
+    fn compare_usize_with_bytes() {
+        let b = Bytes::new(0x1000);
+
+        assert_eq!(0x1000, b);
+        assert_ne!(0x1001, b);
+        assert!(0xfff < b);
+        assert!(0x1000 <= b);
+        assert!(0x1001 > b);
+        assert!(0x1000 >= b);
+        assert_eq!(0x1000_usize.partial_cmp(&b), Some(Ordering::Equal));
     }
-}
-impl Add<Bytes> for PhysAddr {
-    type Output = PhysAddr;
 
-    fn add(self, rhs: Bytes) -> Self::Output {
-        self + rhs.as_usize()
+    #[test]
+    fn const_eq_cmp_agree_with_traits() {
+        let values = [0, 1, 0x1000, usize::MAX].map(Bytes::new);
+
+        for a in values {
+            for b in values {
+                assert_eq!(a.const_eq(b), a == b);
+                assert_eq!(a.const_cmp(b), a.cmp(&b));
+            }
+        }
     }
-}
-impl AddAssign for Bytes {
-    fn add_assign(&mut self, rhs: Bytes) {
-        self.0 += rhs.0;
+
+    #[test]
+    fn const_cmp_in_const_items() {
+        const SMALL: Bytes = Bytes::new(1);
+        const LARGE: Bytes = Bytes::new(2);
+        const _: () = assert!(SMALL.const_cmp(LARGE).is_lt());
+        const _: () = assert!(LARGE.const_cmp(SMALL).is_gt());
+        const _: () = assert!(SMALL.const_eq(SMALL));
+        const _: () = assert!(!SMALL.const_eq(LARGE));
     }
-}
-impl AddAssign<usize> for Bytes {
-    fn add_assign(&mut self, rhs: usize) {
-        self.0 += rhs;
+
+    const CLASSES: [Bytes; 5] = [
+        Bytes::new(16),
+        Bytes::new(32),
+        Bytes::new(48),
+        Bytes::new(64),
+        Bytes::new(128),
+    ];
+
+    #[test]
+    fn round_up_to_class_exact() {
+        assert_eq!(
+            Bytes::new(16).round_up_to_class(&CLASSES),
+            Some(Bytes::new(16))
+        );
+        assert_eq!(
+            Bytes::new(48).round_up_to_class(&CLASSES),
+            Some(Bytes::new(48))
+        );
+        assert_eq!(
+            Bytes::new(128).round_up_to_class(&CLASSES),
+            Some(Bytes::new(128))
+        );
     }
-}
-impl AddAssign<Bytes> for VirtAddr {
-    fn add_assign(&mut self, rhs: Bytes) {
-        *self += rhs.as_usize();
+
+    #[test]
+    fn round_up_to_class_between() {
+        assert_eq!(
+            Bytes::zero().round_up_to_class(&CLASSES),
+            Some(Bytes::new(16))
+        );
+        assert_eq!(
+            Bytes::new(17).round_up_to_class(&CLASSES),
+            Some(Bytes::new(32))
+        );
+        assert_eq!(
+            Bytes::new(65).round_up_to_class(&CLASSES),
+            Some(Bytes::new(128))
+        );
     }
-}
-impl AddAssign<Bytes> for PhysAddr {
-    fn add_assign(&mut self, rhs: Bytes) {
-        *self += rhs.as_usize();
+
+    #[test]
+    fn round_up_to_class_oversize() {
+        assert_eq!(Bytes::new(129).round_up_to_class(&CLASSES), None);
+        assert_eq!(Bytes::new(usize::MAX).round_up_to_class(&CLASSES), None);
     }
-}
-impl Sub for Bytes {
-    type Output = Bytes;
 
-    fn sub(self, rhs: Bytes) -> Self {
-        Self::new(self.0 - rhs.0)
+    #[test]
+    fn round_up_to_class_empty_table() {
+        assert_eq!(Bytes::zero().round_up_to_class(&[]), None);
     }
-}
-impl Sub<usize> for Bytes {
-    type Output = Bytes;
 
-    fn sub(self, rhs: usize) -> Self::Output {
-        Self::new(self.0 - rhs)
+    #[test]
+    fn class_index_for() {
+        assert_eq!(Bytes::new(1).class_index_for(&CLASSES), Some(0));
+        assert_eq!(Bytes::new(32).class_index_for(&CLASSES), Some(1));
+        assert_eq!(Bytes::new(33).class_index_for(&CLASSES), Some(2));
+        assert_eq!(Bytes::new(100).class_index_for(&CLASSES), Some(4));
+        assert_eq!(Bytes::new(200).class_index_for(&CLASSES), None);
     }
-}
-impl Sub<Bytes> for VirtAddr {
-    type Output = VirtAddr;
 
-    fn sub(self, rhs: Bytes) -> Self::Output {
-        self - rhs.as_usize()
+    #[test]
+    fn class_index_for_duplicate_classes() {
+        let classes = [16, 32, 32, 64].map(Bytes::new);
+
+        assert_eq!(Bytes::new(20).class_index_for(&classes), Some(1));
     }
-}
-impl Sub<Bytes> for PhysAddr {
-    type Output = PhysAddr;
 
-    fn sub(self, rhs: Bytes) -> Self::Output {
-        self - rhs.as_usize()
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the size classes are not sorted")]
+    fn class_index_for_unsorted() {
+        let classes = [32, 16].map(Bytes::new);
+
+        let _ = Bytes::new(1).class_index_for(&classes);
     }
-}
-impl SubAssign for Bytes {
-    fn sub_assign(&mut self, rhs: Bytes) {
-        self.0 -= rhs.0;
+
+    fn half() -> NonZeroUsize {
+        NonZeroUsize::new(2).unwrap()
     }
-}
-impl SubAssign<usize> for Bytes {
-    fn sub_assign(&mut self, rhs: usize) {
-        *self -= Bytes::new(rhs);
+
+    #[test]
+    fn grow_by_factor() {
+        let b =
+            Bytes::new(0x10_0000).grow_by_factor(1, half(), Bytes::zero(), Bytes::new(usize::MAX));
+
+        assert_eq!(b, Bytes::new(0x18_0000));
     }
-}
-impl SubAssign<Bytes> for VirtAddr {
-    fn sub_assign(&mut self, rhs: Bytes) {
-        *self -= rhs.as_usize();
+
+    #[test]
+    fn grow_by_factor_more_than_double() {
+        let b = Bytes::new(0x1000).grow_by_factor(
+            3,
+            NonZeroUsize::new(1).unwrap(),
+            Bytes::zero(),
+            Bytes::new(usize::MAX),
+        );
+
+        assert_eq!(b, Bytes::new(0x4000));
     }
-}
-impl SubAssign<Bytes> for PhysAddr {
-    fn sub_assign(&mut self, rhs: Bytes) {
-        *self -= rhs.as_usize()
+
+    #[test]
+    fn grow_by_factor_min_step() {
+        let b = Bytes::new(0x1000).grow_by_factor(
+            1,
+            half(),
+            Bytes::new(0x1_0000),
+            Bytes::new(usize::MAX),
+        );
+
+        assert_eq!(b, Bytes::new(0x1_1000));
     }
-}
-impl Mul<usize> for Bytes {
-    type Output = Bytes;
-    fn mul(self, rhs: usize) -> Self::Output {
-        Self(self.0 * rhs)
+
+    #[test]
+    fn grow_by_factor_from_zero() {
+        let b =
+            Bytes::zero().grow_by_factor(1, half(), Bytes::new(0x1_0000), Bytes::new(usize::MAX));
+
+        assert_eq!(b, Bytes::new(0x1_0000));
     }
-}
-impl MulAssign<usize> for Bytes {
-    fn mul_assign(&mut self, rhs: usize) {
-        *self = *self * rhs;
+
+    #[test]
+    fn grow_by_factor_cap() {
+        let b =
+            Bytes::new(0x10_0000).grow_by_factor(1, half(), Bytes::zero(), Bytes::new(0x12_0000));
+
+        assert_eq!(b, Bytes::new(0x12_0000));
     }
-}
-impl Div<usize> for Bytes {
-    type Output = Bytes;
 
-    fn div(self, rhs: usize) -> Self::Output {
-        Self(self.0 / rhs)
+    #[test]
+    fn grow_by_factor_already_over_cap() {
+        let b =
+            Bytes::new(0x10_0000).grow_by_factor(1, half(), Bytes::zero(), Bytes::new(0x8_0000));
+
+        assert_eq!(b, Bytes::new(0x10_0000));
     }
-}
-impl DivAssign<usize> for Bytes {
-    fn div_assign(&mut self, rhs: usize) {
-        *self = *self / rhs;
+
+    #[test]
+    fn grow_by_factor_near_overflow() {
+        let max = Bytes::new(usize::MAX);
+
+        assert_eq!(
+            Bytes::new(usize::MAX - 1).grow_by_factor(1, half(), Bytes::zero(), max),
+            max
+        );
+        assert_eq!(
+            Bytes::new(usize::MAX / 2 + 1).grow_by_factor(usize::MAX, half(), Bytes::zero(), max),
+            max
+        );
+        assert_eq!(
+            Bytes::new(usize::MAX).grow_by_factor(1, half(), max, max),
+            max
+        );
     }
-}
-impl From<usize> for Bytes {
-    fn from(b: usize) -> Self {
-        Self::new(b)
+
+    #[test]
+    fn grow_by_factor_pages_rounds_up() {
+        let n = Bytes::new(0x1000).grow_by_factor_pages::<Size4KiB>(
+            1,
+            half(),
+            Bytes::new(0x10),
+            Bytes::new(usize::MAX),
+        );
+
+        assert_eq!(n, NumOfPages::new(2));
     }
-}
-impl fmt::Display for Bytes {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let unit = if self.0 == 1 { "byte" } else { "bytes" };
-        write!(f, "{} {}", self.0, unit)
+
+    #[test]
+    fn grow_by_factor_pages_min_step() {
+        let n = Bytes::new(0x2000).grow_by_factor_pages::<Size4KiB>(
+            1,
+            half(),
+            Bytes::new(0x1_0000),
+            Bytes::new(usize::MAX),
+        );
+
+        assert_eq!(n, NumOfPages::new(0x12));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use x86_64::structures::paging::{Size1GiB, Size2MiB, Size4KiB};
-    use x86_64::{PhysAddr, VirtAddr};
+    #[test]
+    fn grow_by_factor_pages_cap_rounds_down() {
+        let n = Bytes::new(0x20_0000).grow_by_factor_pages::<Size2MiB>(
+            1,
+            NonZeroUsize::new(1).unwrap(),
+            Bytes::zero(),
+            Bytes::new(0x5f_ffff),
+        );
+
+        assert_eq!(n, NumOfPages::new(2));
+    }
 
     #[test]
-    fn get_value_from_bytes() {
-        let bytes = Bytes::new(334);
-        assert_eq!(bytes.as_usize(), 334);
+    fn grow_by_factor_pages_never_shrinks() {
+        let n = Bytes::new(0x1001).grow_by_factor_pages::<Size4KiB>(
+            1,
+            half(),
+            Bytes::zero(),
+            Bytes::new(0x1000),
+        );
+
+        assert_eq!(n, NumOfPages::new(2));
     }
 
     #[test]
-    fn bytes_to_pages() {
-        let bytes = Bytes::new(0x40000000);
-        assert_eq!(bytes.as_num_of_pages::<Size4KiB>().as_usize(), 0x40000);
-        assert_eq!(bytes.as_num_of_pages::<Size2MiB>().as_usize(), 512);
-        assert_eq!(bytes.as_num_of_pages::<Size1GiB>().as_usize(), 1);
+    fn grow_by_factor_pages_near_overflow() {
+        let n = Bytes::new(usize::MAX - 0xfff).grow_by_factor_pages::<Size4KiB>(
+            usize::MAX,
+            NonZeroUsize::new(1).unwrap(),
+            Bytes::new(usize::MAX),
+            Bytes::new(usize::MAX),
+        );
+
+        assert_eq!(n, NumOfPages::new(usize::MAX / 0x1000));
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn addition_bytes_to_bytes() {
         let b1 = Bytes::new(3);
         let b2 = Bytes::new(1);
@@ -204,6 +2478,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
     fn add_usize_to_bytes() {
         let b = Bytes::new(3);
 
@@ -211,6 +2486,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn subtraction_bytes_from_bytes() {
         let b1 = Bytes::new(3);
         let b2 = Bytes::new(1);
@@ -220,6 +2496,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
     fn subtract_usize_from_bytes() {
         let b = Bytes::new(5);
 
@@ -227,6 +2504,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn add_assign_bytes_to_bytes() {
         let mut b1 = Bytes::new(3);
         b1 += Bytes::new(1);
@@ -235,6 +2513,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
     fn add_assign_usize_to_bytes() {
         let mut b1 = Bytes::new(3);
         b1 += 1;
@@ -243,6 +2522,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn sub_assign_bytes_to_bytes() {
         let mut b1 = Bytes::new(3);
         b1 -= Bytes::new(1);
@@ -251,6 +2531,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
     fn sub_assign_usize_to_bytes() {
         let mut b1 = Bytes::new(10);
         b1 -= 3;
@@ -259,6 +2540,71 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn add_num_of_pages_to_bytes() {
+        let b = Bytes::new(0x40);
+
+        assert_eq!(b + NumOfPages::<Size4KiB>::new(2), Bytes::new(0x2040));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn add_num_of_pages_of_different_sizes_to_bytes() {
+        let b = Bytes::new(0x40) + NumOfPages::<Size2MiB>::new(1) + NumOfPages::<Size4KiB>::new(3);
+
+        assert_eq!(b, Bytes::new(0x20_3040));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sub_num_of_pages_from_bytes() {
+        let b =
+            Bytes::new(0x40_0000) - NumOfPages::<Size2MiB>::new(1) - NumOfPages::<Size4KiB>::new(1);
+
+        assert_eq!(b, Bytes::new(0x1f_f000));
+    }
+
+    #[test]
+    #[cfg(all(
+        not(feature = "no-panic"),
+        all(debug_assertions, not(feature = "overflow-checks"))
+    ))]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn sub_num_of_pages_from_bytes_underflow() {
+        let _ = Bytes::new(0xfff) - NumOfPages::<Size4KiB>::new(1);
+    }
+
+    #[test]
+    #[cfg(all(
+        not(feature = "no-panic"),
+        all(debug_assertions, not(feature = "overflow-checks"))
+    ))]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn add_num_of_pages_to_bytes_overflow() {
+        let _ = Bytes::new(usize::MAX) + NumOfPages::<Size4KiB>::new(1);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn add_assign_num_of_pages_to_bytes() {
+        let mut b = Bytes::new(0x10);
+        b += NumOfPages::<Size1GiB>::new(1);
+        b += NumOfPages::<Size4KiB>::new(1);
+
+        assert_eq!(b, Bytes::new(0x4000_1010));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sub_assign_num_of_pages_from_bytes() {
+        let mut b = Bytes::new(0x3010);
+        b -= NumOfPages::<Size4KiB>::new(3);
+
+        assert_eq!(b, Bytes::new(0x10));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn mul_bytes_by_usize() {
         let b = Bytes::new(3);
         let mul = b * 4;
@@ -267,6 +2613,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn mul_assign_bytes_by_usize() {
         let mut b = Bytes::new(3);
         b *= 4;
@@ -275,6 +2622,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn div_bytes_by_usize() {
         let b1 = Bytes::new(3);
         let div = b1 / 2;
@@ -283,6 +2631,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn divassign_bytes_by_usize() {
         let mut b = Bytes::new(3);
         b /= 2;
@@ -290,6 +2639,42 @@ mod tests {
         assert_eq!(b.as_usize(), 1);
     }
 
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sum_owned() {
+        let s: Bytes = vec![Bytes::new(3), Bytes::new(4), Bytes::new(5)]
+            .into_iter()
+            .sum();
+
+        assert_eq!(s, Bytes::new(12));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sum_borrowed() {
+        let s: Bytes = [Bytes::new(3), Bytes::new(4), Bytes::new(5)].iter().sum();
+
+        assert_eq!(s, Bytes::new(12));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sum_empty() {
+        let s: Bytes = iter::empty::<Bytes>().sum();
+
+        assert_eq!(s, Bytes::zero());
+    }
+
+    #[test]
+    #[cfg(all(
+        not(feature = "no-panic"),
+        all(debug_assertions, not(feature = "overflow-checks"))
+    ))]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn sum_overflow() {
+        let _: Bytes = [Bytes::new(usize::MAX), Bytes::new(1)].iter().sum();
+    }
+
     #[test]
     fn bytes_zero() {
         let b = Bytes::zero();
@@ -304,10 +2689,93 @@ mod tests {
         assert_eq!(b, Bytes::new(3));
     }
 
+    #[test]
+    fn exact_pages() {
+        let b = Bytes::new(0x3000);
+
+        assert_eq!(
+            b.as_num_of_pages_exact::<Size4KiB>(),
+            Ok(NumOfPages::new(3))
+        );
+    }
+
+    #[test]
+    fn exact_pages_not_a_multiple() {
+        let b = Bytes::new(0x3001);
+
+        assert_eq!(
+            b.as_num_of_pages_exact::<Size4KiB>(),
+            Err(ConversionError::NotAMultiple {
+                remainder: Bytes::new(1)
+            })
+        );
+    }
+
+    #[test]
+    fn try_from_u64() {
+        assert_eq!(Bytes::try_from(3_u64), Ok(Bytes::new(3)));
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    fn try_from_u64_overflow() {
+        assert_eq!(Bytes::try_from(u64::MAX), Err(ConversionError::Overflow));
+    }
+
+    #[test]
+    fn parse_without_unit() {
+        assert_eq!("4096".parse(), Ok(Bytes::new(4096)));
+    }
+
+    #[test]
+    fn parse_bytes_unit() {
+        assert_eq!("1 byte".parse(), Ok(Bytes::new(1)));
+        assert_eq!("2 bytes".parse(), Ok(Bytes::new(2)));
+        assert_eq!("3B".parse(), Ok(Bytes::new(3)));
+    }
+
+    #[test]
+    fn parse_binary_units() {
+        assert_eq!("4 KiB".parse(), Ok(Bytes::new(0x1000)));
+        assert_eq!("16 MiB".parse(), Ok(Bytes::new(0x100_0000)));
+        assert_eq!("1GiB".parse(), Ok(Bytes::new(0x4000_0000)));
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert_eq!("".parse::<Bytes>(), Err(ParseBytesError::Empty));
+    }
+
+    #[test]
+    fn parse_invalid_digit() {
+        assert_eq!("-3".parse::<Bytes>(), Err(ParseBytesError::InvalidDigit));
+        assert_eq!("MiB".parse::<Bytes>(), Err(ParseBytesError::InvalidDigit));
+        assert_eq!(
+            "1.5 MiB".parse::<Bytes>(),
+            Err(ParseBytesError::InvalidDigit)
+        );
+        assert_eq!("1 2".parse::<Bytes>(), Err(ParseBytesError::InvalidDigit));
+    }
+
+    #[test]
+    fn parse_unknown_unit() {
+        assert_eq!("3 MB".parse::<Bytes>(), Err(ParseBytesError::UnknownUnit));
+        assert_eq!("3 kib".parse::<Bytes>(), Err(ParseBytesError::UnknownUnit));
+    }
+
+    #[test]
+    fn parse_overflow() {
+        assert_eq!(
+            "99999999999999999999".parse::<Bytes>(),
+            Err(ParseBytesError::Overflow)
+        );
+        assert_eq!("16 EiB".parse::<Bytes>(), Err(ParseBytesError::Overflow));
+    }
+
     #[test]
     fn debug() {
         let b = Bytes::new(3);
-        let f = format!("{:?}", b);
+        let f = format!("{b:?}");
 
         assert_eq!(f, format!("Bytes(3)"));
     }
@@ -315,7 +2783,7 @@ mod tests {
     #[test]
     fn display_0() {
         let b = Bytes::zero();
-        let f = format!("{}", b);
+        let f = format!("{b}");
 
         assert_eq!(f, format!("0 bytes"));
     }
@@ -323,7 +2791,7 @@ mod tests {
     #[test]
     fn display_1() {
         let b = Bytes::new(1);
-        let f = format!("{}", b);
+        let f = format!("{b}");
 
         assert_eq!(f, format!("1 byte"));
     }
@@ -331,12 +2799,90 @@ mod tests {
     #[test]
     fn display_2() {
         let b = Bytes::new(2);
-        let f = format!("{}", b);
+        let f = format!("{b}");
 
         assert_eq!(f, format!("2 bytes"));
     }
 
     #[test]
+    fn display_binary_0() {
+        let b = Bytes::zero();
+        let f = format!("{}", b.display_binary());
+
+        assert_eq!(f, format!("0 bytes"));
+    }
+
+    #[test]
+    fn display_binary_not_a_multiple() {
+        let b = Bytes::new(4097);
+        let f = format!("{}", b.display_binary());
+
+        assert_eq!(f, format!("4097 bytes"));
+    }
+
+    #[test]
+    fn display_binary_largest_unit() {
+        assert_eq!(format!("{}", Bytes::new(0x1000).display_binary()), "4 KiB");
+        assert_eq!(format!("{}", Bytes::new(0x1800).display_binary()), "6 KiB");
+        assert_eq!(
+            format!("{}", Bytes::new(0x100_0000).display_binary()),
+            "16 MiB"
+        );
+        assert_eq!(
+            format!("{}", Bytes::new(0x4000_0000).display_binary()),
+            "1 GiB"
+        );
+    }
+
+    #[test]
+    fn display_binary_round_trip() {
+        let b = Bytes::new(0x30_0000);
+
+        assert_eq!(b.display_binary().to_string().parse(), Ok(b));
+    }
+
+    #[test]
+    fn display_grouped_0() {
+        let b = Bytes::zero();
+        let f = format!("{}", b.display_grouped(','));
+
+        assert_eq!(f, format!("0 bytes"));
+    }
+
+    #[test]
+    fn display_grouped_under_1000() {
+        let b = Bytes::new(999);
+        let f = format!("{}", b.display_grouped(','));
+
+        assert_eq!(f, format!("999 bytes"));
+    }
+
+    #[test]
+    fn display_grouped_1000() {
+        let b = Bytes::new(1000);
+        let f = format!("{}", b.display_grouped(','));
+
+        assert_eq!(f, format!("1,000 bytes"));
+    }
+
+    #[test]
+    fn display_grouped_many_groups() {
+        let b = Bytes::new(0x4000_0000);
+        let f = format!("{}", b.display_grouped('_'));
+
+        assert_eq!(f, format!("1_073_741_824 bytes"));
+    }
+
+    #[test]
+    fn display_grouped_zero_padded_groups() {
+        let b = Bytes::new(1_002_003);
+        let f = format!("{}", b.display_grouped(','));
+
+        assert_eq!(f, format!("1,002,003 bytes"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn add_bytes_to_virt_addr() {
         let a = VirtAddr::new(0x1000);
         let bytes = Bytes::new(4);
@@ -345,6 +2891,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn add_bytes_to_phys_addr() {
         let a = PhysAddr::new(0x1000);
         let bytes = Bytes::new(4);
@@ -353,6 +2900,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn add_assign_bytes_to_virt_addr() {
         let mut a = VirtAddr::new(0x1000);
         a += Bytes::new(4);
@@ -361,6 +2909,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn add_assign_bytes_to_phys_addr() {
         let mut a = PhysAddr::new(0x1000);
         a += Bytes::new(4);
@@ -369,6 +2918,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn sub_bytes_from_virt_addr() {
         let a = VirtAddr::new(0x1000);
         let bytes = Bytes::new(4);
@@ -377,6 +2927,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn sub_bytes_from_phys_addr() {
         let a = PhysAddr::new(0x1000);
         let bytes = Bytes::new(4);
@@ -385,6 +2936,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn sub_assign_bytes_from_virt_addr() {
         let mut a = VirtAddr::new(0x1000);
         a -= Bytes::new(4);
@@ -393,6 +2945,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn sub_assign_bytes_from_phys_addr() {
         let mut a = PhysAddr::new(0x1000);
         a -= Bytes::new(4);
@@ -0,0 +1,318 @@
+use crate::Bytes;
+use core::convert::TryFrom;
+#[cfg(all(target_has_atomic = "64", target_has_atomic = "ptr"))]
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// A histogram of sizes with `N` power-of-two buckets.
+///
+/// Bucket `i` counts the sizes in `2^i..2^(i + 1)`, except that bucket 0 also counts zero and the
+/// last bucket counts all the sizes larger than its lower bound. `N` must not be zero.
+///
+/// ```rust
+/// use os_units::{Bytes, BytesHistogram};
+///
+/// let mut h = BytesHistogram::<8>::new();
+///
+/// h.record(Bytes::new(16));
+/// h.record(Bytes::new(31));
+/// h.record(Bytes::new(0x1000));
+///
+/// assert_eq!(h.count(4), 2);
+/// assert_eq!(h.count(7), 1);
+/// assert_eq!(h.total_recorded(), Bytes::new(0x102f));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct BytesHistogram<const N: usize> {
+    counts: [u64; N],
+    total: Bytes,
+}
+impl<const N: usize> BytesHistogram<N> {
+    const NON_EMPTY: () = assert!(N > 0, "a histogram must have at least one bucket");
+
+    /// Creates a new histogram with no sizes recorded.
+    #[must_use]
+    pub const fn new() -> Self {
+        let () = Self::NON_EMPTY;
+
+        Self {
+            counts: [0; N],
+            total: Bytes::zero(),
+        }
+    }
+
+    /// Returns the index of the bucket which counts `bytes`.
+    #[must_use]
+    pub const fn bucket(bytes: Bytes) -> usize {
+        let i = match bytes.as_usize().checked_ilog2() {
+            Some(i) => i as usize,
+            None => 0,
+        };
+
+        if i < N {
+            i
+        } else {
+            N - 1
+        }
+    }
+
+    /// Counts `bytes` in its bucket.
+    pub fn record(&mut self, bytes: Bytes) {
+        let count = &mut self.counts[Self::bucket(bytes)];
+
+        *count = count.saturating_add(1);
+        self.total = Bytes::new(self.total.as_usize().saturating_add(bytes.as_usize()));
+    }
+
+    /// Returns the number of the sizes recorded in bucket `bucket`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `bucket >= N`.
+    #[must_use]
+    pub fn count(&self, bucket: usize) -> u64 {
+        self.counts[bucket]
+    }
+
+    /// Returns the sum of all the recorded sizes, saturating at `usize::MAX`.
+    #[must_use]
+    pub const fn total_recorded(&self) -> Bytes {
+        self.total
+    }
+
+    /// Returns an iterator over the lower bound and the count of each bucket.
+    ///
+    /// The buckets whose lower bound does not fit in `usize` are skipped, as no size is recorded
+    /// in them.
+    pub fn iter(&self) -> impl Iterator<Item = (Bytes, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .map_while(|(i, count)| lower_bound(i).map(|b| (b, *count)))
+    }
+}
+impl<const N: usize> Default for BytesHistogram<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// [`BytesHistogram`] which can be shared between threads.
+///
+/// The counts are updated with [`Ordering::Relaxed`], so a [`snapshot`](Self::snapshot) taken
+/// while other threads are recording may not match any point in time, but every count in it is
+/// at least the one in an earlier snapshot.
+///
+/// ```rust
+/// use os_units::{AtomicBytesHistogram, Bytes};
+///
+/// static SIZES: AtomicBytesHistogram<16> = AtomicBytesHistogram::new();
+///
+/// SIZES.record(Bytes::new(0x40));
+///
+/// assert_eq!(SIZES.snapshot().count(6), 1);
+/// ```
+#[cfg(all(target_has_atomic = "64", target_has_atomic = "ptr"))]
+#[derive(Debug)]
+pub struct AtomicBytesHistogram<const N: usize> {
+    counts: [AtomicU64; N],
+    total: AtomicUsize,
+}
+#[cfg(all(target_has_atomic = "64", target_has_atomic = "ptr"))]
+impl<const N: usize> AtomicBytesHistogram<N> {
+    /// Creates a new histogram with no sizes recorded.
+    #[must_use]
+    pub const fn new() -> Self {
+        let () = BytesHistogram::<N>::NON_EMPTY;
+
+        Self {
+            counts: [const { AtomicU64::new(0) }; N],
+            total: AtomicUsize::new(0),
+        }
+    }
+
+    /// Counts `bytes` in its bucket.
+    pub fn record(&self, bytes: Bytes) {
+        let count = &self.counts[BytesHistogram::<N>::bucket(bytes)];
+
+        let _ = count.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+            Some(n.saturating_add(1))
+        });
+
+        let _ = self
+            .total
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |t| {
+                Some(t.saturating_add(bytes.as_usize()))
+            });
+    }
+
+    /// Returns the counts and the total recorded so far.
+    #[must_use]
+    pub fn snapshot(&self) -> BytesHistogram<N> {
+        let mut h = BytesHistogram::new();
+
+        for (count, atomic) in h.counts.iter_mut().zip(&self.counts) {
+            *count = atomic.load(Ordering::Relaxed);
+        }
+        h.total = Bytes::new(self.total.load(Ordering::Relaxed));
+
+        h
+    }
+}
+#[cfg(all(target_has_atomic = "64", target_has_atomic = "ptr"))]
+impl<const N: usize> Default for AtomicBytesHistogram<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn lower_bound(bucket: usize) -> Option<Bytes> {
+    if bucket == 0 {
+        Some(Bytes::zero())
+    } else {
+        let shift = u32::try_from(bucket).ok()?;
+
+        1_usize.checked_shl(shift).map(Bytes::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AtomicBytesHistogram;
+    use super::BytesHistogram;
+    use crate::Bytes;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn bucket_zero_and_one() {
+        assert_eq!(BytesHistogram::<8>::bucket(Bytes::zero()), 0);
+        assert_eq!(BytesHistogram::<8>::bucket(Bytes::new(1)), 0);
+    }
+
+    #[test]
+    fn bucket_boundaries() {
+        assert_eq!(BytesHistogram::<16>::bucket(Bytes::new(2)), 1);
+        assert_eq!(BytesHistogram::<16>::bucket(Bytes::new(3)), 1);
+        assert_eq!(BytesHistogram::<16>::bucket(Bytes::new(4)), 2);
+        assert_eq!(BytesHistogram::<16>::bucket(Bytes::new(0xfff)), 11);
+        assert_eq!(BytesHistogram::<16>::bucket(Bytes::new(0x1000)), 12);
+        assert_eq!(BytesHistogram::<16>::bucket(Bytes::new(0x1001)), 12);
+    }
+
+    #[test]
+    fn bucket_clamped_to_last() {
+        assert_eq!(BytesHistogram::<4>::bucket(Bytes::new(7)), 2);
+        assert_eq!(BytesHistogram::<4>::bucket(Bytes::new(8)), 3);
+        assert_eq!(BytesHistogram::<4>::bucket(Bytes::new(0x1000)), 3);
+        assert_eq!(BytesHistogram::<4>::bucket(Bytes::new(usize::MAX)), 3);
+    }
+
+    #[test]
+    fn bucket_single() {
+        assert_eq!(BytesHistogram::<1>::bucket(Bytes::new(usize::MAX)), 0);
+    }
+
+    #[test]
+    fn record() {
+        let mut h = BytesHistogram::<4>::new();
+
+        h.record(Bytes::new(2));
+        h.record(Bytes::new(3));
+        h.record(Bytes::new(0x100));
+
+        assert_eq!(h.count(0), 0);
+        assert_eq!(h.count(1), 2);
+        assert_eq!(h.count(2), 0);
+        assert_eq!(h.count(3), 1);
+        assert_eq!(h.total_recorded(), Bytes::new(0x105));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn count_out_of_range() {
+        let _ = BytesHistogram::<4>::new().count(4);
+    }
+
+    #[test]
+    fn total_saturates() {
+        let mut h = BytesHistogram::<4>::new();
+
+        h.record(Bytes::new(usize::MAX));
+        h.record(Bytes::new(1));
+
+        assert_eq!(h.total_recorded(), Bytes::new(usize::MAX));
+    }
+
+    #[test]
+    fn iter() {
+        let mut h = BytesHistogram::<4>::new();
+
+        h.record(Bytes::new(1));
+        h.record(Bytes::new(5));
+        h.record(Bytes::new(0x1000));
+
+        let v: Vec<_> = h.iter().collect();
+
+        assert_eq!(
+            v,
+            [
+                (Bytes::zero(), 1),
+                (Bytes::new(2), 0),
+                (Bytes::new(4), 1),
+                (Bytes::new(8), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn iter_skips_unreachable_buckets() {
+        let h = BytesHistogram::<80>::new();
+
+        assert_eq!(h.iter().count(), usize::BITS as usize);
+        assert_eq!(
+            h.iter().last(),
+            Some((Bytes::new(1 << (usize::BITS - 1)), 0))
+        );
+    }
+
+    #[test]
+    fn atomic_snapshot() {
+        let h = AtomicBytesHistogram::<8>::new();
+
+        h.record(Bytes::new(4));
+        h.record(Bytes::new(0x1000));
+
+        let s = h.snapshot();
+
+        assert_eq!(s.count(2), 1);
+        assert_eq!(s.count(7), 1);
+        assert_eq!(s.total_recorded(), Bytes::new(0x1004));
+    }
+
+    #[test]
+    fn atomic_concurrent_record() {
+        let h = Arc::new(AtomicBytesHistogram::<16>::new());
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let h = Arc::clone(&h);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        h.record(Bytes::new(1 << i));
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let s = h.snapshot();
+
+        for i in 0..8 {
+            assert_eq!(s.count(i), 1000);
+        }
+        assert_eq!(s.total_recorded(), Bytes::new(1000 * 0xff));
+    }
+}
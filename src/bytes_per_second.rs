@@ -0,0 +1,179 @@
+use crate::Bytes;
+use core::convert::TryFrom;
+use core::fmt;
+#[cfg(not(feature = "no-panic"))]
+use core::ops::Mul;
+use core::time::Duration;
+
+pub(crate) const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+const UNITS: [(&str, u32); 6] = [
+    ("EiB", 60),
+    ("PiB", 50),
+    ("TiB", 40),
+    ("GiB", 30),
+    ("MiB", 20),
+    ("KiB", 10),
+];
+
+/// A throughput in bytes per second, created by [`Bytes::per`].
+///
+/// ```rust
+/// use core::time::Duration;
+/// use os_units::Bytes;
+///
+/// let rate = Bytes::new(0x300_0000).per(Duration::from_secs(4)).unwrap();
+///
+/// assert_eq!(rate.as_u64(), 0xc0_0000);
+/// assert_eq!(
+///     rate.checked_mul(Duration::from_millis(500)),
+///     Some(Bytes::new(0x60_0000))
+/// );
+/// assert_eq!(rate.to_string(), "12.0 MiB/s");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BytesPerSecond(u64);
+impl BytesPerSecond {
+    /// Creates a new instance of `bytes_per_second`.
+    #[must_use]
+    pub const fn new(bytes_per_second: u64) -> Self {
+        Self(bytes_per_second)
+    }
+
+    /// Returns the value as `u64`.
+    #[must_use]
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the bytes transferred in `d` at this rate, rounded down, or [`None`] if the result
+    /// does not fit in `usize`.
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use os_units::{Bytes, BytesPerSecond};
+    ///
+    /// let rate = BytesPerSecond::new(1000);
+    ///
+    /// assert_eq!(rate.checked_mul(Duration::from_millis(1500)), Some(Bytes::new(1500)));
+    /// assert_eq!(BytesPerSecond::new(u64::MAX).checked_mul(Duration::from_secs(2)), None);
+    /// ```
+    #[must_use]
+    pub fn checked_mul(self, d: Duration) -> Option<Bytes> {
+        let rate = u128::from(self.0);
+        let bytes =
+            rate * u128::from(d.as_secs()) + rate * u128::from(d.subsec_nanos()) / NANOS_PER_SEC;
+
+        usize::try_from(bytes).ok().map(Bytes::new)
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl Mul<Duration> for BytesPerSecond {
+    type Output = Bytes;
+
+    /// Returns the bytes transferred in `rhs` at this rate, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the result does not fit in `usize`.
+    fn mul(self, rhs: Duration) -> Bytes {
+        self.checked_mul(rhs)
+            .expect("attempt to multiply with overflow")
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl Mul<BytesPerSecond> for Duration {
+    type Output = Bytes;
+
+    fn mul(self, rhs: BytesPerSecond) -> Bytes {
+        rhs * self
+    }
+}
+impl fmt::Display for BytesPerSecond {
+    /// Displays the rate in the largest binary unit not exceeding it with one decimal place,
+    /// rounded down, e.g. `12.4 MiB/s`. Rates below 1 KiB/s are displayed in bytes, e.g.
+    /// `512 bytes/s`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = UNITS.iter().find(|(_, shift)| self.0 >> shift > 0);
+
+        match unit {
+            Some((name, shift)) => {
+                let tenths = ((u128::from(self.0) * 10) >> shift) % 10;
+
+                write!(f, "{}.{} {}/s", self.0 >> shift, tenths, name)
+            }
+            None if self.0 == 1 => f.write_str("1 byte/s"),
+            None => write!(f, "{} bytes/s", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BytesPerSecond;
+    use crate::Bytes;
+    use core::time::Duration;
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn mul_duration() {
+        let rate = BytesPerSecond::new(1000);
+
+        assert_eq!(rate * Duration::from_secs(3), Bytes::new(3000));
+        assert_eq!(rate * Duration::from_millis(1500), Bytes::new(1500));
+        assert_eq!(Duration::from_nanos(999_999) * rate, Bytes::zero());
+    }
+
+    #[test]
+    #[cfg(all(not(feature = "no-panic"), target_pointer_width = "64"))]
+    fn mul_duration_does_not_overflow() {
+        // `(1 << 40) * (1 << 20) * 1_000_000_000` does not fit in `u64`.
+        let rate = BytesPerSecond::new(1 << 40);
+
+        assert_eq!(rate * Duration::from_secs(1 << 20), Bytes::new(1 << 60));
+        assert_eq!(
+            rate * Duration::new(1 << 20, 500_000_000),
+            Bytes::new((1 << 60) + (1 << 39))
+        );
+    }
+
+    #[test]
+    fn checked_mul_overflow() {
+        let rate = BytesPerSecond::new(usize::MAX as u64);
+
+        assert_eq!(
+            rate.checked_mul(Duration::from_secs(1)),
+            Some(Bytes::new(usize::MAX))
+        );
+        assert_eq!(rate.checked_mul(Duration::from_secs(2)), None);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn mul_duration_overflow() {
+        let _ = BytesPerSecond::new(u64::MAX) * Duration::from_secs(2);
+    }
+
+    #[test]
+    fn display_bytes() {
+        assert_eq!(BytesPerSecond::new(0).to_string(), "0 bytes/s");
+        assert_eq!(BytesPerSecond::new(1).to_string(), "1 byte/s");
+        assert_eq!(BytesPerSecond::new(1023).to_string(), "1023 bytes/s");
+    }
+
+    #[test]
+    fn display_units() {
+        assert_eq!(BytesPerSecond::new(1024).to_string(), "1.0 KiB/s");
+        assert_eq!(BytesPerSecond::new(1536).to_string(), "1.5 KiB/s");
+        assert_eq!(
+            BytesPerSecond::new(12 * 0x10_0000 + 0x6_6667).to_string(),
+            "12.4 MiB/s"
+        );
+        assert_eq!(
+            BytesPerSecond::new(0x4000_0000 - 1).to_string(),
+            "1023.9 MiB/s"
+        );
+        assert_eq!(BytesPerSecond::new(u64::MAX).to_string(), "15.9 EiB/s");
+    }
+}
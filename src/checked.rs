@@ -0,0 +1,240 @@
+use crate::{Bytes, NumOfPages};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use x86_64::structures::paging::PageSize;
+
+/// A wrapper of [`Bytes`] or [`NumOfPages`] whose operators are checked, like
+/// [`Wrapping`](core::num::Wrapping) for the wrapping arithmetic.
+///
+/// Once an operation overflows or divides by zero, the value becomes invalid and stays so through
+/// the rest of the chain, and [`Checked::get`] returns [`None`]. This lets a long chain of size
+/// arithmetic be written with the operators and checked once at the end. The operators never
+/// panic, so they are available with the `no-panic` feature.
+///
+/// An invalid value compares equal to another invalid value, and less than every valid value.
+///
+/// ```rust
+/// use os_units::{Bytes, Checked};
+///
+/// let header = Bytes::new(0x40);
+/// let entry = Bytes::new(0x10);
+///
+/// let total = Checked::new(entry) * 0x100 + header - Bytes::new(0x8);
+/// assert_eq!(total.get(), Some(Bytes::new(0x1038)));
+///
+/// let total = Checked::new(entry) * usize::MAX + header - Bytes::new(0x8);
+/// assert_eq!(total.get(), None);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<T>(Option<T>);
+impl<T> Checked<T> {
+    /// Creates a new valid instance.
+    #[must_use]
+    pub const fn new(value: T) -> Self {
+        Self(Some(value))
+    }
+
+    /// Returns the value, or [`None`] if an operation has overflowed or divided by zero.
+    #[must_use]
+    pub fn get(self) -> Option<T> {
+        self.0
+    }
+
+    fn zip_with(self, rhs: Self, f: impl FnOnce(T, T) -> Option<T>) -> Self {
+        match (self.0, rhs.0) {
+            (Some(a), Some(b)) => Self(f(a, b)),
+            _ => Self(None),
+        }
+    }
+}
+impl<T> From<T> for Checked<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+impl<T> From<Option<T>> for Checked<T> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+impl<T> From<Checked<T>> for Option<T> {
+    fn from(c: Checked<T>) -> Self {
+        c.get()
+    }
+}
+
+/// Implements the operators of `Checked<$ty>` with the `checked_*` methods of `$ty`.
+///
+/// The generic parameters are written in brackets like `const_op_impl`, e.g.
+/// `impl[T: PageSize] Checked<NumOfPages<T>>`.
+macro_rules! checked_ops_impl {
+    (impl $([$($g:tt)*])? Checked<$ty:ty>) => {
+        impl<$($($g)*)?> Add for Checked<$ty> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                self.zip_with(rhs, <$ty>::checked_add)
+            }
+        }
+        impl<$($($g)*)?> Add<$ty> for Checked<$ty> {
+            type Output = Self;
+
+            fn add(self, rhs: $ty) -> Self {
+                self + Self::new(rhs)
+            }
+        }
+        impl<$($($g)*)?> AddAssign for Checked<$ty> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+        impl<$($($g)*)?> AddAssign<$ty> for Checked<$ty> {
+            fn add_assign(&mut self, rhs: $ty) {
+                *self = *self + rhs;
+            }
+        }
+        impl<$($($g)*)?> Sub for Checked<$ty> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                self.zip_with(rhs, <$ty>::checked_sub)
+            }
+        }
+        impl<$($($g)*)?> Sub<$ty> for Checked<$ty> {
+            type Output = Self;
+
+            fn sub(self, rhs: $ty) -> Self {
+                self - Self::new(rhs)
+            }
+        }
+        impl<$($($g)*)?> SubAssign for Checked<$ty> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+        impl<$($($g)*)?> SubAssign<$ty> for Checked<$ty> {
+            fn sub_assign(&mut self, rhs: $ty) {
+                *self = *self - rhs;
+            }
+        }
+        impl<$($($g)*)?> Mul<usize> for Checked<$ty> {
+            type Output = Self;
+
+            fn mul(self, rhs: usize) -> Self {
+                Self(self.0.and_then(|a| a.checked_mul(rhs)))
+            }
+        }
+        impl<$($($g)*)?> MulAssign<usize> for Checked<$ty> {
+            fn mul_assign(&mut self, rhs: usize) {
+                *self = *self * rhs;
+            }
+        }
+        impl<$($($g)*)?> Div<usize> for Checked<$ty> {
+            type Output = Self;
+
+            fn div(self, rhs: usize) -> Self {
+                Self(self.0.and_then(|a| a.checked_div(rhs)))
+            }
+        }
+        impl<$($($g)*)?> DivAssign<usize> for Checked<$ty> {
+            fn div_assign(&mut self, rhs: usize) {
+                *self = *self / rhs;
+            }
+        }
+    };
+}
+
+checked_ops_impl!(impl Checked<Bytes>);
+checked_ops_impl!(impl[T: PageSize] Checked<NumOfPages<T>>);
+
+#[cfg(test)]
+mod tests {
+    use super::Checked;
+    use crate::{Bytes, NumOfPages};
+    use x86_64::structures::paging::{Size1GiB, Size4KiB};
+
+    #[test]
+    fn chain_in_range() {
+        let c = Checked::new(Bytes::new(0x1000)) + Bytes::new(0x20) - Bytes::new(0x10);
+        let c = c * 3 / 2;
+
+        assert_eq!(c.get(), Some(Bytes::new(0x1818)));
+    }
+
+    #[test]
+    fn overflow_in_middle_of_chain() {
+        // The result would fit in `usize` if the addition wrapped around.
+        let c = Checked::new(Bytes::new(usize::MAX)) + Bytes::new(1) - Bytes::new(2);
+
+        assert_eq!(c.get(), None);
+    }
+
+    #[test]
+    fn invalid_value_propagates() {
+        let c = Checked::new(Bytes::new(1)) - Bytes::new(2);
+        let c = c + Bytes::new(0x1000);
+
+        assert_eq!(c.get(), None);
+        assert_eq!((Checked::new(Bytes::new(1)) + c).get(), None);
+    }
+
+    #[test]
+    fn mul_and_div() {
+        let c = Checked::new(NumOfPages::<Size4KiB>::new(usize::MAX / 2 + 1));
+
+        assert_eq!((c * 2).get(), None);
+        assert_eq!((c / 0).get(), None);
+        assert_eq!((c / 2 * 2).get(), Some(NumOfPages::new(usize::MAX / 2 + 1)));
+    }
+
+    #[test]
+    fn num_of_pages_chain() {
+        let c = Checked::new(NumOfPages::<Size1GiB>::new(3)) + Checked::new(NumOfPages::new(4))
+            - NumOfPages::new(2);
+
+        assert_eq!(c.get(), Some(NumOfPages::new(5)));
+        assert_eq!((c - NumOfPages::new(6) + NumOfPages::new(6)).get(), None);
+    }
+
+    #[test]
+    fn assign_operators() {
+        let mut c = Checked::new(Bytes::new(0x10));
+        c += Bytes::new(0x10);
+        c -= Checked::new(Bytes::new(0x8));
+        c *= 2;
+        c /= 3;
+
+        assert_eq!(c.get(), Some(Bytes::new(0x10)));
+
+        c -= Bytes::new(0x11);
+        c += Bytes::new(0x11);
+
+        assert_eq!(c.get(), None);
+    }
+
+    #[test]
+    fn conversions() {
+        let c: Checked<Bytes> = Bytes::new(3).into();
+
+        assert_eq!(c, Checked::new(Bytes::new(3)));
+        assert_eq!(Option::<Bytes>::from(c), Some(Bytes::new(3)));
+        assert_eq!(Checked::<Bytes>::from(None).get(), None);
+        assert_eq!(Checked::from(Some(Bytes::new(3))), c);
+    }
+
+    #[test]
+    fn comparison() {
+        let invalid = Checked::new(Bytes::zero()) - Bytes::new(1);
+
+        assert!(Checked::new(Bytes::new(1)) < Checked::new(Bytes::new(2)));
+        assert!(invalid < Checked::new(Bytes::zero()));
+        assert_eq!(invalid, Checked::from(None));
+    }
+
+    #[test]
+    fn debug() {
+        let c = Checked::new(NumOfPages::<Size4KiB>::new(3));
+
+        assert_eq!(format!("{c:?}"), "Checked(Some(NumOfPages::<4KiB>(3)))");
+        assert_eq!(format!("{:?}", c - NumOfPages::new(4)), "Checked(None)");
+    }
+}
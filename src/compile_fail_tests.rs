@@ -0,0 +1,84 @@
+//! The compile-fail tests of the features which remove items.
+//!
+//! Only a doc test can check that some code does not compile, so these are kept here instead of
+//! the API docs. This module is compiled only for the doc tests.
+
+/// With the `strict-operands` feature, a `usize` must be wrapped with [`Bytes::new`] or
+/// [`NumOfPages::new`] to be added to or subtracted from them.
+///
+/// ```rust
+/// # #[cfg(not(feature = "no-panic"))]
+/// # {
+/// use os_units::Bytes;
+///
+/// assert_eq!(Bytes::new(3) + Bytes::new(4), Bytes::new(7));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// use os_units::Bytes;
+///
+/// let _ = Bytes::new(3) + 4;
+/// ```
+///
+/// ```compile_fail
+/// use os_units::Bytes;
+///
+/// let mut b = Bytes::new(3);
+/// b -= 1;
+/// ```
+///
+/// ```compile_fail
+/// use os_units::NumOfPages;
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// let _ = NumOfPages::<Size4KiB>::new(3) - 1;
+/// ```
+///
+/// ```compile_fail
+/// use os_units::NumOfPages;
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// let mut n = NumOfPages::<Size4KiB>::new(3);
+/// n += 1;
+/// ```
+///
+/// [`Bytes::new`]: crate::Bytes::new
+/// [`NumOfPages::new`]: crate::NumOfPages::new
+#[cfg(feature = "strict-operands")]
+pub mod strict_operands {}
+
+/// With the `no-panic` feature, the operators which may panic do not compile, and the `checked_*`
+/// methods like [`Bytes::checked_sub`] replace them.
+///
+/// ```rust
+/// use os_units::Bytes;
+///
+/// assert_eq!(Bytes::new(4).checked_sub(Bytes::new(3)), Some(Bytes::new(1)));
+/// ```
+///
+/// ```compile_fail
+/// use os_units::Bytes;
+///
+/// let _ = Bytes::new(4) - Bytes::new(3);
+/// ```
+///
+/// The same holds for the operators of `NumOfPages` and for `NumOfPages::as_bytes`:
+///
+/// ```compile_fail
+/// use os_units::NumOfPages;
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// let _ = NumOfPages::<Size4KiB>::new(4) - NumOfPages::new(3);
+/// ```
+///
+/// ```compile_fail
+/// use os_units::NumOfPages;
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// let _ = NumOfPages::<Size4KiB>::new(4).as_bytes();
+/// ```
+///
+/// [`Bytes::checked_sub`]: crate::Bytes::checked_sub
+#[cfg(feature = "no-panic")]
+pub mod no_panic {}
@@ -0,0 +1,38 @@
+//! Tests of the `nightly-const-ops` feature, which use the operators in `const` items.
+
+use crate::{Bytes, NumOfPages};
+use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+const A: Bytes = Bytes::new(0x3000);
+const B: Bytes = Bytes::new(0x1000);
+
+#[test]
+fn bytes() {
+    const TOTAL: Bytes = A + B;
+    const DIFF: Bytes = A - B;
+    const SCALED: Bytes = A * 4 / 3;
+    const WITH_USIZE: Bytes = A + 0x10 - 0x8;
+
+    assert_eq!(TOTAL, Bytes::new(0x4000));
+    assert_eq!(DIFF, Bytes::new(0x2000));
+    assert_eq!(SCALED, Bytes::new(0x4000));
+    assert_eq!(WITH_USIZE, Bytes::new(0x3008));
+}
+
+#[test]
+fn num_of_pages() {
+    const P: NumOfPages<Size4KiB> = NumOfPages::new(6);
+    const TOTAL: NumOfPages<Size4KiB> = P + NumOfPages::new(2) - 1;
+    const SCALED: NumOfPages<Size2MiB> = NumOfPages::new(3) * 4 / 2;
+
+    assert_eq!(TOTAL, NumOfPages::new(7));
+    assert_eq!(SCALED, NumOfPages::new(6));
+}
+
+#[test]
+fn agree_with_const_methods() {
+    const OPERATOR: Bytes = A * 2 - B;
+    const METHOD: Bytes = A.const_mul(2).const_sub(B);
+
+    assert_eq!(OPERATOR, METHOD);
+}
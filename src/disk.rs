@@ -0,0 +1,148 @@
+use crate::{Bytes, NonZeroBytes};
+use core::convert::TryFrom;
+
+impl Bytes {
+    /// Returns the size of a disk with the CHS geometry of `cylinders`, `heads`, and
+    /// `sectors_per_track`, and sectors of `sector_size`.
+    ///
+    /// This method returns [`None`] if the size does not fit in `usize`.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(
+    ///     Bytes::from_chs(1024, 16, 63, Bytes::new(512)),
+    ///     Some(Bytes::new(0x1f80_0000))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_chs(
+        cylinders: u32,
+        heads: u32,
+        sectors_per_track: u32,
+        sector_size: Self,
+    ) -> Option<Self> {
+        let sectors = u64::from(cylinders)
+            .checked_mul(u64::from(heads))?
+            .checked_mul(u64::from(sectors_per_track))?;
+
+        Self::from_sectors(sectors, sector_size)
+    }
+
+    /// Returns the size of the sectors `start_lba..end_lba_exclusive` of `sector_size` each.
+    ///
+    /// This method returns [`None`] if `end_lba_exclusive < start_lba` or if the size does not
+    /// fit in `usize`.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(
+    ///     Bytes::from_lba_range(2048, 4096, Bytes::new(512)),
+    ///     Some(Bytes::new(0x10_0000))
+    /// );
+    /// assert_eq!(Bytes::from_lba_range(4096, 2048, Bytes::new(512)), None);
+    /// ```
+    #[must_use]
+    pub fn from_lba_range(
+        start_lba: u64,
+        end_lba_exclusive: u64,
+        sector_size: Self,
+    ) -> Option<Self> {
+        Self::from_sectors(end_lba_exclusive.checked_sub(start_lba)?, sector_size)
+    }
+
+    fn from_sectors(sectors: u64, sector_size: Self) -> Option<Self> {
+        usize::try_from(sectors)
+            .ok()?
+            .checked_mul(sector_size.as_usize())
+            .map(Self::new)
+    }
+
+    /// Returns the number of the whole sectors of `sector_size` in `self`, and the bytes left
+    /// over.
+    ///
+    /// ```rust
+    /// use os_units::{Bytes, NonZeroBytes};
+    ///
+    /// let sector = NonZeroBytes::new(Bytes::new(512)).unwrap();
+    ///
+    /// assert_eq!(Bytes::new(0x1234).sectors(sector), (9, Bytes::new(0x34)));
+    /// ```
+    #[must_use]
+    pub fn sectors(self, sector_size: NonZeroBytes) -> (u64, Self) {
+        let bytes = self.as_usize();
+        let sector_size = sector_size.get().as_usize();
+
+        ((bytes / sector_size) as u64, Self::new(bytes % sector_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bytes, NonZeroBytes};
+
+    #[test]
+    fn from_chs() {
+        assert_eq!(
+            Bytes::from_chs(1024, 16, 63, Bytes::new(512)),
+            Some(Bytes::new(0x1f80_0000))
+        );
+        assert_eq!(
+            Bytes::from_chs(2, 2, 2, Bytes::new(4096)),
+            Some(Bytes::new(0x8000))
+        );
+        assert_eq!(
+            Bytes::from_chs(0, 255, 63, Bytes::new(512)),
+            Some(Bytes::zero())
+        );
+    }
+
+    #[test]
+    fn from_chs_overflow() {
+        assert_eq!(
+            Bytes::from_chs(u32::MAX, u32::MAX, u32::MAX, Bytes::new(512)),
+            None
+        );
+        assert_eq!(
+            Bytes::from_chs(u32::MAX, u32::MAX, 1, Bytes::new(4096)),
+            None
+        );
+    }
+
+    #[test]
+    fn from_lba_range() {
+        assert_eq!(
+            Bytes::from_lba_range(34, 2082, Bytes::new(512)),
+            Some(Bytes::new(0x10_0000))
+        );
+        assert_eq!(
+            Bytes::from_lba_range(6, 262, Bytes::new(4096)),
+            Some(Bytes::new(0x10_0000))
+        );
+        assert_eq!(
+            Bytes::from_lba_range(10, 10, Bytes::new(512)),
+            Some(Bytes::zero())
+        );
+    }
+
+    #[test]
+    fn from_lba_range_reversed() {
+        assert_eq!(Bytes::from_lba_range(11, 10, Bytes::new(512)), None);
+    }
+
+    #[test]
+    fn from_lba_range_overflow() {
+        assert_eq!(Bytes::from_lba_range(0, u64::MAX, Bytes::new(512)), None);
+    }
+
+    #[test]
+    fn sectors() {
+        let s512 = NonZeroBytes::new(Bytes::new(512)).unwrap();
+        let s4096 = NonZeroBytes::new(Bytes::new(4096)).unwrap();
+
+        assert_eq!(Bytes::new(0x10_0000).sectors(s512), (2048, Bytes::zero()));
+        assert_eq!(Bytes::new(0x10_0001).sectors(s4096), (256, Bytes::new(1)));
+        assert_eq!(Bytes::new(511).sectors(s512), (0, Bytes::new(511)));
+    }
+}
@@ -0,0 +1,159 @@
+//! Helpers for loading the segments of ELF files.
+
+use crate::{Alignment, Bytes, NumOfPages};
+use core::convert::TryFrom;
+use x86_64::structures::paging::Size4KiB;
+
+/// The memory to map for a loadable segment, returned by [`segment_extent`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SegmentExtent {
+    /// The start of the mapped region, i.e. `p_vaddr` rounded down to the alignment.
+    pub start: Bytes,
+    /// The offset of `p_vaddr` from [`start`](Self::start). The file contents are copied here.
+    pub offset: Bytes,
+    /// The number of the pages to map.
+    pub pages: NumOfPages<Size4KiB>,
+    /// The bytes to fill with zeros after the file contents, i.e. `p_memsz - p_filesz`.
+    pub zero_fill: Bytes,
+}
+
+/// Returns the memory to map for a loadable segment with `p_vaddr`, `p_filesz`, `p_memsz`, and
+/// `p_align` of `vaddr`, `filesz`, `memsz`, and `align`.
+///
+/// The mapped region is `vaddr..vaddr + memsz` with the start rounded down and the end rounded up
+/// to `align`. `0` and `1` mean no alignment as in the ELF specification, and an alignment smaller
+/// than 4 KiB is raised to 4 KiB as the region is mapped by pages.
+///
+/// This function returns [`None`] if `memsz < filesz`, if `align` is not a power of two, or if
+/// the end of the region does not fit in `usize`.
+///
+/// ```rust
+/// use os_units::elf;
+/// use os_units::{Bytes, NumOfPages};
+///
+/// let e = elf::segment_extent(
+///     Bytes::new(0x40_1234),
+///     Bytes::new(0x100),
+///     Bytes::new(0x2000),
+///     Bytes::new(0x1000),
+/// )
+/// .unwrap();
+///
+/// assert_eq!(e.start, Bytes::new(0x40_1000));
+/// assert_eq!(e.offset, Bytes::new(0x234));
+/// assert_eq!(e.pages, NumOfPages::new(3));
+/// assert_eq!(e.zero_fill, Bytes::new(0x1f00));
+/// ```
+#[must_use]
+pub fn segment_extent(
+    vaddr: Bytes,
+    filesz: Bytes,
+    memsz: Bytes,
+    align: Bytes,
+) -> Option<SegmentExtent> {
+    let zero_fill = memsz.as_usize().checked_sub(filesz.as_usize())?;
+
+    let align = match align.as_usize() {
+        0 | 1 => Alignment::ONE,
+        _ => Alignment::try_from(align).ok()?,
+    }
+    .max(Alignment::of_page::<Size4KiB>());
+
+    let start = align.align_down(vaddr);
+    let end = align.align_up(Bytes::new(vaddr.as_usize().checked_add(memsz.as_usize())?))?;
+
+    Some(SegmentExtent {
+        start,
+        offset: Bytes::new(vaddr.as_usize() - start.as_usize()),
+        pages: Bytes::new(end.as_usize() - start.as_usize()).as_num_of_pages(),
+        zero_fill: Bytes::new(zero_fill),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{segment_extent, SegmentExtent};
+    use crate::{Bytes, NumOfPages};
+
+    fn extent(vaddr: usize, filesz: usize, memsz: usize, align: usize) -> Option<SegmentExtent> {
+        segment_extent(
+            Bytes::new(vaddr),
+            Bytes::new(filesz),
+            Bytes::new(memsz),
+            Bytes::new(align),
+        )
+    }
+
+    #[test]
+    fn aligned() {
+        let e = extent(0x40_0000, 0x3000, 0x3000, 0x1000).unwrap();
+
+        assert_eq!(e.start, Bytes::new(0x40_0000));
+        assert_eq!(e.offset, Bytes::zero());
+        assert_eq!(e.pages, NumOfPages::new(3));
+        assert_eq!(e.zero_fill, Bytes::zero());
+    }
+
+    #[test]
+    fn misaligned_vaddr() {
+        let e = extent(0x40_0ff0, 0x20, 0x20, 0x1000).unwrap();
+
+        assert_eq!(e.start, Bytes::new(0x40_0000));
+        assert_eq!(e.offset, Bytes::new(0xff0));
+        assert_eq!(e.pages, NumOfPages::new(2));
+    }
+
+    #[test]
+    fn bss() {
+        let e = extent(0x60_1100, 0x800, 0x5000, 0x1000).unwrap();
+
+        assert_eq!(e.start, Bytes::new(0x60_1000));
+        assert_eq!(e.offset, Bytes::new(0x100));
+        assert_eq!(e.pages, NumOfPages::new(6));
+        assert_eq!(e.zero_fill, Bytes::new(0x4800));
+    }
+
+    #[test]
+    fn large_align() {
+        let e = extent(0x20_1000, 0x1000, 0x1000, 0x20_0000).unwrap();
+
+        assert_eq!(e.start, Bytes::new(0x20_0000));
+        assert_eq!(e.offset, Bytes::new(0x1000));
+        assert_eq!(e.pages, NumOfPages::new(0x200));
+    }
+
+    #[test]
+    fn small_align_uses_pages() {
+        let no_align = extent(0x1010, 0x10, 0x10, 0).unwrap();
+
+        assert_eq!(no_align.start, Bytes::new(0x1000));
+        assert_eq!(no_align.pages, NumOfPages::new(1));
+        assert_eq!(extent(0x1010, 0x10, 0x10, 1), Some(no_align));
+        assert_eq!(extent(0x1010, 0x10, 0x10, 0x10), Some(no_align));
+    }
+
+    #[test]
+    fn empty() {
+        let e = extent(0x1000, 0, 0, 0x1000).unwrap();
+
+        assert_eq!(e.pages, NumOfPages::zero());
+    }
+
+    #[test]
+    fn filesz_larger_than_memsz() {
+        assert_eq!(extent(0x1000, 0x2000, 0x1000, 0x1000), None);
+    }
+
+    #[test]
+    fn align_not_power_of_two() {
+        assert_eq!(extent(0x1000, 0x10, 0x10, 0x3000), None);
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(extent(usize::MAX - 0xfff, 0, 0x1000, 0x1000), None);
+        assert_eq!(extent(usize::MAX - 0xfff, 0, 0x10, 0x1000), None);
+        assert!(extent(usize::MAX - 0x1fff, 0, 0x1000, 0x1000).is_some());
+    }
+}
@@ -0,0 +1,407 @@
+use crate::Bytes;
+use core::fmt;
+use core::num::TryFromIntError;
+use x86_64::structures::paging::mapper::MapToError;
+use x86_64::structures::paging::PageSize;
+
+/// An error which can be returned when parsing a string into [`Bytes`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseBytesError {
+    /// The string contains a character which is not a digit where a digit is expected.
+    InvalidDigit,
+    /// The unit suffix is not recognized.
+    UnknownUnit,
+    /// The value is too large to be represented.
+    Overflow,
+    /// The string is empty.
+    Empty,
+    /// The value is not a multiple of the required unit.
+    NotAMultiple {
+        /// The remainder of the division by the unit.
+        remainder: Bytes,
+    },
+}
+impl fmt::Display for ParseBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDigit => f.write_str("invalid digit found in string"),
+            Self::UnknownUnit => f.write_str("unknown unit"),
+            Self::Overflow => f.write_str("number too large to be represented"),
+            Self::Empty => f.write_str("cannot parse bytes from empty string"),
+            Self::NotAMultiple { remainder } => {
+                write!(f, "not a multiple of the unit (remainder: {remainder})")
+            }
+        }
+    }
+}
+impl core::error::Error for ParseBytesError {}
+/// Lets a function which both parses and converts return one error type with `?`.
+impl From<ConversionError> for ParseBytesError {
+    fn from(e: ConversionError) -> Self {
+        match e {
+            ConversionError::Overflow => Self::Overflow,
+            ConversionError::NotAMultiple { remainder } => Self::NotAMultiple { remainder },
+        }
+    }
+}
+
+/// An error which can be returned when converting a value into one of the unit types.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ConversionError {
+    /// The value does not fit in the target type.
+    Overflow,
+    /// The value is not a multiple of the required unit.
+    NotAMultiple {
+        /// The remainder of the division by the unit.
+        remainder: Bytes,
+    },
+}
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow => f.write_str("value too large for the target type"),
+            Self::NotAMultiple { remainder } => {
+                write!(f, "not a multiple of the unit (remainder: {remainder})")
+            }
+        }
+    }
+}
+impl core::error::Error for ConversionError {}
+impl From<TryFromIntError> for ConversionError {
+    fn from(_: TryFromIntError) -> Self {
+        Self::Overflow
+    }
+}
+
+/// An error which can be returned when converting a value into a region like
+/// [`PhysRegion`](crate::PhysRegion).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RegionError {
+    /// The size or an address of the region cannot be converted.
+    Conversion(ConversionError),
+    /// An address of the region is not a valid address.
+    InvalidAddress,
+    /// The end of the region is below its start.
+    EndBelowStart,
+}
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Conversion(e) => e.fmt(f),
+            Self::InvalidAddress => f.write_str("the region has an invalid address"),
+            Self::EndBelowStart => f.write_str("the end of the region is below its start"),
+        }
+    }
+}
+impl core::error::Error for RegionError {}
+impl From<ConversionError> for RegionError {
+    fn from(e: ConversionError) -> Self {
+        Self::Conversion(e)
+    }
+}
+impl From<TryFromIntError> for RegionError {
+    fn from(e: TryFromIntError) -> Self {
+        Self::Conversion(e.into())
+    }
+}
+
+/// An error which can be returned by [`Bytes::distribute_weighted`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DistributeError {
+    /// The sum of the weights is zero.
+    ZeroTotalWeight,
+    /// The lengths of the weights and the output slice differ.
+    LengthMismatch,
+}
+impl fmt::Display for DistributeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroTotalWeight => f.write_str("the total weight is zero"),
+            Self::LengthMismatch => {
+                f.write_str("the weights and the output have different lengths")
+            }
+        }
+    }
+}
+impl core::error::Error for DistributeError {}
+
+/// An error which can be returned by [`MemoryStats::allocate`] and [`MemoryStats::release`].
+///
+/// [`MemoryStats::allocate`]: crate::MemoryStats::allocate
+/// [`MemoryStats::release`]: crate::MemoryStats::release
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MemoryStatsError {
+    /// The free memory is smaller than the requested size.
+    InsufficientMemory {
+        /// The free memory at the time of the request.
+        free: Bytes,
+    },
+    /// The used memory is smaller than the released size.
+    ReleasedTooMuch {
+        /// The used memory at the time of the request.
+        used: Bytes,
+    },
+}
+impl fmt::Display for MemoryStatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InsufficientMemory { free } => {
+                write!(f, "not enough free memory (free: {free})")
+            }
+            Self::ReleasedTooMuch { used } => {
+                write!(f, "released more memory than used (used: {used})")
+            }
+        }
+    }
+}
+impl core::error::Error for MemoryStatsError {}
+
+/// An error which can be returned by [`MapperExt::map_range`].
+///
+/// [`MapperExt::map_range`]: crate::MapperExt::map_range
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MapRangeError<S: PageSize> {
+    /// The range of the pages goes past the end of the address space.
+    RangeOverflow,
+    /// The frames ran out before all the pages were mapped.
+    NotEnoughFrames,
+    /// Mapping a page failed.
+    Map(MapToError<S>),
+}
+impl<S: PageSize> fmt::Display for MapRangeError<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RangeOverflow => f.write_str("the pages go past the end of the address space"),
+            Self::NotEnoughFrames => f.write_str("not enough frames to map the pages"),
+            Self::Map(MapToError::FrameAllocationFailed) => {
+                f.write_str("failed to allocate a frame for a page table")
+            }
+            Self::Map(MapToError::ParentEntryHugePage) => {
+                f.write_str("a page is a part of an already mapped huge page")
+            }
+            Self::Map(MapToError::PageAlreadyMapped(_)) => f.write_str("a page is already mapped"),
+        }
+    }
+}
+impl<S: PageSize + fmt::Debug> core::error::Error for MapRangeError<S> {}
+impl<S: PageSize> From<MapToError<S>> for MapRangeError<S> {
+    fn from(e: MapToError<S>) -> Self {
+        Self::Map(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConversionError;
+    use super::DistributeError;
+    use super::MapRangeError;
+    use super::MemoryStatsError;
+    use super::ParseBytesError;
+    use super::RegionError;
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use core::convert::TryFrom;
+    use x86_64::structures::paging::mapper::MapToError;
+    use x86_64::structures::paging::Size4KiB;
+
+    #[test]
+    fn from_try_from_int_error() {
+        let e = u8::try_from(256_u32).unwrap_err();
+
+        assert_eq!(ConversionError::from(e), ConversionError::Overflow);
+    }
+
+    #[test]
+    fn display_invalid_digit() {
+        let f = format!("{}", ParseBytesError::InvalidDigit);
+
+        assert_eq!(f, format!("invalid digit found in string"));
+    }
+
+    #[test]
+    fn display_unknown_unit() {
+        let f = format!("{}", ParseBytesError::UnknownUnit);
+
+        assert_eq!(f, format!("unknown unit"));
+    }
+
+    #[test]
+    fn display_overflow() {
+        let f = format!("{}", ParseBytesError::Overflow);
+
+        assert_eq!(f, format!("number too large to be represented"));
+    }
+
+    #[test]
+    fn display_empty() {
+        let f = format!("{}", ParseBytesError::Empty);
+
+        assert_eq!(f, format!("cannot parse bytes from empty string"));
+    }
+
+    #[test]
+    fn parse_error_from_conversion_error() {
+        let remainder = Bytes::new(3);
+
+        assert_eq!(
+            ParseBytesError::from(ConversionError::Overflow),
+            ParseBytesError::Overflow
+        );
+        assert_eq!(
+            ParseBytesError::from(ConversionError::NotAMultiple { remainder }),
+            ParseBytesError::NotAMultiple { remainder }
+        );
+    }
+
+    #[test]
+    fn question_mark_converts_into_parse_error() {
+        fn parse_pages(s: &str) -> Result<NumOfPages<Size4KiB>, ParseBytesError> {
+            Ok(s.parse::<Bytes>()?.as_num_of_pages_exact()?)
+        }
+
+        assert_eq!(parse_pages("8 KiB"), Ok(NumOfPages::new(2)));
+        assert_eq!(
+            parse_pages("4097"),
+            Err(ParseBytesError::NotAMultiple {
+                remainder: Bytes::new(1)
+            })
+        );
+        assert_eq!(parse_pages("4 XiB"), Err(ParseBytesError::UnknownUnit));
+    }
+
+    #[test]
+    fn region_error_from_conversion_error() {
+        assert_eq!(
+            RegionError::from(ConversionError::Overflow),
+            RegionError::Conversion(ConversionError::Overflow)
+        );
+        assert_eq!(
+            RegionError::from(u32::try_from(u64::MAX).unwrap_err()),
+            RegionError::Conversion(ConversionError::Overflow)
+        );
+    }
+
+    #[test]
+    fn display_parse_not_a_multiple() {
+        let e = ParseBytesError::NotAMultiple {
+            remainder: Bytes::new(3),
+        };
+
+        assert_eq!(
+            format!("{e}"),
+            "not a multiple of the unit (remainder: 3 bytes)"
+        );
+    }
+
+    #[test]
+    fn display_not_a_multiple() {
+        let e = ConversionError::NotAMultiple {
+            remainder: Bytes::new(3),
+        };
+        let f = format!("{e}");
+
+        assert_eq!(
+            f,
+            format!("not a multiple of the unit (remainder: 3 bytes)")
+        );
+    }
+
+    #[test]
+    fn display_conversion_overflow() {
+        let f = format!("{}", ConversionError::Overflow);
+
+        assert_eq!(f, "value too large for the target type");
+    }
+
+    #[test]
+    fn display_invalid_address() {
+        let f = format!("{}", RegionError::InvalidAddress);
+
+        assert_eq!(f, "the region has an invalid address");
+    }
+
+    #[test]
+    fn display_end_below_start() {
+        let f = format!("{}", RegionError::EndBelowStart);
+
+        assert_eq!(f, "the end of the region is below its start");
+    }
+
+    #[test]
+    fn display_region_conversion() {
+        let f = format!("{}", RegionError::Conversion(ConversionError::Overflow));
+
+        assert_eq!(f, "value too large for the target type");
+    }
+
+    #[test]
+    fn display_zero_total_weight() {
+        let f = format!("{}", DistributeError::ZeroTotalWeight);
+
+        assert_eq!(f, format!("the total weight is zero"));
+    }
+
+    #[test]
+    fn display_length_mismatch() {
+        let f = format!("{}", DistributeError::LengthMismatch);
+
+        assert_eq!(
+            f,
+            format!("the weights and the output have different lengths")
+        );
+    }
+
+    #[test]
+    fn display_range_overflow() {
+        let f = format!("{}", MapRangeError::<Size4KiB>::RangeOverflow);
+
+        assert_eq!(f, "the pages go past the end of the address space");
+    }
+
+    #[test]
+    fn display_not_enough_frames() {
+        let f = format!("{}", MapRangeError::<Size4KiB>::NotEnoughFrames);
+
+        assert_eq!(f, "not enough frames to map the pages");
+    }
+
+    #[test]
+    fn display_map() {
+        let f = format!(
+            "{}",
+            MapRangeError::<Size4KiB>::from(MapToError::FrameAllocationFailed)
+        );
+
+        assert_eq!(f, "failed to allocate a frame for a page table");
+    }
+
+    #[test]
+    fn display_insufficient_memory() {
+        let f = format!(
+            "{}",
+            MemoryStatsError::InsufficientMemory {
+                free: Bytes::new(0x1000)
+            }
+        );
+
+        assert_eq!(f, "not enough free memory (free: 4096 bytes)");
+    }
+
+    #[test]
+    fn display_released_too_much() {
+        let f = format!(
+            "{}",
+            MemoryStatsError::ReleasedTooMuch {
+                used: Bytes::new(1)
+            }
+        );
+
+        assert_eq!(f, "released more memory than used (used: 1 byte)");
+    }
+}
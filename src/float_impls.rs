@@ -0,0 +1,182 @@
+use crate::Bytes;
+
+const KIB: f64 = 1024.0;
+const MIB: f64 = 1024.0 * 1024.0;
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+// The conversions are approximate by design.
+#[allow(clippy::cast_precision_loss)]
+impl Bytes {
+    /// Returns the value in KiB as `f64`.
+    ///
+    /// This method is available with the `float` feature. The result is approximate: values
+    /// above 2^53 bytes are rounded to the nearest representable `f64`.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::new(0x600).as_kib_f64(), 1.5);
+    /// ```
+    #[must_use]
+    pub fn as_kib_f64(self) -> f64 {
+        self.as_usize() as f64 / KIB
+    }
+
+    /// Returns the value in MiB as `f64`.
+    ///
+    /// This method is available with the `float` feature. The result is approximate like
+    /// [`Bytes::as_kib_f64`].
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::new(0x28_0000).as_mib_f64(), 2.5);
+    /// ```
+    #[must_use]
+    pub fn as_mib_f64(self) -> f64 {
+        self.as_usize() as f64 / MIB
+    }
+
+    /// Returns the value in GiB as `f64`.
+    ///
+    /// This method is available with the `float` feature. The result is approximate like
+    /// [`Bytes::as_kib_f64`].
+    #[must_use]
+    pub fn as_gib_f64(self) -> f64 {
+        self.as_usize() as f64 / GIB
+    }
+
+    /// Returns the value in KiB as `f32`.
+    ///
+    /// This method is available with the `float` feature. The result is approximate: values
+    /// above 2^24 bytes are rounded to the nearest representable `f32`.
+    #[must_use]
+    pub fn as_kib_f32(self) -> f32 {
+        self.as_usize() as f32 / 1024.0
+    }
+
+    /// Returns the value in MiB as `f32`.
+    ///
+    /// This method is available with the `float` feature. The result is approximate like
+    /// [`Bytes::as_kib_f32`].
+    #[must_use]
+    pub fn as_mib_f32(self) -> f32 {
+        self.as_usize() as f32 / (1024.0 * 1024.0)
+    }
+
+    /// Returns the value in GiB as `f32`.
+    ///
+    /// This method is available with the `float` feature. The result is approximate like
+    /// [`Bytes::as_kib_f32`].
+    #[must_use]
+    pub fn as_gib_f32(self) -> f32 {
+        self.as_usize() as f32 / (1024.0 * 1024.0 * 1024.0)
+    }
+
+    /// Converts `mib` MiB into bytes, rounding down to a whole byte.
+    ///
+    /// This method is available with the `float` feature.
+    ///
+    /// This method returns [`None`] if `mib` is NaN, infinite, negative, or too large for
+    /// `usize`.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::from_mib_f64(1.5), Some(Bytes::new(0x18_0000)));
+    /// assert_eq!(Bytes::from_mib_f64(-1.0), None);
+    /// assert_eq!(Bytes::from_mib_f64(f64::NAN), None);
+    /// ```
+    #[must_use]
+    pub fn from_mib_f64(mib: f64) -> Option<Self> {
+        let bytes = mib * MIB;
+
+        // `usize::MAX as f64 + 1.0` is exactly 2^usize::BITS on both 32-bit and 64-bit targets.
+        if bytes.is_finite() && bytes >= 0.0 && bytes < usize::MAX as f64 + 1.0 {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let bytes = bytes as usize;
+
+            Some(Self::new(bytes))
+        } else {
+            None
+        }
+    }
+}
+
+// The expected values are exactly representable, so they are compared strictly.
+#[cfg(test)]
+#[allow(clippy::cast_precision_loss, clippy::float_cmp)]
+mod tests {
+    use crate::Bytes;
+
+    #[test]
+    fn as_f64() {
+        let b = Bytes::new(0xc000_0000);
+
+        assert_eq!(b.as_kib_f64(), 3_145_728.0);
+        assert_eq!(b.as_mib_f64(), 3072.0);
+        assert_eq!(b.as_gib_f64(), 3.0);
+        assert_eq!(Bytes::new(0x100).as_kib_f64(), 0.25);
+    }
+
+    #[test]
+    fn as_f32() {
+        let b = Bytes::new(0x6000_0000);
+
+        assert_eq!(b.as_kib_f32(), 1_572_864.0);
+        assert_eq!(b.as_mib_f32(), 1536.0);
+        assert_eq!(b.as_gib_f32(), 1.5);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn f64_precision_loss() {
+        let b = Bytes::new((1 << 53) + 1);
+
+        assert_eq!(b.as_kib_f64() * 1024.0, (1_u64 << 53) as f64);
+        assert_eq!(Bytes::new(usize::MAX).as_gib_f64(), 17_179_869_184.0);
+    }
+
+    #[test]
+    fn f32_precision_loss() {
+        let b = Bytes::new((1 << 24) + 1);
+
+        assert_eq!(b.as_kib_f32(), 16384.0);
+        assert_eq!(Bytes::new((1 << 24) + 2).as_kib_f32(), 16384.002);
+    }
+
+    #[test]
+    fn from_mib_f64() {
+        assert_eq!(Bytes::from_mib_f64(0.0), Some(Bytes::zero()));
+        assert_eq!(Bytes::from_mib_f64(-0.0), Some(Bytes::zero()));
+        assert_eq!(Bytes::from_mib_f64(4.0), Some(Bytes::new(0x40_0000)));
+        assert_eq!(Bytes::from_mib_f64(1.0 / 1024.0), Some(Bytes::new(0x400)));
+    }
+
+    #[test]
+    fn from_mib_f64_rounds_down() {
+        assert_eq!(Bytes::from_mib_f64(1.5 / 1_048_576.0), Some(Bytes::new(1)));
+        assert_eq!(Bytes::from_mib_f64(1e-9), Some(Bytes::zero()));
+    }
+
+    #[test]
+    fn from_mib_f64_rejects_invalid() {
+        assert_eq!(Bytes::from_mib_f64(f64::NAN), None);
+        assert_eq!(Bytes::from_mib_f64(f64::INFINITY), None);
+        assert_eq!(Bytes::from_mib_f64(f64::NEG_INFINITY), None);
+        assert_eq!(Bytes::from_mib_f64(-1.0), None);
+        assert_eq!(Bytes::from_mib_f64(-1e-9), None);
+    }
+
+    #[test]
+    fn from_mib_f64_too_large() {
+        let max_mib = (usize::MAX >> 20) as f64;
+
+        assert_eq!(
+            Bytes::from_mib_f64(max_mib),
+            Some(Bytes::new((usize::MAX >> 20) << 20))
+        );
+        assert_eq!(Bytes::from_mib_f64(max_mib + 1.0), None);
+        assert_eq!(Bytes::from_mib_f64(f64::MAX), None);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::NumOfPages;
+use x86_64::structures::paging::frame::PhysFrameRange;
+use x86_64::structures::paging::{FrameAllocator, PageSize};
+
+/// Allocating physically contiguous frames with a [`FrameAllocator`].
+///
+/// The default implementation of [`allocate_contiguous`](Self::allocate_contiguous) only uses
+/// [`FrameAllocator::allocate_frame`], so it works with any allocator, but it may waste frames.
+/// Allocators which know their free frames should override it.
+///
+/// ```rust
+/// use os_units::{FrameAllocatorExt, NumOfPages};
+/// use x86_64::structures::paging::{FrameAllocator, PhysFrame, Size4KiB};
+/// use x86_64::PhysAddr;
+///
+/// struct Bump(u64);
+/// unsafe impl FrameAllocator<Size4KiB> for Bump {
+///     fn allocate_frame(&mut self) -> Option<PhysFrame> {
+///         let frame = PhysFrame::containing_address(PhysAddr::new(self.0));
+///         self.0 += 0x1000;
+///         Some(frame)
+///     }
+/// }
+/// unsafe impl FrameAllocatorExt<Size4KiB> for Bump {}
+///
+/// let frames = Bump(0x1000).allocate_contiguous(NumOfPages::new(3)).unwrap();
+///
+/// assert_eq!(frames.start.start_address(), PhysAddr::new(0x1000));
+/// assert_eq!(frames.end.start_address(), PhysAddr::new(0x4000));
+/// ```
+///
+/// # Safety
+///
+/// The implementer must ensure that the frames returned by
+/// [`allocate_contiguous`](Self::allocate_contiguous) are unused, as required by
+/// [`FrameAllocator`].
+pub unsafe trait FrameAllocatorExt<S: PageSize>: FrameAllocator<S> {
+    /// Allocates `count` physically contiguous frames.
+    ///
+    /// This method returns [`None`] if `count` is zero or the allocator runs out of frames before
+    /// `count` contiguous frames are found.
+    ///
+    /// The default implementation allocates frames one by one, and starts over from the latest
+    /// frame when it is not next to the previous one. The frames allocated before are neither used
+    /// nor returned to the allocator, because [`FrameAllocator`] cannot deallocate frames.
+    fn allocate_contiguous(&mut self, count: NumOfPages<S>) -> Option<PhysFrameRange<S>> {
+        if count.as_usize() == 0 {
+            return None;
+        }
+
+        let mut start = self.allocate_frame()?;
+        let mut len = 1;
+
+        while len < count.as_usize() {
+            let frame = self.allocate_frame()?;
+
+            let contiguous = crate::frame_range(start, NumOfPages::new(len))
+                .is_some_and(|range| range.end == frame);
+
+            if contiguous {
+                len += 1;
+            } else {
+                start = frame;
+                len = 1;
+            }
+        }
+
+        crate::frame_range(start, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrameAllocatorExt;
+    use crate::NumOfPages;
+    use x86_64::structures::paging::frame::PhysFrameRange;
+    use x86_64::structures::paging::{FrameAllocator, PhysFrame, Size4KiB};
+    use x86_64::PhysAddr;
+
+    fn frame(addr: u64) -> PhysFrame {
+        PhysFrame::containing_address(PhysAddr::new(addr))
+    }
+
+    /// Hands out the frames in `frames` in order.
+    struct Listed {
+        frames: Vec<u64>,
+        next: usize,
+    }
+    impl Listed {
+        fn new(frames: Vec<u64>) -> Self {
+            Self { frames, next: 0 }
+        }
+    }
+    unsafe impl FrameAllocator<Size4KiB> for Listed {
+        fn allocate_frame(&mut self) -> Option<PhysFrame> {
+            let f = self.frames.get(self.next).copied().map(frame);
+            self.next += 1;
+            f
+        }
+    }
+    unsafe impl FrameAllocatorExt<Size4KiB> for Listed {}
+
+    /// A bump allocator overriding `allocate_contiguous`.
+    struct Bump {
+        next: u64,
+        end: u64,
+    }
+    unsafe impl FrameAllocator<Size4KiB> for Bump {
+        fn allocate_frame(&mut self) -> Option<PhysFrame> {
+            self.allocate_contiguous(NumOfPages::new(1))
+                .map(|range| range.start)
+        }
+    }
+    unsafe impl FrameAllocatorExt<Size4KiB> for Bump {
+        fn allocate_contiguous(
+            &mut self,
+            count: NumOfPages<Size4KiB>,
+        ) -> Option<PhysFrameRange<Size4KiB>> {
+            let start = self.next;
+            let end = start.checked_add(count.checked_as_bytes()?.as_usize() as u64)?;
+
+            if count.as_usize() == 0 || end > self.end {
+                None
+            } else {
+                self.next = end;
+                Some(PhysFrame::range(frame(start), frame(end)))
+            }
+        }
+    }
+
+    #[test]
+    fn default_contiguous() {
+        let mut a = Listed::new(vec![0x1000, 0x2000, 0x3000]);
+
+        let r = a.allocate_contiguous(NumOfPages::new(3));
+
+        assert_eq!(r, Some(PhysFrame::range(frame(0x1000), frame(0x4000))));
+    }
+
+    #[test]
+    fn default_starts_over_after_gap() {
+        let mut a = Listed::new(vec![0x1000, 0x2000, 0x5000, 0x6000, 0x7000, 0x8000]);
+
+        let r = a.allocate_contiguous(NumOfPages::new(3));
+
+        assert_eq!(r, Some(PhysFrame::range(frame(0x5000), frame(0x8000))));
+        assert_eq!(a.allocate_frame(), Some(frame(0x8000)));
+    }
+
+    #[test]
+    fn default_descending_frames_are_not_contiguous() {
+        let mut a = Listed::new(vec![0x3000, 0x2000, 0x1000]);
+
+        assert_eq!(a.allocate_contiguous(NumOfPages::new(2)), None);
+    }
+
+    #[test]
+    fn default_out_of_frames() {
+        let mut a = Listed::new(vec![0x1000, 0x2000]);
+
+        assert_eq!(a.allocate_contiguous(NumOfPages::new(3)), None);
+    }
+
+    #[test]
+    fn default_zero() {
+        let mut a = Listed::new(vec![0x1000]);
+
+        assert_eq!(a.allocate_contiguous(NumOfPages::new(0)), None);
+        assert_eq!(a.allocate_frame(), Some(frame(0x1000)));
+    }
+
+    #[test]
+    fn overridden() {
+        let mut a = Bump {
+            next: 0x1000,
+            end: 0x5000,
+        };
+
+        let r = a.allocate_contiguous(NumOfPages::new(3));
+
+        assert_eq!(r, Some(PhysFrame::range(frame(0x1000), frame(0x4000))));
+        assert_eq!(a.allocate_frame(), Some(frame(0x4000)));
+        assert_eq!(a.allocate_contiguous(NumOfPages::new(1)), None);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Bytes, NumOfPages};
+use x86_64::structures::paging::Size4KiB;
+
+impl Bytes {
+    /// Returns the size of a framebuffer of `width` x `height` pixels of `bytes_per_pixel` each,
+    /// with rows `stride_bytes` apart.
+    ///
+    /// The size is `stride * height`. If `stride_bytes` is [`None`], the rows are packed, i.e. the
+    /// stride is `width * bytes_per_pixel`.
+    ///
+    /// This method returns [`None`] if the stride is smaller than `width * bytes_per_pixel`, or
+    /// if the size does not fit in `usize`.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::framebuffer(800, 600, 4, None), Some(Bytes::new(0x1d_4c00)));
+    /// assert_eq!(
+    ///     Bytes::framebuffer(800, 600, 4, Some(Bytes::new(0x1000))),
+    ///     Some(Bytes::new(0x25_8000))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn framebuffer(
+        width: usize,
+        height: usize,
+        bytes_per_pixel: usize,
+        stride_bytes: Option<Self>,
+    ) -> Option<Self> {
+        let packed = width.checked_mul(bytes_per_pixel)?;
+        let stride = stride_bytes.map_or(packed, Self::as_usize);
+
+        if stride < packed {
+            return None;
+        }
+
+        stride.checked_mul(height).map(Self::new)
+    }
+
+    /// Like [`Bytes::framebuffer`], but returns the number of the 4 KiB pages to allocate for the
+    /// framebuffer.
+    ///
+    /// ```rust
+    /// use os_units::{Bytes, NumOfPages};
+    ///
+    /// assert_eq!(
+    ///     Bytes::framebuffer_pages(800, 600, 4, None),
+    ///     Some(NumOfPages::new(469))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn framebuffer_pages(
+        width: usize,
+        height: usize,
+        bytes_per_pixel: usize,
+        stride_bytes: Option<Self>,
+    ) -> Option<NumOfPages<Size4KiB>> {
+        Self::framebuffer(width, height, bytes_per_pixel, stride_bytes).map(Self::as_num_of_pages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bytes, NumOfPages};
+
+    #[test]
+    fn framebuffer_packed() {
+        assert_eq!(
+            Bytes::framebuffer(1024, 768, 4, None),
+            Some(Bytes::new(0x30_0000))
+        );
+        assert_eq!(
+            Bytes::framebuffer(1024, 768, 4, Some(Bytes::new(4096))),
+            Some(Bytes::new(0x30_0000))
+        );
+    }
+
+    #[test]
+    fn framebuffer_with_stride() {
+        // 1366 * 4 = 5464 bytes per row, padded to 5504.
+        assert_eq!(
+            Bytes::framebuffer(1366, 768, 4, Some(Bytes::new(5504))),
+            Some(Bytes::new(5504 * 768))
+        );
+        assert_eq!(
+            Bytes::framebuffer_pages(1366, 768, 4, Some(Bytes::new(5504))),
+            Some(NumOfPages::new(1032))
+        );
+        assert_eq!(
+            Bytes::framebuffer_pages(1366, 768, 4, None),
+            Some(NumOfPages::new(1025))
+        );
+    }
+
+    #[test]
+    fn framebuffer_stride_too_small() {
+        assert_eq!(
+            Bytes::framebuffer(1024, 768, 4, Some(Bytes::new(4095))),
+            None
+        );
+    }
+
+    #[test]
+    fn framebuffer_overflow() {
+        assert_eq!(Bytes::framebuffer(usize::MAX, 1, 2, None), None);
+        assert_eq!(Bytes::framebuffer(usize::MAX / 4, 2, 4, None), None);
+        assert_eq!(
+            Bytes::framebuffer(1, usize::MAX, 1, Some(Bytes::new(2))),
+            None
+        );
+        assert_eq!(Bytes::framebuffer_pages(usize::MAX, 1, 2, None), None);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::Bytes;
+use crate::NumOfPages;
+use hash32::Hash;
+use hash32::Hasher;
+use x86_64::structures::paging::PageSize;
+
+/// Hashes the value in the same way as the `core::hash::Hash` implementation: only the `usize`
+/// value is fed to the hasher.
+impl Hash for Bytes {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_usize().hash(state);
+    }
+}
+
+/// Hashes the value in the same way as the `core::hash::Hash` implementation: only the number of
+/// pages is fed to the hasher.
+impl<T: PageSize> Hash for NumOfPages<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_usize().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use hash32::{BuildHasher, BuildHasherDefault, FnvHasher, Hash, Hasher};
+    use heapless::FnvIndexMap;
+    use x86_64::structures::paging::Size4KiB;
+
+    fn hash_of<T: Hash>(v: &T) -> u32 {
+        let mut h = BuildHasherDefault::<FnvHasher>::default().build_hasher();
+        v.hash(&mut h);
+        h.finish()
+    }
+
+    #[test]
+    fn bytes_hash_matches_usize() {
+        assert_eq!(hash_of(&Bytes::new(0x1000)), hash_of(&0x1000_usize));
+    }
+
+    #[test]
+    fn num_of_pages_hash_matches_usize() {
+        assert_eq!(hash_of(&NumOfPages::<Size4KiB>::new(3)), hash_of(&3_usize));
+    }
+
+    #[test]
+    fn bytes_as_fnv_index_map_key() {
+        let mut map = FnvIndexMap::<Bytes, u32, 8>::new();
+
+        map.insert(Bytes::new(0x1000), 1).unwrap();
+        map.insert(Bytes::new(0x20_0000), 2).unwrap();
+        map.insert(Bytes::new(0x1000), 3).unwrap();
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&Bytes::new(0x1000)), Some(&3));
+        assert_eq!(map.get(&Bytes::new(0x20_0000)), Some(&2));
+        assert_eq!(map.get(&Bytes::new(0x3000)), None);
+    }
+
+    #[test]
+    fn num_of_pages_as_fnv_index_map_key() {
+        let mut map = FnvIndexMap::<NumOfPages<Size4KiB>, u32, 8>::new();
+
+        map.insert(NumOfPages::new(1), 10).unwrap();
+        map.insert(NumOfPages::new(512), 20).unwrap();
+
+        assert_eq!(map.get(&NumOfPages::new(512)), Some(&20));
+        assert_eq!(map.remove(&NumOfPages::new(1)), Some(10));
+        assert_eq!(map.get(&NumOfPages::new(1)), None);
+    }
+}
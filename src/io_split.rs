@@ -0,0 +1,169 @@
+use crate::paging::PageSizeExt;
+use crate::{Bytes, NumOfPages};
+use core::fmt;
+use x86_64::structures::paging::PageSize;
+
+/// A transfer split at the page boundaries, returned by [`Bytes::io_split`].
+///
+/// `head + middle.as_bytes() + tail` is always the length of the transfer.
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IoSplit<T: PageSize> {
+    /// The bytes from the start to the next page boundary, or all the bytes if the transfer
+    /// ends before it. Zero if the start is page-aligned.
+    pub head: Bytes,
+    /// The whole pages after [`head`](Self::head).
+    pub middle: NumOfPages<T>,
+    /// The bytes from the last page boundary to the end, which are fewer than `T::SIZE`.
+    pub tail: Bytes,
+}
+impl<T: PageSize> fmt::Debug for IoSplit<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IoSplit")
+            .field("head", &self.head)
+            .field("middle", &self.middle)
+            .field("tail", &self.tail)
+            .finish()
+    }
+}
+
+impl Bytes {
+    /// Splits the transfer of `self` bytes starting at `start_offset` into an unaligned head
+    /// up to the next page boundary, a run of whole pages, and an unaligned tail.
+    ///
+    /// ```rust
+    /// use os_units::{Bytes, NumOfPages};
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// let s = Bytes::new(0x3100).io_split::<Size4KiB>(Bytes::new(0x1f00));
+    ///
+    /// assert_eq!(s.head, Bytes::new(0x100));
+    /// assert_eq!(s.middle, NumOfPages::new(3));
+    /// assert_eq!(s.tail, Bytes::zero());
+    /// ```
+    #[must_use]
+    pub const fn io_split<T: PageSize>(self, start_offset: Self) -> IoSplit<T> {
+        let len = self.as_usize() as u64;
+        let to_boundary = T::SIZE.wrapping_sub(start_offset.as_usize() as u64 & T::MASK) & T::MASK;
+        let head = if len < to_boundary { len } else { to_boundary };
+        let rest = len - head;
+
+        // All of them are at most `len`, which came from `usize`.
+        #[allow(clippy::cast_possible_truncation)]
+        IoSplit {
+            head: Self::new(head as usize),
+            middle: NumOfPages::new((rest >> T::SHIFT) as usize),
+            tail: Self::new((rest & T::MASK) as usize),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IoSplit;
+    use crate::{Bytes, NumOfPages};
+    use x86_64::structures::paging::{PageSize, Size1GiB, Size2MiB, Size4KiB};
+
+    fn split<T: PageSize>(len: usize, start: usize) -> IoSplit<T> {
+        let s = Bytes::new(len).io_split::<T>(Bytes::new(start));
+
+        assert_eq!(
+            s.head.as_usize() + s.middle.checked_as_bytes().unwrap().as_usize() + s.tail.as_usize(),
+            len,
+            "{len:#x} bytes from {start:#x}"
+        );
+        assert!((s.head.as_usize() as u64) < T::SIZE);
+        assert!((s.tail.as_usize() as u64) < T::SIZE);
+
+        s
+    }
+
+    #[test]
+    fn within_one_page() {
+        let s = split::<Size4KiB>(0x200, 0x1100);
+
+        assert_eq!(s.head, Bytes::new(0x200));
+        assert_eq!(s.middle, NumOfPages::zero());
+        assert_eq!(s.tail, Bytes::zero());
+    }
+
+    #[test]
+    fn ending_at_page_boundary() {
+        let s = split::<Size4KiB>(0xf00, 0x1100);
+
+        assert_eq!(s.head, Bytes::new(0xf00));
+        assert_eq!(s.middle, NumOfPages::zero());
+        assert_eq!(s.tail, Bytes::zero());
+    }
+
+    #[test]
+    fn aligned_start_within_one_page() {
+        let s = split::<Size4KiB>(0x200, 0x2000);
+
+        assert_eq!(s.head, Bytes::zero());
+        assert_eq!(s.middle, NumOfPages::zero());
+        assert_eq!(s.tail, Bytes::new(0x200));
+    }
+
+    #[test]
+    fn page_aligned() {
+        let s = split::<Size4KiB>(0x3000, 0x5000);
+
+        assert_eq!(s.head, Bytes::zero());
+        assert_eq!(s.middle, NumOfPages::new(3));
+        assert_eq!(s.tail, Bytes::zero());
+    }
+
+    #[test]
+    fn many_pages_with_head_and_tail() {
+        let s = split::<Size4KiB>(0x5234, 0x1f00);
+
+        assert_eq!(s.head, Bytes::new(0x100));
+        assert_eq!(s.middle, NumOfPages::new(5));
+        assert_eq!(s.tail, Bytes::new(0x134));
+
+        let s = split::<Size2MiB>(0x50_0000, 0x30_0000);
+
+        assert_eq!(s.head, Bytes::new(0x10_0000));
+        assert_eq!(s.middle, NumOfPages::new(2));
+        assert_eq!(s.tail, Bytes::zero());
+    }
+
+    #[test]
+    fn empty() {
+        let s = split::<Size4KiB>(0, 0x123);
+
+        assert_eq!(s.head, Bytes::zero());
+        assert_eq!(s.middle, NumOfPages::zero());
+        assert_eq!(s.tail, Bytes::zero());
+    }
+
+    #[test]
+    fn extremes() {
+        split::<Size4KiB>(usize::MAX, 0);
+        split::<Size4KiB>(usize::MAX, 1);
+        split::<Size4KiB>(usize::MAX, usize::MAX);
+        split::<Size1GiB>(usize::MAX, 0x123_4567);
+        split::<Size1GiB>(0x1234, usize::MAX);
+    }
+
+    #[test]
+    fn sums_back() {
+        for &start in &[0, 1, 0xfff, 0x1000, 0x1001, 0x1f_ffff, 0x20_0001] {
+            for &len in &[0, 1, 0xfff, 0x1000, 0x1001, 0x20_0000, 0x123_4567] {
+                split::<Size4KiB>(len, start);
+                split::<Size2MiB>(len, start);
+            }
+        }
+    }
+
+    #[test]
+    fn debug() {
+        let s = split::<Size4KiB>(0x1100, 0xf00);
+
+        assert_eq!(
+            format!("{s:?}"),
+            "IoSplit { head: Bytes(256), middle: NumOfPages::<4KiB>(1), tail: Bytes(0) }"
+        );
+    }
+}
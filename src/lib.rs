@@ -13,10 +13,104 @@
 //! let pages_of_kernel = bytes_of_kernel.as_num_of_pages::<Size4KiB>();
 //! assert_eq!(pages_of_kernel.as_usize(), 77);
 //!
+//! # #[cfg(not(feature = "no-panic"))]
 //! let bytes_of_pages = pages_of_kernel.as_bytes();
+//! # #[cfg(not(feature = "no-panic"))]
 //! assert_eq!(bytes_of_pages.as_usize(), 315392);
 //! ```
+//!
+//! # Targets
+//!
+//! The crate supports the targets with 32-bit and 64-bit `usize`, and CI runs the tests for
+//! `i686-unknown-linux-gnu` as well as the 64-bit host. The `limine` feature is the exception, as
+//! the `limine` crate only builds for the 64-bit targets. The tests whose values do not fit in
+//! 32 bits run only on the 64-bit targets.
+//!
+//! The page-size conversions also handle the targets with 16-bit `usize`, where [`Size2MiB`] and
+//! [`Size1GiB`] do not fit in `usize`. They compute in `u64` instead of truncating such a page
+//! size: the checked ones like `NumOfPages::checked_as_bytes` return [`None`], the ones which
+//! cannot fail like `Bytes::as_num_of_pages` and `NumOfPages::spanning` give the exact result, and
+//! `NumOfPages::as_bytes` and `PageIndex::start_offset` panic like on an overflow. CI does not run
+//! on a 16-bit target, so these paths are tested on the 32-bit target with a page size of 4 GiB,
+//! and the tests with [`Size2MiB`] run only on the 16-bit targets.
+//!
+//! [`Size2MiB`]: x86_64::structures::paging::Size2MiB
+//! [`Size1GiB`]: x86_64::structures::paging::Size1GiB
+//!
+//! # Features
+//!
+//! - `alloc`: Provides `alloc_pages` and `alloc_pages_zeroed` which allocate page-aligned
+//!   buffers with the global allocator.
+//! - `arbitrary`: Implements `Arbitrary` of the [`arbitrary`](https://docs.rs/arbitrary) crate
+//!   for fuzzing. The generated values are biased toward edge cases. This feature requires `std`.
+//! - `bootloader_api`: Provides conversions from `MemoryRegion` of the
+//!   [`bootloader_api`](https://docs.rs/bootloader_api) crate in `bootloader_helpers`.
+//! - `bytemuck`: Implements `Pod`, `Zeroable`, and `TransparentWrapper` of the
+//!   [`bytemuck`](https://docs.rs/bytemuck) crate.
+//! - `float`: Provides the approximate floating-point conversions like `Bytes::as_mib_f64` and
+//!   `Bytes::from_mib_f64`. This feature is off by default so that targets without a floating-point
+//!   unit do not pull in soft-float code.
+//! - `hash32`: Implements `Hash` of the [`hash32`](https://docs.rs/hash32/0.2) crate so that the
+//!   types can be used as keys of the maps of the `heapless` crate.
+//! - `limine`: Provides conversions from the memory map entries of the
+//!   [`limine`](https://docs.rs/limine) crate in `limine_helpers`.
+//! - `multiboot2`: Provides conversions from `MemoryArea` of the
+//!   [`multiboot2`](https://docs.rs/multiboot2) crate in `multiboot2_helpers`.
+//! - `nightly-const-ops`: Implements the arithmetic operators of `Bytes` and `NumOfPages` as
+//!   `impl const` so that they can be used in `const` items, e.g. `const TOTAL: Bytes = A + B;`.
+//!   This feature requires a nightly compiler. Use the `const_*` methods on stable.
+//! - `no-panic`: Removes the items which panic on overflow or division by zero, and the `strict_*`
+//!   methods, which always do. These are the arithmetic operators of `Bytes`, `NumOfPages`,
+//!   `PageIndex`, `BytesPerSecond`, and the binary units like `Kibibytes`, the `const_*`
+//!   counterparts of the operators, `NumOfPages::as_bytes`, `PageIndex::start_offset`,
+//!   `MemoryUnit::to_bytes`, and `AddrExt::add_bytes` and `AddrExt::sub_bytes`.
+//!   The `checked_*`, `saturating_*`, `wrapping_*`, and `overflowing_*` methods and `Checked`
+//!   remain. The methods which panic on an index out of bounds like `PageBitmap::set` and
+//!   `BytesHistogram::count`, and the ones which panic only if the page size does not fit in
+//!   `usize`, which never happens on the 32-bit and 64-bit targets, are not removed. This is for
+//!   the code which must be proven not to panic, e.g. with the `#[no_panic]` attribute of the
+//!   [`no-panic`](https://docs.rs/no-panic) crate, which fails to link a function if a panic is
+//!   reachable from it. With this feature, a panicking operation is a compile error instead of a
+//!   link error far from its cause. Unlike the other features, this one removes items, so enable
+//!   it only in the final binary, never in a library.
+//! - `overflow-checks`: Makes the `+`, `-`, and `*` operators of `Bytes`, `NumOfPages`, and
+//!   `PageIndex`, `NumOfPages::as_bytes`, and `PageIndex::start_offset` panic on overflow
+//!   regardless of the profile, with the operands in the message, e.g.
+//!   `Bytes addition overflowed: 18446744073709551615 + 4096`. Without this feature, they panic
+//!   only if the overflow checks of the profile are enabled, and wrap around otherwise.
+//!   The `const_*` methods are not affected, and with `nightly-const-ops`, the operators are no
+//!   longer `impl const` as the message cannot be formatted in `const`.
+//! - `quickcheck`: Implements `Arbitrary` of the [`quickcheck`](https://docs.rs/quickcheck)
+//!   crate. This feature requires `std`.
+//! - `rand`: Implements `Distribution` of the [`rand`](https://docs.rs/rand) crate, and
+//!   provides the uniform samplers `UniformBytes` and `UniformNumOfPages`.
+//! - `rkyv`: Implements `Archive`, `Serialize`, and `Deserialize` of the
+//!   [`rkyv`](https://rkyv.org) crate. Both types are archived as `u64`.
+//! - `schemars`: Implements `JsonSchema` of the [`schemars`](https://graham.cool/schemars/)
+//!   crate. This feature requires `alloc`.
+//! - `serde`: Implements `Serialize` and `Deserialize` of the [`serde`](https://serde.rs) crate.
+//! - `strict-operands`: Removes the `+`, `-`, `+=`, and `-=` operators of `Bytes` and `NumOfPages`
+//!   whose right-hand side is `usize`, so that a page count held as `usize` cannot be added to a
+//!   byte count by mistake. Wrap the value with `Bytes::new` or `NumOfPages::new` instead. The
+//!   comparisons with `usize`, `NumOfPages::checked_add_usize`, `NumOfPages::checked_sub_usize`,
+//!   `Bytes::wrapping_add_usize`, and `Bytes::wrapping_sub_usize` are removed as well. The
+//!   multiplication and division by `usize` remain as they scale the value without changing the
+//!   unit. Like `no-panic`, this feature removes items, so enable it only in the final binary.
+//! - `test-helpers`: Provides the `assert_bytes_eq` and `assert_pages_eq` macros.
+//! - `track-alloc`: Provides `TrackingAllocator`, a `GlobalAlloc` wrapper which counts the
+//!   allocated bytes.
+//! - `uefi`: Provides conversions from `MemoryDescriptor` of the [`uefi`](https://docs.rs/uefi)
+//!   crate in `uefi_helpers`.
+//! - `uom`: Implements the conversions between `Bytes` and `Information` of the
+//!   [`uom`](https://docs.rs/uom) crate in bytes, with `usize` or `u64` as the storage type. The
+//!   conversion from the `u64` one is `TryFrom` as it may not fit in `usize`.
+//! - `zerocopy`: Implements `FromBytes`, `IntoBytes`, `KnownLayout`, and `Immutable` of the
+//!   [`zerocopy`](https://docs.rs/zerocopy) crate.
 #![cfg_attr(not(test), no_std)]
+#![cfg_attr(
+    all(feature = "nightly-const-ops", not(feature = "overflow-checks")),
+    feature(const_trait_impl, const_ops)
+)]
 #![deny(
     rustdoc::all,
     missing_docs,
@@ -28,7 +122,6 @@
     non_ascii_idents,
     private_doc_tests,
     single_use_lifetimes,
-    unaligned_references,
     unreachable_pub,
     unused_crate_dependencies,
     unused_extern_crates,
@@ -36,13 +129,167 @@
     unused_import_braces,
     unused_lifetimes,
     unused_qualifications,
-    pointer_structural_match,
     missing_debug_implementations
 )]
 #![deny(clippy::all, clippy::pedantic)]
+// The derive macros of `zerocopy` generate non-ASCII identifiers.
+#![cfg_attr(feature = "zerocopy", allow(non_ascii_idents))]
+
+#[cfg(any(feature = "alloc", feature = "quickcheck", feature = "schemars"))]
+extern crate alloc;
 
+/// Implements an operator trait, as `impl const` with the `nightly-const-ops` feature unless the
+/// `overflow-checks` feature is enabled, whose panic messages cannot be formatted in `const`.
+///
+/// The generic parameters are written in brackets, e.g. `impl[T: PageSize] Add for
+/// NumOfPages<T> { ... }`.
+#[cfg(all(feature = "nightly-const-ops", not(feature = "overflow-checks")))]
+#[cfg_attr(feature = "no-panic", allow(unused_macros))]
+macro_rules! const_op_impl {
+    (impl $([$($g:tt)*])? $trait:ident $(<$rhs:ty>)? for $ty:ty { $($body:tt)* }) => {
+        impl<$($($g)*)?> const $trait$(<$rhs>)? for $ty { $($body)* }
+    };
+}
+#[cfg(not(all(feature = "nightly-const-ops", not(feature = "overflow-checks"))))]
+#[cfg_attr(feature = "no-panic", allow(unused_macros))]
+macro_rules! const_op_impl {
+    (impl $([$($g:tt)*])? $trait:ident $(<$rhs:ty>)? for $ty:ty { $($body:tt)* }) => {
+        impl<$($($g)*)?> $trait$(<$rhs>)? for $ty { $($body)* }
+    };
+}
+
+mod addr_ext;
+mod alignment;
+#[cfg(feature = "alloc")]
+mod alloc_pages;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
+#[cfg(target_has_atomic = "ptr")]
+mod atomic_bytes;
+#[cfg(target_has_atomic = "ptr")]
+mod atomic_num_of_pages;
+mod binary_units;
+#[cfg(feature = "bootloader_api")]
+pub mod bootloader_helpers;
+mod bump_offset;
+#[cfg(target_has_atomic = "ptr")]
+mod byte_counter;
+mod byte_region;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impls;
 mod bytes;
+mod bytes_histogram;
+mod bytes_per_second;
+mod checked;
+#[cfg(doctest)]
+pub mod compile_fail_tests;
+#[cfg(all(
+    test,
+    feature = "nightly-const-ops",
+    not(any(feature = "no-panic", feature = "overflow-checks"))
+))]
+mod const_ops_tests;
+mod disk;
+pub mod elf;
+mod error;
+#[cfg(feature = "float")]
+mod float_impls;
+mod frame_allocator_ext;
+mod framebuffer;
+#[cfg(feature = "hash32")]
+mod hash32_impls;
+mod io_split;
+#[cfg(feature = "limine")]
+pub mod limine_helpers;
+mod mapper_ext;
+mod memory_stats;
+mod memory_unit;
+#[cfg(feature = "multiboot2")]
+pub mod multiboot2_helpers;
+mod non_zero_bytes;
 mod num_of_pages;
+#[cfg(not(feature = "no-panic"))]
+mod overflow;
+mod page_bitmap;
+mod page_index;
+mod paging;
+mod pci;
+mod per_cpu;
+mod phys_region;
+mod pow2;
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impls;
+#[cfg(feature = "rand")]
+mod rand_impls;
+mod range;
+mod region_summary;
+#[cfg(feature = "rkyv")]
+mod rkyv_impls;
+#[cfg(feature = "schemars")]
+mod schemars_impls;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod smbios;
+#[cfg(feature = "test-helpers")]
+#[doc(hidden)]
+pub mod test_helpers;
+#[cfg(all(feature = "track-alloc", target_has_atomic = "ptr"))]
+mod tracking_allocator;
+#[cfg(feature = "uefi")]
+pub mod uefi_helpers;
+#[cfg(feature = "uom")]
+mod uom_impls;
+mod virt_region;
+pub mod virtio;
+#[cfg(feature = "zerocopy")]
+mod zerocopy_impls;
 
+pub use addr_ext::AddrExt;
+pub use alignment::Alignment;
+#[cfg(feature = "alloc")]
+pub use alloc_pages::{alloc_pages, alloc_pages_zeroed, PageBuffer};
+#[cfg(target_has_atomic = "ptr")]
+pub use atomic_bytes::AtomicBytes;
+#[cfg(target_has_atomic = "ptr")]
+pub use atomic_num_of_pages::AtomicNumOfPages;
+pub use binary_units::{Gibibytes, Kibibytes, Mebibytes};
+pub use bump_offset::BumpOffset;
+#[cfg(target_has_atomic = "ptr")]
+pub use byte_counter::ByteCounter;
+pub use byte_region::{coalesce_regions, ByteRegion};
 pub use bytes::Bytes;
+#[cfg(all(target_has_atomic = "64", target_has_atomic = "ptr"))]
+pub use bytes_histogram::AtomicBytesHistogram;
+pub use bytes_histogram::BytesHistogram;
+pub use bytes_per_second::BytesPerSecond;
+pub use checked::Checked;
+pub use error::{
+    ConversionError, DistributeError, MapRangeError, MemoryStatsError, ParseBytesError, RegionError,
+};
+pub use frame_allocator_ext::FrameAllocatorExt;
+pub use io_split::IoSplit;
+pub use mapper_ext::MapperExt;
+pub use memory_stats::MemoryStats;
+pub use memory_unit::MemoryUnit;
+pub use non_zero_bytes::NonZeroBytes;
 pub use num_of_pages::NumOfPages;
+pub use page_bitmap::PageBitmap;
+pub use page_index::{PageIndex, PageOffset};
+pub use paging::{frame_range, frame_range_inclusive, page_range, page_range_inclusive};
+pub use phys_region::PhysRegion;
+#[cfg(feature = "rand")]
+pub use rand_impls::{UniformBytes, UniformNumOfPages};
+pub use range::{ByteRangeExt, BytesRange, NumOfPagesRange};
+pub use region_summary::{summarize_phys_regions, summarize_regions, RegionSummary};
+#[cfg(all(feature = "track-alloc", target_has_atomic = "ptr"))]
+pub use tracking_allocator::{AllocStats, TrackingAllocator};
+pub use virt_region::VirtRegion;
+
+// These crates are only used by the tests of optional features.
+#[cfg(test)]
+use {
+    heapless as _, multiboot2 as _, postcard as _, rand as _, rkyv as _, schemars as _, serde as _,
+    serde_json as _,
+};
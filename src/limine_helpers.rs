@@ -0,0 +1,258 @@
+//! Conversions from the memory map of the [`limine`](https://docs.rs/limine) crate.
+//!
+//! The base address and the length of an [`Entry`] are `u64` even on 32-bit targets, so the
+//! conversions are fallible and fail with [`ConversionError::Overflow`] instead of truncating.
+//!
+//! ```rust
+//! use core::convert::TryFrom;
+//! use limine::memory_map::{Entry, EntryType};
+//! use os_units::{limine_helpers, Bytes, NumOfPages};
+//! use x86_64::structures::paging::Size4KiB;
+//!
+//! let usable = Entry { base: 0x1000, length: 0x9_e000, entry_type: EntryType::USABLE };
+//! let framebuffer = Entry { base: 0xfd00_0000, length: 0x30_0000, entry_type: EntryType::FRAMEBUFFER };
+//!
+//! assert_eq!(Bytes::try_from(&usable), Ok(Bytes::new(0x9_e000)));
+//! assert_eq!(NumOfPages::<Size4KiB>::try_from(&usable), Ok(NumOfPages::new(0x9e)));
+//!
+//! let summary = limine_helpers::summarize(&[&usable, &framebuffer]).unwrap();
+//!
+//! assert_eq!(summary.usable, Bytes::new(0x9_e000));
+//! assert_eq!(summary.framebuffer, Bytes::new(0x30_0000));
+//! ```
+
+use crate::Bytes;
+use crate::ConversionError;
+use crate::NumOfPages;
+use crate::PhysRegion;
+use crate::RegionError;
+use core::convert::TryFrom;
+use limine::memory_map::{Entry, EntryType};
+use x86_64::structures::paging::{PageSize, Size4KiB};
+use x86_64::PhysAddr;
+
+/// The total sizes of the entries of a memory map per entry type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct MemoryMapSummary {
+    /// The total size of [`EntryType::USABLE`].
+    pub usable: Bytes,
+    /// The total size of [`EntryType::RESERVED`].
+    pub reserved: Bytes,
+    /// The total size of [`EntryType::ACPI_RECLAIMABLE`].
+    pub acpi_reclaimable: Bytes,
+    /// The total size of [`EntryType::ACPI_NVS`].
+    pub acpi_nvs: Bytes,
+    /// The total size of [`EntryType::BAD_MEMORY`].
+    pub bad_memory: Bytes,
+    /// The total size of [`EntryType::BOOTLOADER_RECLAIMABLE`].
+    pub bootloader_reclaimable: Bytes,
+    /// The total size of [`EntryType::EXECUTABLE_AND_MODULES`].
+    pub executable_and_modules: Bytes,
+    /// The total size of [`EntryType::FRAMEBUFFER`].
+    pub framebuffer: Bytes,
+    /// The total size of the entry types unknown to this crate.
+    pub unknown: Bytes,
+}
+impl MemoryMapSummary {
+    const fn zero() -> Self {
+        Self {
+            usable: Bytes::zero(),
+            reserved: Bytes::zero(),
+            acpi_reclaimable: Bytes::zero(),
+            acpi_nvs: Bytes::zero(),
+            bad_memory: Bytes::zero(),
+            bootloader_reclaimable: Bytes::zero(),
+            executable_and_modules: Bytes::zero(),
+            framebuffer: Bytes::zero(),
+            unknown: Bytes::zero(),
+        }
+    }
+
+    fn total_mut(&mut self, entry_type: EntryType) -> &mut Bytes {
+        match entry_type {
+            EntryType::USABLE => &mut self.usable,
+            EntryType::RESERVED => &mut self.reserved,
+            EntryType::ACPI_RECLAIMABLE => &mut self.acpi_reclaimable,
+            EntryType::ACPI_NVS => &mut self.acpi_nvs,
+            EntryType::BAD_MEMORY => &mut self.bad_memory,
+            EntryType::BOOTLOADER_RECLAIMABLE => &mut self.bootloader_reclaimable,
+            EntryType::EXECUTABLE_AND_MODULES => &mut self.executable_and_modules,
+            EntryType::FRAMEBUFFER => &mut self.framebuffer,
+            _ => &mut self.unknown,
+        }
+    }
+}
+
+/// Sums the sizes of `entries` per entry type.
+///
+/// `MemoryMapResponse::entries` of the `limine` crate can be passed directly.
+///
+/// This function returns [`None`] if the size of an entry or a total does not fit in `usize`.
+#[must_use]
+pub fn summarize(entries: &[&Entry]) -> Option<MemoryMapSummary> {
+    let mut summary = MemoryMapSummary::zero();
+
+    for e in entries {
+        let total = summary.total_mut(e.entry_type);
+        let size = Bytes::try_from(*e).ok()?;
+
+        *total = Bytes::new(total.as_usize().checked_add(size.as_usize())?);
+    }
+
+    Some(summary)
+}
+
+/// The size of the entry.
+impl TryFrom<&Entry> for Bytes {
+    type Error = ConversionError;
+
+    fn try_from(e: &Entry) -> Result<Self, Self::Error> {
+        Self::try_from(e.length)
+    }
+}
+/// The number of the whole frames in the entry. A partial frame at either end is not counted.
+impl TryFrom<&Entry> for NumOfPages<Size4KiB> {
+    type Error = ConversionError;
+
+    fn try_from(e: &Entry) -> Result<Self, Self::Error> {
+        let end = e
+            .base
+            .checked_add(e.length)
+            .ok_or(ConversionError::Overflow)?;
+        let start = e.base.div_ceil(Size4KiB::SIZE);
+        let end = end / Size4KiB::SIZE;
+
+        Ok(Self::new(usize::try_from(end.saturating_sub(start))?))
+    }
+}
+/// Fails with [`RegionError::InvalidAddress`] if the entry does not fit in the physical
+/// address space.
+impl TryFrom<&Entry> for PhysRegion {
+    type Error = RegionError;
+
+    fn try_from(e: &Entry) -> Result<Self, Self::Error> {
+        let start = PhysAddr::try_new(e.base).map_err(|_| RegionError::InvalidAddress)?;
+
+        Self::try_from((start, Bytes::try_from(e)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{summarize, MemoryMapSummary};
+    use crate::{Bytes, ConversionError, NumOfPages, PhysRegion, RegionError};
+    use core::convert::TryFrom;
+    use limine::memory_map::{Entry, EntryType};
+    use x86_64::structures::paging::Size4KiB;
+    use x86_64::PhysAddr;
+
+    fn entry(base: u64, length: u64, entry_type: EntryType) -> Entry {
+        Entry {
+            base,
+            length,
+            entry_type,
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        let e = entry(0x1000, 0x2800, EntryType::USABLE);
+
+        assert_eq!(Bytes::try_from(&e), Ok(Bytes::new(0x2800)));
+    }
+
+    #[test]
+    fn bytes_larger_than_4_gib() {
+        let e = entry(0x1_0000_0000, 0x3_0000_0000, EntryType::USABLE);
+
+        assert_eq!(Bytes::try_from(&e), Ok(Bytes::new(0x3_0000_0000)));
+    }
+
+    #[test]
+    fn num_of_pages_larger_than_4_gib() {
+        let e = entry(0x1_0000_0000, 0x3_0000_0000, EntryType::USABLE);
+
+        assert_eq!(
+            NumOfPages::<Size4KiB>::try_from(&e),
+            Ok(NumOfPages::new(0x30_0000))
+        );
+    }
+
+    #[test]
+    fn num_of_pages_ignores_partial_frames() {
+        let e = entry(0x800, 0x2000, EntryType::USABLE);
+
+        assert_eq!(NumOfPages::<Size4KiB>::try_from(&e), Ok(NumOfPages::new(1)));
+    }
+
+    #[test]
+    fn num_of_pages_end_overflow() {
+        let e = entry(u64::MAX, 2, EntryType::RESERVED);
+
+        assert_eq!(
+            NumOfPages::<Size4KiB>::try_from(&e),
+            Err(ConversionError::Overflow)
+        );
+    }
+
+    #[test]
+    fn phys_region() {
+        let e = entry(0x1_0000_0000, 0x3_0000_0000, EntryType::USABLE);
+        let expected =
+            PhysRegion::new(PhysAddr::new(0x1_0000_0000), Bytes::new(0x3_0000_0000)).unwrap();
+
+        assert_eq!(PhysRegion::try_from(&e), Ok(expected));
+    }
+
+    #[test]
+    fn phys_region_out_of_range() {
+        let e = entry(1 << 52, 0x1000, EntryType::RESERVED);
+
+        assert_eq!(PhysRegion::try_from(&e), Err(RegionError::InvalidAddress));
+    }
+
+    #[test]
+    fn summarize_representative_map() {
+        let map = [
+            entry(0, 0x1000, EntryType::RESERVED),
+            entry(0x1000, 0x9_e000, EntryType::USABLE),
+            entry(0x10_0000, 0x10_0000, EntryType::EXECUTABLE_AND_MODULES),
+            entry(0x20_0000, 0x7e00_0000, EntryType::USABLE),
+            entry(0x7e20_0000, 0x4_0000, EntryType::BOOTLOADER_RECLAIMABLE),
+            entry(0x7e24_0000, 0x2_0000, EntryType::ACPI_RECLAIMABLE),
+            entry(0x7e26_0000, 0x1_0000, EntryType::ACPI_NVS),
+            entry(0x7e27_0000, 0x1000, EntryType::BAD_MEMORY),
+            entry(0xfd00_0000, 0x30_0000, EntryType::FRAMEBUFFER),
+            entry(0x1_0000_0000, 0x1_8000_0000, EntryType::USABLE),
+        ];
+        let refs: Vec<&Entry> = map.iter().collect();
+
+        let expected = MemoryMapSummary {
+            usable: Bytes::new(0x9_e000 + 0x7e00_0000 + 0x1_8000_0000),
+            reserved: Bytes::new(0x1000),
+            acpi_reclaimable: Bytes::new(0x2_0000),
+            acpi_nvs: Bytes::new(0x1_0000),
+            bad_memory: Bytes::new(0x1000),
+            bootloader_reclaimable: Bytes::new(0x4_0000),
+            executable_and_modules: Bytes::new(0x10_0000),
+            framebuffer: Bytes::new(0x30_0000),
+            unknown: Bytes::zero(),
+        };
+
+        assert_eq!(summarize(&refs), Some(expected));
+    }
+
+    #[test]
+    fn summarize_empty_map() {
+        assert_eq!(summarize(&[]), Some(MemoryMapSummary::zero()));
+    }
+
+    #[test]
+    fn summarize_overflow() {
+        let a = entry(0, u64::MAX, EntryType::USABLE);
+        let b = entry(0, 1, EntryType::USABLE);
+
+        assert_eq!(summarize(&[&a, &b]), None);
+    }
+}
@@ -0,0 +1,379 @@
+use crate::MapRangeError;
+use crate::NumOfPages;
+use x86_64::structures::paging::mapper::{MapperFlushAll, UnmapError};
+use x86_64::structures::paging::{
+    FrameAllocator, Mapper, Page, PageSize, PageTableFlags, PhysFrame, Size4KiB,
+};
+
+/// Mapping and unmapping a range of pages with a [`Mapper`].
+///
+/// This trait is implemented for all the mappers.
+pub trait MapperExt<S: PageSize>: Mapper<S> {
+    /// Maps `count` pages from `start` to the frames yielded by `frames`, in order.
+    ///
+    /// If mapping a page fails, the pages mapped by this call are unmapped before the error is
+    /// returned. The frames yielded before the failure are not used any more.
+    ///
+    /// On success, the returned [`MapperFlushAll`] must be flushed or ignored, like the
+    /// [`MapperFlush`] returned by [`Mapper::map_to`].
+    ///
+    /// # Errors
+    ///
+    /// - [`MapRangeError::RangeOverflow`] if the pages go past the end of the address space.
+    /// - [`MapRangeError::NotEnoughFrames`] if `frames` yields fewer than `count` frames.
+    /// - [`MapRangeError::Map`] if [`Mapper::map_to`] fails.
+    ///
+    /// # Safety
+    ///
+    /// The same as [`Mapper::map_to`], for each pair of the page and the frame.
+    ///
+    /// [`MapperFlush`]: x86_64::structures::paging::mapper::MapperFlush
+    unsafe fn map_range<I, A>(
+        &mut self,
+        start: Page<S>,
+        count: NumOfPages<S>,
+        frames: I,
+        flags: PageTableFlags,
+        frame_allocator: &mut A,
+    ) -> Result<MapperFlushAll, MapRangeError<S>>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = PhysFrame<S>>,
+        A: FrameAllocator<Size4KiB> + ?Sized,
+    {
+        let pages = crate::page_range(start, count).ok_or(MapRangeError::RangeOverflow)?;
+        let mut frames = frames.into_iter();
+
+        for (mapped, page) in pages.enumerate() {
+            let result = frames
+                .next()
+                .ok_or(MapRangeError::NotEnoughFrames)
+                .and_then(|frame| {
+                    // SAFETY: The caller must uphold the safety requirements.
+                    unsafe { self.map_to(page, frame, flags, frame_allocator) }
+                        .map_err(MapRangeError::from)
+                });
+
+            match result {
+                Ok(flush) => flush.ignore(),
+                Err(e) => {
+                    // The pages were mapped by this call, so unmapping them never fails.
+                    for page in pages.take(mapped) {
+                        if let Ok((_, flush)) = self.unmap(page) {
+                            flush.ignore();
+                        }
+                    }
+
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(MapperFlushAll::new())
+    }
+
+    /// Unmaps `count` pages from `start`.
+    ///
+    /// The returned [`MapperFlushAll`] must be flushed or ignored, like the [`MapperFlush`]
+    /// returned by [`Mapper::unmap`].
+    ///
+    /// # Errors
+    ///
+    /// This method returns the error of the first page which [`Mapper::unmap`] fails to unmap. The
+    /// pages before it stay unmapped, and the pages after it are left untouched. As the pages
+    /// before it may be cached in the TLB, the whole TLB should be flushed in this case too.
+    ///
+    /// If the pages go past the end of the address space, this method returns
+    /// [`UnmapError::PageNotMapped`] without unmapping any page.
+    ///
+    /// [`MapperFlush`]: x86_64::structures::paging::mapper::MapperFlush
+    fn unmap_range(
+        &mut self,
+        start: Page<S>,
+        count: NumOfPages<S>,
+    ) -> Result<MapperFlushAll, UnmapError> {
+        let pages = crate::page_range(start, count).ok_or(UnmapError::PageNotMapped)?;
+
+        for page in pages {
+            let (_, flush) = self.unmap(page)?;
+            flush.ignore();
+        }
+
+        Ok(MapperFlushAll::new())
+    }
+}
+impl<S: PageSize, M: Mapper<S>> MapperExt<S> for M {}
+
+#[cfg(test)]
+mod tests {
+    use super::MapperExt;
+    use crate::{MapRangeError, NumOfPages};
+    use x86_64::structures::paging::mapper::{
+        MapToError, MappedPageTable, PageTableFrameMapping, UnmapError,
+    };
+    use x86_64::structures::paging::{
+        FrameAllocator, Mapper, Page, PageTable, PageTableFlags, PhysFrame, Size4KiB,
+    };
+    use x86_64::{PhysAddr, VirtAddr};
+
+    const NUM_TABLES: usize = 8;
+
+    /// Page tables in a boxed array. The physical address of the `n`th table is `n * 0x1000`.
+    struct Tables {
+        tables: Box<[PageTable; NUM_TABLES]>,
+        next: usize,
+    }
+    impl Tables {
+        fn new() -> Self {
+            Self {
+                tables: Box::new([(); NUM_TABLES].map(|()| PageTable::new())),
+                next: 1,
+            }
+        }
+
+        fn mapper(&mut self) -> (MappedPageTable<'_, Mapping>, TableAllocator<'_>) {
+            let base: *mut PageTable = self.tables.as_mut_ptr();
+            // SAFETY: The tables live as long as the mapper does, and `Mapping` maps the
+            // physical addresses of the tables to them.
+            let level_4 = unsafe { &mut *base };
+            let mapper = unsafe { MappedPageTable::new(level_4, Mapping(base)) };
+
+            (mapper, TableAllocator(&mut self.next))
+        }
+    }
+
+    struct Mapping(*mut PageTable);
+    unsafe impl PageTableFrameMapping for Mapping {
+        fn frame_to_pointer(&self, frame: PhysFrame) -> *mut PageTable {
+            #[allow(clippy::cast_possible_truncation)]
+            let index = (frame.start_address().as_u64() / 0x1000) as usize;
+
+            assert!(index < NUM_TABLES, "Not a page table.");
+            self.0.wrapping_add(index)
+        }
+    }
+
+    /// Hands out the unused tables of `Tables`.
+    struct TableAllocator<'a>(&'a mut usize);
+    unsafe impl FrameAllocator<Size4KiB> for TableAllocator<'_> {
+        fn allocate_frame(&mut self) -> Option<PhysFrame> {
+            if *self.0 < NUM_TABLES {
+                let frame = frame(*self.0 as u64 * 0x1000);
+                *self.0 += 1;
+                Some(frame)
+            } else {
+                None
+            }
+        }
+    }
+
+    fn page(addr: u64) -> Page {
+        Page::containing_address(VirtAddr::new(addr))
+    }
+
+    fn frame(addr: u64) -> PhysFrame {
+        PhysFrame::containing_address(PhysAddr::new(addr))
+    }
+
+    fn data_frames(start: u64) -> impl Iterator<Item = PhysFrame> {
+        (0..).map(move |i| frame(start + i * 0x1000))
+    }
+
+    const FLAGS: PageTableFlags = PageTableFlags::PRESENT.union(PageTableFlags::WRITABLE);
+
+    #[test]
+    fn map_range() {
+        let mut tables = Tables::new();
+        let (mut mapper, mut allocator) = tables.mapper();
+
+        let r = unsafe {
+            mapper.map_range(
+                page(0x40_0000),
+                NumOfPages::new(3),
+                data_frames(0x100_0000),
+                FLAGS,
+                &mut allocator,
+            )
+        };
+        r.unwrap().ignore();
+
+        assert_eq!(
+            mapper.translate_page(page(0x40_0000)).ok(),
+            Some(frame(0x100_0000))
+        );
+        assert_eq!(
+            mapper.translate_page(page(0x40_2000)).ok(),
+            Some(frame(0x100_2000))
+        );
+        assert!(mapper.translate_page(page(0x40_3000)).is_err());
+    }
+
+    #[test]
+    fn map_range_zero() {
+        let mut tables = Tables::new();
+        let (mut mapper, mut allocator) = tables.mapper();
+
+        let r = unsafe {
+            mapper.map_range(
+                page(0x40_0000),
+                NumOfPages::new(0),
+                data_frames(0x100_0000),
+                FLAGS,
+                &mut allocator,
+            )
+        };
+        r.unwrap().ignore();
+
+        assert!(mapper.translate_page(page(0x40_0000)).is_err());
+    }
+
+    #[test]
+    fn map_range_unwinds_on_already_mapped_page() {
+        let mut tables = Tables::new();
+        let (mut mapper, mut allocator) = tables.mapper();
+
+        unsafe { mapper.map_to(page(0x40_2000), frame(0x200_0000), FLAGS, &mut allocator) }
+            .unwrap()
+            .ignore();
+
+        let r = unsafe {
+            mapper.map_range(
+                page(0x40_0000),
+                NumOfPages::new(4),
+                data_frames(0x100_0000),
+                FLAGS,
+                &mut allocator,
+            )
+        };
+
+        assert!(matches!(
+            r,
+            Err(MapRangeError::Map(MapToError::PageAlreadyMapped(f))) if f == frame(0x100_2000)
+        ));
+        assert!(mapper.translate_page(page(0x40_0000)).is_err());
+        assert!(mapper.translate_page(page(0x40_1000)).is_err());
+        assert_eq!(
+            mapper.translate_page(page(0x40_2000)).ok(),
+            Some(frame(0x200_0000))
+        );
+        assert!(mapper.translate_page(page(0x40_3000)).is_err());
+    }
+
+    #[test]
+    fn map_range_unwinds_on_not_enough_frames() {
+        let mut tables = Tables::new();
+        let (mut mapper, mut allocator) = tables.mapper();
+
+        let r = unsafe {
+            mapper.map_range(
+                page(0x40_0000),
+                NumOfPages::new(3),
+                data_frames(0x100_0000).take(2),
+                FLAGS,
+                &mut allocator,
+            )
+        };
+
+        assert!(matches!(r, Err(MapRangeError::NotEnoughFrames)));
+        assert!(mapper.translate_page(page(0x40_0000)).is_err());
+        assert!(mapper.translate_page(page(0x40_1000)).is_err());
+    }
+
+    #[test]
+    fn map_range_unwinds_on_table_allocation_failure() {
+        let mut tables = Tables::new();
+        let (mut mapper, mut allocator) = tables.mapper();
+
+        // The first page needs 3 tables. Each 1 GiB boundary crossed needs 2 more, so the fourth
+        // page runs out of the 7 tables.
+        let pages = (0..5).map(|i| page(i * 0x4000_0000));
+        let frames = data_frames(0x100_0000);
+
+        for (i, (p, f)) in pages.zip(frames).enumerate() {
+            let r =
+                unsafe { mapper.map_range(p, NumOfPages::new(1), Some(f), FLAGS, &mut allocator) };
+
+            if i < 3 {
+                r.unwrap().ignore();
+            } else {
+                assert!(matches!(
+                    r,
+                    Err(MapRangeError::Map(MapToError::FrameAllocationFailed))
+                ));
+                assert!(mapper.translate_page(p).is_err());
+                return;
+            }
+        }
+
+        panic!("The tables did not run out.");
+    }
+
+    #[test]
+    fn map_range_overflow() {
+        let mut tables = Tables::new();
+        let (mut mapper, mut allocator) = tables.mapper();
+
+        let r = unsafe {
+            mapper.map_range(
+                page(0xffff_ffff_ffff_f000),
+                NumOfPages::new(2),
+                data_frames(0x100_0000),
+                FLAGS,
+                &mut allocator,
+            )
+        };
+
+        assert!(matches!(r, Err(MapRangeError::RangeOverflow)));
+    }
+
+    #[test]
+    fn unmap_range() {
+        let mut tables = Tables::new();
+        let (mut mapper, mut allocator) = tables.mapper();
+
+        unsafe {
+            mapper.map_range(
+                page(0x40_0000),
+                NumOfPages::new(3),
+                data_frames(0x100_0000),
+                FLAGS,
+                &mut allocator,
+            )
+        }
+        .unwrap()
+        .ignore();
+
+        mapper
+            .unmap_range(page(0x40_0000), NumOfPages::new(2))
+            .unwrap()
+            .ignore();
+
+        assert!(mapper.translate_page(page(0x40_0000)).is_err());
+        assert!(mapper.translate_page(page(0x40_1000)).is_err());
+        assert_eq!(
+            mapper.translate_page(page(0x40_2000)).ok(),
+            Some(frame(0x100_2000))
+        );
+    }
+
+    #[test]
+    fn unmap_range_stops_at_unmapped_page() {
+        let mut tables = Tables::new();
+        let (mut mapper, mut allocator) = tables.mapper();
+
+        for p in [0x40_0000, 0x40_2000] {
+            unsafe { mapper.map_to(page(p), frame(0x100_0000 + p), FLAGS, &mut allocator) }
+                .unwrap()
+                .ignore();
+        }
+
+        let r = mapper.unmap_range(page(0x40_0000), NumOfPages::new(3));
+
+        assert!(matches!(r, Err(UnmapError::PageNotMapped)));
+        assert!(mapper.translate_page(page(0x40_0000)).is_err());
+        assert_eq!(
+            mapper.translate_page(page(0x40_2000)).ok(),
+            Some(frame(0x140_2000))
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use crate::error::MemoryStatsError;
+use crate::Bytes;
+use core::fmt;
+
+/// The amount of memory in a pool, split into used, reserved, and free parts.
+///
+/// The sum of the used and the reserved memory never exceeds the total.
+///
+/// ```rust
+/// use os_units::{Bytes, MemoryStats};
+///
+/// let mut stats = MemoryStats::new(Bytes::new(0x4000_0000), Bytes::new(0x100_0000)).unwrap();
+///
+/// stats.allocate(Bytes::new(0x1000_0000)).unwrap();
+///
+/// assert_eq!(stats.free(), Bytes::new(0x2f00_0000));
+/// assert_eq!(stats.percent_used(), 25);
+/// assert_eq!(
+///     stats.to_string(),
+///     "256 MiB used, 16 MiB reserved, 752 MiB free of 1 GiB (25% used)"
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryStats {
+    total: Bytes,
+    used: Bytes,
+    reserved: Bytes,
+}
+impl MemoryStats {
+    /// Creates a new instance with no used memory.
+    ///
+    /// This method returns [`None`] if `reserved > total`.
+    #[must_use]
+    pub const fn new(total: Bytes, reserved: Bytes) -> Option<Self> {
+        if reserved.as_usize() <= total.as_usize() {
+            Some(Self {
+                total,
+                used: Bytes::zero(),
+                reserved,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the total memory.
+    #[must_use]
+    pub const fn total(self) -> Bytes {
+        self.total
+    }
+
+    /// Returns the used memory.
+    #[must_use]
+    pub const fn used(self) -> Bytes {
+        self.used
+    }
+
+    /// Returns the reserved memory.
+    #[must_use]
+    pub const fn reserved(self) -> Bytes {
+        self.reserved
+    }
+
+    /// Returns the memory which is neither used nor reserved.
+    ///
+    /// This never underflows as the used and the reserved memory never exceed the total.
+    #[must_use]
+    pub const fn free(self) -> Bytes {
+        Bytes::new(self.total.as_usize() - self.used.as_usize() - self.reserved.as_usize())
+    }
+
+    /// Returns the percentage of the used memory in the total, rounded down.
+    ///
+    /// This method returns 0 if the total is zero.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn percent_used(self) -> u8 {
+        if self.total.as_usize() == 0 {
+            0
+        } else {
+            // The result is at most 100 as the used memory never exceeds the total.
+            (self.used.as_usize() as u128 * 100 / self.total.as_usize() as u128) as u8
+        }
+    }
+
+    /// Marks `bytes` of the free memory as used.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`MemoryStatsError::InsufficientMemory`] and leaves `self` unchanged if
+    /// `bytes` is larger than the free memory.
+    pub fn allocate(&mut self, bytes: Bytes) -> Result<(), MemoryStatsError> {
+        let free = self.free();
+
+        if bytes.as_usize() > free.as_usize() {
+            return Err(MemoryStatsError::InsufficientMemory { free });
+        }
+
+        self.used = Bytes::new(self.used.as_usize() + bytes.as_usize());
+
+        Ok(())
+    }
+
+    /// Marks `bytes` of the used memory as free.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`MemoryStatsError::ReleasedTooMuch`] and leaves `self` unchanged if
+    /// `bytes` is larger than the used memory.
+    pub fn release(&mut self, bytes: Bytes) -> Result<(), MemoryStatsError> {
+        let used = self.used;
+
+        if bytes.as_usize() > used.as_usize() {
+            return Err(MemoryStatsError::ReleasedTooMuch { used });
+        }
+
+        self.used = Bytes::new(used.as_usize() - bytes.as_usize());
+
+        Ok(())
+    }
+}
+impl fmt::Display for MemoryStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} used, {} reserved, {} free of {} ({}% used)",
+            self.used.display_binary(),
+            self.reserved.display_binary(),
+            self.free().display_binary(),
+            self.total.display_binary(),
+            self.percent_used()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MemoryStats;
+    use crate::error::MemoryStatsError;
+    use crate::Bytes;
+
+    fn stats(total: usize, reserved: usize) -> MemoryStats {
+        MemoryStats::new(Bytes::new(total), Bytes::new(reserved)).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        let s = stats(0x4000, 0x1000);
+
+        assert_eq!(s.total(), Bytes::new(0x4000));
+        assert_eq!(s.used(), Bytes::zero());
+        assert_eq!(s.reserved(), Bytes::new(0x1000));
+        assert_eq!(s.free(), Bytes::new(0x3000));
+    }
+
+    #[test]
+    fn new_all_reserved() {
+        let s = stats(0x4000, 0x4000);
+
+        assert_eq!(s.free(), Bytes::zero());
+    }
+
+    #[test]
+    fn new_reserved_exceeds_total() {
+        assert_eq!(
+            MemoryStats::new(Bytes::new(0x1000), Bytes::new(0x1001)),
+            None
+        );
+    }
+
+    #[test]
+    fn allocate_release() {
+        let mut s = stats(0x4000, 0x1000);
+
+        assert_eq!(s.allocate(Bytes::new(0x2000)), Ok(()));
+        assert_eq!(s.used(), Bytes::new(0x2000));
+        assert_eq!(s.free(), Bytes::new(0x1000));
+
+        assert_eq!(s.release(Bytes::new(0x1000)), Ok(()));
+        assert_eq!(s.used(), Bytes::new(0x1000));
+        assert_eq!(s.free(), Bytes::new(0x2000));
+    }
+
+    #[test]
+    fn allocate_all_free() {
+        let mut s = stats(0x4000, 0x1000);
+
+        assert_eq!(s.allocate(Bytes::new(0x3000)), Ok(()));
+        assert_eq!(s.free(), Bytes::zero());
+    }
+
+    #[test]
+    fn allocate_more_than_free() {
+        let mut s = stats(0x4000, 0x1000);
+
+        assert_eq!(
+            s.allocate(Bytes::new(0x3001)),
+            Err(MemoryStatsError::InsufficientMemory {
+                free: Bytes::new(0x3000)
+            })
+        );
+        assert_eq!(s, stats(0x4000, 0x1000));
+    }
+
+    #[test]
+    fn allocate_does_not_overflow() {
+        let mut s = stats(usize::MAX, 0);
+
+        assert_eq!(s.allocate(Bytes::new(usize::MAX)), Ok(()));
+        assert_eq!(
+            s.allocate(Bytes::new(1)),
+            Err(MemoryStatsError::InsufficientMemory {
+                free: Bytes::zero()
+            })
+        );
+        assert_eq!(s.percent_used(), 100);
+    }
+
+    #[test]
+    fn release_more_than_used() {
+        let mut s = stats(0x4000, 0);
+        s.allocate(Bytes::new(0x1000)).unwrap();
+
+        assert_eq!(
+            s.release(Bytes::new(0x1001)),
+            Err(MemoryStatsError::ReleasedTooMuch {
+                used: Bytes::new(0x1000)
+            })
+        );
+        assert_eq!(s.used(), Bytes::new(0x1000));
+    }
+
+    #[test]
+    fn percent_used_zero() {
+        assert_eq!(stats(0x4000, 0x1000).percent_used(), 0);
+    }
+
+    #[test]
+    fn percent_used_hundred() {
+        let mut s = stats(0x4000, 0);
+        s.allocate(Bytes::new(0x4000)).unwrap();
+
+        assert_eq!(s.percent_used(), 100);
+    }
+
+    #[test]
+    fn percent_used_rounds_down() {
+        let mut s = stats(3, 0);
+        s.allocate(Bytes::new(2)).unwrap();
+
+        assert_eq!(s.percent_used(), 66);
+    }
+
+    #[test]
+    fn percent_used_empty_total() {
+        assert_eq!(stats(0, 0).percent_used(), 0);
+    }
+
+    #[test]
+    fn display() {
+        let mut s = stats(0x4000, 0x1000);
+        s.allocate(Bytes::new(0x2000)).unwrap();
+
+        assert_eq!(
+            format!("{s}"),
+            "8 KiB used, 4 KiB reserved, 4 KiB free of 16 KiB (50% used)"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use crate::Bytes;
+use crate::NumOfPages;
+use core::fmt;
+use x86_64::structures::paging::PageSize;
+
+/// The operations shared by [`Bytes`] and [`NumOfPages`], for the code which is generic over
+/// both, e.g. summing or clamping against a quota.
+///
+/// This trait is sealed: it is implemented only for [`Bytes`] and [`NumOfPages`], and cannot be
+/// implemented outside this crate. This lets methods be added to it without a breaking change,
+/// and lets the generic code rely on every implementor being a size which converts to bytes.
+///
+/// ```rust
+/// use os_units::{Bytes, MemoryUnit, NumOfPages};
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// fn total<U: MemoryUnit>(xs: &[U]) -> Option<U> {
+///     xs.iter().try_fold(U::zero(), |acc, &x| acc.checked_add(x))
+/// }
+///
+/// assert_eq!(
+///     total(&[Bytes::new(0x1000), Bytes::new(0x20)]),
+///     Some(Bytes::new(0x1020))
+/// );
+/// assert_eq!(total(&[Bytes::new(usize::MAX), Bytes::new(1)]), None);
+///
+/// let pages = total(&[NumOfPages::<Size4KiB>::new(2), NumOfPages::new(3)]).unwrap();
+///
+/// assert_eq!(pages, NumOfPages::new(5));
+/// # #[cfg(not(feature = "no-panic"))]
+/// assert_eq!(pages.to_bytes(), Bytes::new(0x5000));
+/// ```
+pub trait MemoryUnit: Copy + Ord + fmt::Debug + fmt::Display + private::Sealed {
+    /// Creates a new instance with the given value, like `Bytes::new` and `NumOfPages::new`.
+    fn from_usize(n: usize) -> Self;
+
+    /// Returns the value as `usize`.
+    fn as_usize(self) -> usize;
+
+    /// Returns an instance with the value 0.
+    #[must_use]
+    fn zero() -> Self {
+        Self::from_usize(0)
+    }
+
+    /// Returns `self + rhs`, or [`None`] on overflow.
+    #[must_use]
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.as_usize()
+            .checked_add(rhs.as_usize())
+            .map(Self::from_usize)
+    }
+
+    /// Returns `self - rhs`, or [`None`] if `rhs` is larger than `self`.
+    #[must_use]
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.as_usize()
+            .checked_sub(rhs.as_usize())
+            .map(Self::from_usize)
+    }
+
+    /// Returns the size in bytes. This is the identity for [`Bytes`], and
+    /// [`NumOfPages::as_bytes`] for [`NumOfPages`].
+    ///
+    /// This method is not available with the `no-panic` feature.
+    ///
+    /// # Panics
+    ///
+    /// Like [`NumOfPages::as_bytes`], this method panics on overflow if the overflow checks are
+    /// enabled.
+    #[cfg(not(feature = "no-panic"))]
+    fn to_bytes(&self) -> Bytes;
+}
+impl MemoryUnit for Bytes {
+    fn from_usize(n: usize) -> Self {
+        Self::new(n)
+    }
+
+    fn as_usize(self) -> usize {
+        Bytes::as_usize(self)
+    }
+
+    #[cfg(not(feature = "no-panic"))]
+    fn to_bytes(&self) -> Bytes {
+        *self
+    }
+}
+impl<T: PageSize> MemoryUnit for NumOfPages<T> {
+    fn from_usize(n: usize) -> Self {
+        Self::new(n)
+    }
+
+    fn as_usize(self) -> usize {
+        NumOfPages::as_usize(self)
+    }
+
+    #[cfg(not(feature = "no-panic"))]
+    fn to_bytes(&self) -> Bytes {
+        self.as_bytes()
+    }
+}
+
+mod private {
+    use crate::{Bytes, NumOfPages};
+    use x86_64::structures::paging::PageSize;
+
+    pub trait Sealed {}
+    impl Sealed for Bytes {}
+    impl<T: PageSize> Sealed for NumOfPages<T> {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MemoryUnit;
+    use crate::{Bytes, NumOfPages};
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    fn clamp_to_quota<U: MemoryUnit>(x: U, used: U, quota: U) -> U {
+        x.min(quota.checked_sub(used).unwrap_or_else(U::zero))
+    }
+
+    #[test]
+    fn generic_over_both() {
+        assert_eq!(
+            clamp_to_quota(Bytes::new(0x3000), Bytes::new(0x1000), Bytes::new(0x2000)),
+            Bytes::new(0x1000)
+        );
+        assert_eq!(
+            clamp_to_quota(
+                NumOfPages::<Size4KiB>::new(3),
+                NumOfPages::new(5),
+                NumOfPages::new(4)
+            ),
+            NumOfPages::zero()
+        );
+    }
+
+    #[test]
+    fn checked() {
+        assert_eq!(
+            MemoryUnit::checked_add(Bytes::new(1), Bytes::new(2)),
+            Some(Bytes::new(3))
+        );
+        assert_eq!(
+            MemoryUnit::checked_add(NumOfPages::<Size4KiB>::new(usize::MAX), NumOfPages::new(1)),
+            None
+        );
+        assert_eq!(
+            MemoryUnit::checked_sub(NumOfPages::<Size2MiB>::new(1), NumOfPages::new(2)),
+            None
+        );
+        assert_eq!(
+            MemoryUnit::checked_sub(NumOfPages::<Size2MiB>::new(2), NumOfPages::new(1)),
+            Some(NumOfPages::new(1))
+        );
+    }
+
+    #[test]
+    fn usize_round_trip() {
+        assert_eq!(<Bytes as MemoryUnit>::from_usize(7).as_usize(), 7);
+        assert_eq!(
+            <NumOfPages<Size4KiB> as MemoryUnit>::from_usize(7),
+            NumOfPages::new(7)
+        );
+        assert_eq!(
+            <NumOfPages<Size4KiB> as MemoryUnit>::zero(),
+            NumOfPages::zero()
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn to_bytes() {
+        assert_eq!(MemoryUnit::to_bytes(&Bytes::new(0x123)), Bytes::new(0x123));
+        assert_eq!(
+            MemoryUnit::to_bytes(&NumOfPages::<Size2MiB>::new(3)),
+            Bytes::new(0x60_0000)
+        );
+    }
+}
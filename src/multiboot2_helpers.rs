@@ -0,0 +1,195 @@
+//! Conversions from the memory map of the [`multiboot2`](https://docs.rs/multiboot2) crate.
+//!
+//! The base address and the length of a [`MemoryArea`] are `u64` even on 32-bit targets, so the
+//! conversions are fallible and fail with [`ConversionError::Overflow`] instead of truncating.
+//!
+//! ```rust
+//! use core::convert::TryFrom;
+//! use multiboot2::{MemoryArea, MemoryAreaType};
+//! use os_units::{multiboot2_helpers, Bytes, NumOfPages};
+//! use x86_64::structures::paging::Size4KiB;
+//!
+//! let areas = [
+//!     MemoryArea::new(0, 0x9_f000, MemoryAreaType::Available),
+//!     MemoryArea::new(0x9_f000, 0x6_1000, MemoryAreaType::Reserved),
+//! ];
+//!
+//! assert_eq!(Bytes::try_from(&areas[0]), Ok(Bytes::new(0x9_f000)));
+//! assert_eq!(NumOfPages::<Size4KiB>::try_from(&areas[0]), Ok(NumOfPages::new(0x9f)));
+//! assert_eq!(multiboot2_helpers::available_bytes(&areas), Some(Bytes::new(0x9_f000)));
+//! assert_eq!(multiboot2_helpers::reserved_bytes(&areas), Some(Bytes::new(0x6_1000)));
+//! ```
+
+use crate::Bytes;
+use crate::ConversionError;
+use crate::NumOfPages;
+use crate::PhysRegion;
+use crate::RegionError;
+use core::convert::TryFrom;
+use multiboot2::{MemoryArea, MemoryAreaType};
+use x86_64::structures::paging::{PageSize, Size4KiB};
+use x86_64::PhysAddr;
+
+/// Returns the sum of the sizes of the [`MemoryAreaType::Available`] areas.
+///
+/// This function returns [`None`] if the size of an area or the sum does not fit in `usize`.
+#[must_use]
+pub fn available_bytes(areas: &[MemoryArea]) -> Option<Bytes> {
+    sum_bytes(areas, |typ| typ == MemoryAreaType::Available)
+}
+
+/// Returns the sum of the sizes of the areas which are not [`MemoryAreaType::Available`].
+///
+/// This function returns [`None`] if the size of an area or the sum does not fit in `usize`.
+#[must_use]
+pub fn reserved_bytes(areas: &[MemoryArea]) -> Option<Bytes> {
+    sum_bytes(areas, |typ| typ != MemoryAreaType::Available)
+}
+
+fn sum_bytes(
+    areas: &[MemoryArea],
+    mut predicate: impl FnMut(MemoryAreaType) -> bool,
+) -> Option<Bytes> {
+    areas
+        .iter()
+        .filter(|a| predicate(a.typ()))
+        .try_fold(Bytes::zero(), |sum, a| {
+            sum.as_usize()
+                .checked_add(Bytes::try_from(a).ok()?.as_usize())
+                .map(Bytes::new)
+        })
+}
+
+/// The size of the area.
+impl TryFrom<&MemoryArea> for Bytes {
+    type Error = ConversionError;
+
+    fn try_from(a: &MemoryArea) -> Result<Self, Self::Error> {
+        Self::try_from(a.size())
+    }
+}
+/// The number of the whole frames in the area. A partial frame at either end is not counted.
+impl TryFrom<&MemoryArea> for NumOfPages<Size4KiB> {
+    type Error = ConversionError;
+
+    fn try_from(a: &MemoryArea) -> Result<Self, Self::Error> {
+        let end = a
+            .start_address()
+            .checked_add(a.size())
+            .ok_or(ConversionError::Overflow)?;
+        let start = a.start_address().div_ceil(Size4KiB::SIZE);
+        let end = end / Size4KiB::SIZE;
+
+        Ok(Self::new(usize::try_from(end.saturating_sub(start))?))
+    }
+}
+/// Fails with [`RegionError::InvalidAddress`] if the area does not fit in the physical
+/// address space.
+impl TryFrom<&MemoryArea> for PhysRegion {
+    type Error = RegionError;
+
+    fn try_from(a: &MemoryArea) -> Result<Self, Self::Error> {
+        let start =
+            PhysAddr::try_new(a.start_address()).map_err(|_| RegionError::InvalidAddress)?;
+
+        Self::try_from((start, Bytes::try_from(a)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{available_bytes, reserved_bytes};
+    use crate::{Bytes, ConversionError, NumOfPages, PhysRegion, RegionError};
+    use core::convert::TryFrom;
+    use multiboot2::{MemoryArea, MemoryAreaType, MemoryMapTag};
+    use x86_64::structures::paging::Size4KiB;
+    use x86_64::PhysAddr;
+
+    fn tag() -> Box<MemoryMapTag> {
+        MemoryMapTag::new(&[
+            MemoryArea::new(0, 0x9_fc00, MemoryAreaType::Available),
+            MemoryArea::new(0x9_fc00, 0x400, MemoryAreaType::Reserved),
+            MemoryArea::new(0xf_0000, 0x1_0000, MemoryAreaType::Reserved),
+            MemoryArea::new(0x10_0000, 0x7de_0000, MemoryAreaType::Available),
+            MemoryArea::new(0x7ee_0000, 0x2_0000, MemoryAreaType::AcpiAvailable),
+            MemoryArea::new(0xfffc_0000, 0x4_0000, MemoryAreaType::Reserved),
+        ])
+    }
+
+    #[test]
+    fn bytes() {
+        let a = MemoryArea::new(0x1000, 0x2800, MemoryAreaType::Available);
+
+        assert_eq!(Bytes::try_from(&a), Ok(Bytes::new(0x2800)));
+    }
+
+    #[test]
+    fn num_of_pages_ignores_partial_frames() {
+        let a = MemoryArea::new(0x9_fc00, 0x6400, MemoryAreaType::Available);
+
+        assert_eq!(NumOfPages::<Size4KiB>::try_from(&a), Ok(NumOfPages::new(6)));
+    }
+
+    #[test]
+    fn num_of_pages_within_one_frame() {
+        let a = MemoryArea::new(0x9_fc00, 0x400, MemoryAreaType::Reserved);
+
+        assert_eq!(NumOfPages::<Size4KiB>::try_from(&a), Ok(NumOfPages::new(0)));
+    }
+
+    #[test]
+    fn num_of_pages_end_overflow() {
+        let a = MemoryArea::new(u64::MAX, 2, MemoryAreaType::Reserved);
+
+        assert_eq!(
+            NumOfPages::<Size4KiB>::try_from(&a),
+            Err(ConversionError::Overflow)
+        );
+    }
+
+    #[test]
+    fn phys_region() {
+        let a = MemoryArea::new(0x10_0000, 0x7fe_0000, MemoryAreaType::Available);
+        let expected = PhysRegion::new(PhysAddr::new(0x10_0000), Bytes::new(0x7fe_0000)).unwrap();
+
+        assert_eq!(PhysRegion::try_from(&a), Ok(expected));
+    }
+
+    #[test]
+    fn phys_region_out_of_range() {
+        let a = MemoryArea::new(1 << 52, 0x1000, MemoryAreaType::Reserved);
+
+        assert_eq!(PhysRegion::try_from(&a), Err(RegionError::InvalidAddress));
+    }
+
+    #[test]
+    fn available_bytes_of_tag() {
+        let t = tag();
+
+        assert_eq!(
+            available_bytes(t.memory_areas()),
+            Some(Bytes::new(0x9_fc00 + 0x7de_0000))
+        );
+    }
+
+    #[test]
+    fn reserved_bytes_of_tag() {
+        let t = tag();
+
+        assert_eq!(
+            reserved_bytes(t.memory_areas()),
+            Some(Bytes::new(0x400 + 0x1_0000 + 0x2_0000 + 0x4_0000))
+        );
+    }
+
+    #[test]
+    fn sum_overflow() {
+        let areas = [
+            MemoryArea::new(0, u64::MAX, MemoryAreaType::Available),
+            MemoryArea::new(0, 1, MemoryAreaType::Available),
+        ];
+
+        assert_eq!(available_bytes(&areas), None);
+        assert_eq!(reserved_bytes(&areas), Some(Bytes::zero()));
+    }
+}
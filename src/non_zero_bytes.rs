@@ -0,0 +1,73 @@
+use crate::Bytes;
+use core::fmt;
+use core::num::NonZeroUsize;
+
+/// A byte size which is known not to be zero.
+///
+/// Like [`NonZeroUsize`], `Option<NonZeroBytes>` has the same size as `usize`.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonZeroBytes(NonZeroUsize);
+impl NonZeroBytes {
+    /// Creates a new instance if `bytes` is not zero.
+    #[must_use]
+    pub const fn new(bytes: Bytes) -> Option<Self> {
+        match NonZeroUsize::new(bytes.as_usize()) {
+            Some(n) => Some(Self(n)),
+            None => None,
+        }
+    }
+
+    /// Returns the value as [`Bytes`].
+    #[must_use]
+    pub const fn get(self) -> Bytes {
+        Bytes::new(self.0.get())
+    }
+}
+impl From<NonZeroBytes> for Bytes {
+    fn from(b: NonZeroBytes) -> Self {
+        b.get()
+    }
+}
+impl fmt::Display for NonZeroBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NonZeroBytes;
+    use crate::Bytes;
+
+    #[test]
+    fn new_zero() {
+        assert_eq!(NonZeroBytes::new(Bytes::zero()), None);
+    }
+
+    #[test]
+    fn new_get() {
+        let b = NonZeroBytes::new(Bytes::new(3)).unwrap();
+
+        assert_eq!(b.get(), Bytes::new(3));
+    }
+
+    #[test]
+    fn option_size() {
+        assert_eq!(size_of::<Option<NonZeroBytes>>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn into_bytes() {
+        let b = NonZeroBytes::new(Bytes::new(3)).unwrap();
+
+        assert_eq!(Bytes::from(b), Bytes::new(3));
+    }
+
+    #[test]
+    fn display() {
+        let b = NonZeroBytes::new(Bytes::new(3)).unwrap();
+
+        assert_eq!(format!("{b}"), "3 bytes");
+    }
+}
@@ -1,133 +1,893 @@
+use crate::paging;
+use crate::paging::PageSizeExt;
+use crate::pow2;
 use crate::Bytes;
+use crate::ConversionError;
+use crate::NumOfPagesRange;
+use core::alloc::Layout;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
 use core::fmt;
+use core::hash::{Hash, Hasher};
 use core::marker::PhantomData;
-use core::ops::Add;
-use core::ops::AddAssign;
-use core::ops::Div;
-use core::ops::DivAssign;
-use core::ops::Mul;
-use core::ops::MulAssign;
-use core::ops::Sub;
-use core::ops::SubAssign;
+use core::num::NonZeroUsize;
+use core::panic::{RefUnwindSafe, UnwindSafe};
+use x86_64::structures::paging::page::PageRange;
+use x86_64::structures::paging::Page;
 use x86_64::structures::paging::PageSize;
+use x86_64::structures::paging::{Size1GiB, Size2MiB, Size4KiB};
+use x86_64::VirtAddr;
+#[cfg(not(feature = "no-panic"))]
+use {
+    crate::overflow,
+    core::iter::Sum,
+    core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+};
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::KnownLayout,
+        zerocopy::Immutable
+    )
+)]
 /// A struct representing the number of pages.
+///
+/// This type is `repr(transparent)` over `usize`: it has the same size, alignment, and ABI as
+/// `usize` regardless of `T`. It can be passed across an FFI boundary directly.
+/// [`NumOfPages::into_raw`] and [`NumOfPages::from_raw`] are provided for FFI shims which take
+/// `usize`.
+///
+/// `NumOfPages<T>` is `Send`, `Sync`, `Unpin`, `UnwindSafe`, and `RefUnwindSafe` regardless of
+/// `T`, and is `'static` whenever `T` is, so it can be stored in a `static` shared between cores.
+/// It is covariant in `T`. These properties are part of the public API, and removing any of them
+/// is a breaking change.
 pub struct NumOfPages<T: PageSize> {
     num_of_pages: usize,
     _marker: PhantomData<fn() -> T>,
 }
+const _: () = assert!(size_of::<NumOfPages<Size4KiB>>() == size_of::<usize>());
+const _: () = assert!(size_of::<NumOfPages<Size2MiB>>() == size_of::<usize>());
+const _: () = assert!(size_of::<NumOfPages<Size1GiB>>() == size_of::<usize>());
+const _: () = assert!(align_of::<NumOfPages<Size4KiB>>() == align_of::<usize>());
+// Checked for every `T` as the bodies of generic functions are type-checked without knowing `T`.
+const fn assert_auto_traits<T: Send + Sync + Unpin + UnwindSafe + RefUnwindSafe>() {}
+const fn assert_num_of_pages_auto_traits<T: PageSize>() {
+    assert_auto_traits::<NumOfPages<T>>();
+}
+const _: () = assert_num_of_pages_auto_traits::<Size4KiB>();
 impl<T: PageSize> NumOfPages<T> {
     /// Creates a new instance with given value.
     #[must_use]
-    pub fn new(num_of_pages: usize) -> Self {
+    pub const fn new(num_of_pages: usize) -> Self {
+        Self {
+            num_of_pages,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new instance from a raw value passed across an FFI boundary. This is the same as
+    /// [`NumOfPages::new`].
+    #[must_use]
+    pub const fn from_raw(num_of_pages: usize) -> Self {
         Self {
             num_of_pages,
             _marker: PhantomData,
         }
     }
 
+    /// Returns the raw value to be passed across an FFI boundary. This is the same as
+    /// [`NumOfPages::as_usize`].
+    #[must_use]
+    pub const fn into_raw(self) -> usize {
+        self.num_of_pages
+    }
+
     /// Equivalent to `NumOfPages::new(0)`.
     #[must_use]
-    pub fn zero() -> Self {
+    pub const fn zero() -> Self {
         Self::new(0)
     }
 
     /// Returns the value.
     #[must_use]
-    pub fn as_usize(self) -> usize {
+    pub const fn as_usize(self) -> usize {
         self.num_of_pages
     }
 
+    /// `self + rhs` which can be called in `const` contexts, where the [`Add`] implementation
+    /// cannot be used until const traits are stabilized.
+    ///
+    /// Like [`Add`], this panics on overflow if the overflow checks are enabled, and wraps around
+    /// otherwise. In a `const` item, an overflow is a compile error.
+    ///
+    /// ```rust
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// const A: NumOfPages<Size4KiB> = NumOfPages::new(3);
+    /// const B: NumOfPages<Size4KiB> = NumOfPages::new(4);
+    /// const TOTAL: NumOfPages<Size4KiB> = A.const_add(B);
+    ///
+    /// assert_eq!(TOTAL, A + B);
+    /// ```
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    pub const fn const_add(self, rhs: Self) -> Self {
+        Self::new(self.num_of_pages + rhs.num_of_pages)
+    }
+
+    /// `self - rhs` which can be called in `const` contexts. See [`NumOfPages::const_add`].
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    pub const fn const_sub(self, rhs: Self) -> Self {
+        Self::new(self.num_of_pages - rhs.num_of_pages)
+    }
+
+    /// `self * rhs` which can be called in `const` contexts. See [`NumOfPages::const_add`].
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    pub const fn const_mul(self, rhs: usize) -> Self {
+        Self::new(self.num_of_pages * rhs)
+    }
+
+    /// `self / rhs` which can be called in `const` contexts. See [`NumOfPages::const_add`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `rhs` is zero.
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    pub const fn const_div(self, rhs: usize) -> Self {
+        Self::new(self.num_of_pages / rhs)
+    }
+
+    /// Returns `self + rhs`, or [`None`] on overflow.
+    ///
+    /// ```rust
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// let n = NumOfPages::<Size4KiB>::new(3);
+    ///
+    /// assert_eq!(n.checked_add(NumOfPages::new(4)), Some(NumOfPages::new(7)));
+    /// assert_eq!(n.checked_add(NumOfPages::new(usize::MAX)), None);
+    /// ```
+    #[must_use]
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.num_of_pages.checked_add(rhs.num_of_pages) {
+            Some(n) => Some(Self::new(n)),
+            None => None,
+        }
+    }
+
+    /// Returns `self - rhs`, or [`None`] if `rhs` is larger than `self`.
+    #[must_use]
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.num_of_pages.checked_sub(rhs.num_of_pages) {
+            Some(n) => Some(Self::new(n)),
+            None => None,
+        }
+    }
+
+    /// Returns `self * rhs`, or [`None`] on overflow.
+    #[must_use]
+    pub const fn checked_mul(self, rhs: usize) -> Option<Self> {
+        match self.num_of_pages.checked_mul(rhs) {
+            Some(n) => Some(Self::new(n)),
+            None => None,
+        }
+    }
+
+    /// Returns `self / rhs`, or [`None`] if `rhs` is zero.
+    #[must_use]
+    pub const fn checked_div(self, rhs: usize) -> Option<Self> {
+        match self.num_of_pages.checked_div(rhs) {
+            Some(n) => Some(Self::new(n)),
+            None => None,
+        }
+    }
+
+    /// Returns `self + rhs` for a number of pages held as `usize`, or [`None`] on overflow.
+    ///
+    /// This method is not available with the `strict-operands` feature.
+    #[cfg(not(feature = "strict-operands"))]
+    #[must_use]
+    pub const fn checked_add_usize(self, rhs: usize) -> Option<Self> {
+        self.checked_add(Self::new(rhs))
+    }
+
+    /// Returns `self - rhs` for a number of pages held as `usize`, or [`None`] if `rhs` is larger
+    /// than `self`.
+    ///
+    /// This method is not available with the `strict-operands` feature.
+    #[cfg(not(feature = "strict-operands"))]
+    #[must_use]
+    pub const fn checked_sub_usize(self, rhs: usize) -> Option<Self> {
+        self.checked_sub(Self::new(rhs))
+    }
+
+    /// Returns `self + rhs`, saturating at `usize::MAX`.
+    #[must_use]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self::new(self.num_of_pages.saturating_add(rhs.num_of_pages))
+    }
+
+    /// Returns `self - rhs`, saturating at zero.
+    #[must_use]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self::new(self.num_of_pages.saturating_sub(rhs.num_of_pages))
+    }
+
+    /// Returns `self * rhs`, saturating at `usize::MAX`.
+    #[must_use]
+    pub const fn saturating_mul(self, rhs: usize) -> Self {
+        Self::new(self.num_of_pages.saturating_mul(rhs))
+    }
+
+    /// Returns `self + rhs`, panicking on overflow regardless of the overflow checks and the
+    /// features.
+    ///
+    /// This method is not available with the `no-panic` feature.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the sum does not fit in `usize`, with both operands and the page
+    /// size in the message.
+    ///
+    /// ```rust,should_panic
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// // Panics with "NumOfPages::strict_sub overflowed: 1 pages of 4KiB - 2 pages of 4KiB".
+    /// let _ = NumOfPages::<Size4KiB>::new(1).strict_sub(NumOfPages::new(2));
+    /// ```
+    #[cfg(not(feature = "no-panic"))]
+    #[track_caller]
+    #[must_use]
+    pub fn strict_add(self, rhs: Self) -> Self {
+        match self.checked_add(rhs) {
+            Some(n) => n,
+            None => overflow::strict_failed(
+                "NumOfPages::strict_add",
+                "overflowed",
+                format_args!("{} pages of {}", self.num_of_pages, T::SIZE_AS_DEBUG_STR),
+                '+',
+                format_args!("{} pages of {}", rhs.num_of_pages, T::SIZE_AS_DEBUG_STR),
+            ),
+        }
+    }
+
+    /// Returns `self - rhs`, panicking if `rhs` is larger than `self`. See
+    /// [`NumOfPages::strict_add`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `rhs` is larger than `self`.
+    #[cfg(not(feature = "no-panic"))]
+    #[track_caller]
+    #[must_use]
+    pub fn strict_sub(self, rhs: Self) -> Self {
+        match self.checked_sub(rhs) {
+            Some(n) => n,
+            None => overflow::strict_failed(
+                "NumOfPages::strict_sub",
+                "overflowed",
+                format_args!("{} pages of {}", self.num_of_pages, T::SIZE_AS_DEBUG_STR),
+                '-',
+                format_args!("{} pages of {}", rhs.num_of_pages, T::SIZE_AS_DEBUG_STR),
+            ),
+        }
+    }
+
+    /// Returns `self * rhs`, panicking on overflow. See [`NumOfPages::strict_add`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the product does not fit in `usize`.
+    #[cfg(not(feature = "no-panic"))]
+    #[track_caller]
+    #[must_use]
+    pub fn strict_mul(self, rhs: usize) -> Self {
+        match self.checked_mul(rhs) {
+            Some(n) => n,
+            None => overflow::strict_failed(
+                "NumOfPages::strict_mul",
+                "overflowed",
+                format_args!("{} pages of {}", self.num_of_pages, T::SIZE_AS_DEBUG_STR),
+                '*',
+                format_args!("{rhs}"),
+            ),
+        }
+    }
+
+    /// Returns `self / rhs`, panicking if `rhs` is zero. See [`NumOfPages::strict_add`].
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `rhs` is zero.
+    #[cfg(not(feature = "no-panic"))]
+    #[track_caller]
+    #[must_use]
+    pub fn strict_div(self, rhs: usize) -> Self {
+        match self.checked_div(rhs) {
+            Some(n) => n,
+            None => overflow::strict_failed(
+                "NumOfPages::strict_div",
+                "divided by zero",
+                format_args!("{} pages of {}", self.num_of_pages, T::SIZE_AS_DEBUG_STR),
+                '/',
+                format_args!("{rhs}"),
+            ),
+        }
+    }
+
+    /// Returns `self + rhs` and whether it overflowed, wrapping around on overflow.
+    ///
+    /// ```rust
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// let max = NumOfPages::<Size4KiB>::new(usize::MAX);
+    ///
+    /// assert_eq!(max.overflowing_add(NumOfPages::new(2)), (NumOfPages::new(1), true));
+    /// ```
+    #[must_use]
+    pub const fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let (n, overflowed) = self.num_of_pages.overflowing_add(rhs.num_of_pages);
+
+        (Self::new(n), overflowed)
+    }
+
+    /// Returns `self - rhs` and whether it overflowed, wrapping around on overflow.
+    #[must_use]
+    pub const fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let (n, overflowed) = self.num_of_pages.overflowing_sub(rhs.num_of_pages);
+
+        (Self::new(n), overflowed)
+    }
+
+    /// Returns `self * rhs` and whether it overflowed, wrapping around on overflow.
+    #[must_use]
+    pub const fn overflowing_mul(self, rhs: usize) -> (Self, bool) {
+        let (n, overflowed) = self.num_of_pages.overflowing_mul(rhs);
+
+        (Self::new(n), overflowed)
+    }
+
+    /// Returns `self + rhs`, wrapping around at `usize::MAX`, e.g. for the indices of a circular
+    /// queue.
+    #[must_use]
+    pub const fn wrapping_add(self, rhs: Self) -> Self {
+        Self::new(self.num_of_pages.wrapping_add(rhs.num_of_pages))
+    }
+
+    /// Returns `self - rhs`, wrapping around at zero.
+    #[must_use]
+    pub const fn wrapping_sub(self, rhs: Self) -> Self {
+        Self::new(self.num_of_pages.wrapping_sub(rhs.num_of_pages))
+    }
+
+    /// Returns `self * rhs`, wrapping around at `usize::MAX`.
+    #[must_use]
+    pub const fn wrapping_mul(self, rhs: usize) -> Self {
+        Self::new(self.num_of_pages.wrapping_mul(rhs))
+    }
+
+    /// Returns `self / rhs`, or [`None`] if `rhs` does not divide `self` evenly or is zero.
+    ///
+    /// ```rust
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// let n = NumOfPages::<Size4KiB>::new(12);
+    ///
+    /// assert_eq!(n.exact_div(4), Some(NumOfPages::new(3)));
+    /// assert_eq!(n.exact_div(5), None);
+    /// assert_eq!(n.exact_div(0), None);
+    /// ```
+    #[must_use]
+    pub const fn exact_div(self, rhs: usize) -> Option<Self> {
+        match self.num_of_pages.checked_rem(rhs) {
+            Some(0) => Some(Self::new(self.num_of_pages / rhs)),
+            _ => None,
+        }
+    }
+
+    /// `self == other` which can be called in `const` contexts, where the [`PartialEq`]
+    /// implementation cannot be used.
+    #[must_use]
+    pub const fn const_eq(self, other: Self) -> bool {
+        self.num_of_pages == other.num_of_pages
+    }
+
+    /// `self.cmp(&other)` which can be called in `const` contexts, e.g. in a static assertion,
+    /// where the [`Ord`] implementation cannot be used.
+    ///
+    /// ```rust
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// const STACK_SIZE: NumOfPages<Size4KiB> = NumOfPages::new(0x8000);
+    /// const MIN: NumOfPages<Size4KiB> = NumOfPages::new(0x4000);
+    ///
+    /// const _: () = assert!(STACK_SIZE.const_cmp(MIN).is_ge());
+    /// ```
+    #[must_use]
+    pub const fn const_cmp(self, other: Self) -> Ordering {
+        if self.num_of_pages < other.num_of_pages {
+            Ordering::Less
+        } else if self.num_of_pages == other.num_of_pages {
+            Ordering::Equal
+        } else {
+            Ordering::Greater
+        }
+    }
+
+    /// Returns the memory representation of the value as a byte array in little-endian byte order.
+    #[must_use]
+    pub const fn to_le_bytes(self) -> [u8; size_of::<usize>()] {
+        self.num_of_pages.to_le_bytes()
+    }
+
+    /// Returns the memory representation of the value as a byte array in big-endian byte order.
+    #[must_use]
+    pub const fn to_be_bytes(self) -> [u8; size_of::<usize>()] {
+        self.num_of_pages.to_be_bytes()
+    }
+
+    /// Returns the memory representation of the value as a byte array in native byte order.
+    #[must_use]
+    pub const fn to_ne_bytes(self) -> [u8; size_of::<usize>()] {
+        self.num_of_pages.to_ne_bytes()
+    }
+
+    /// Creates a new instance from its representation as a byte array in little-endian byte order.
+    #[must_use]
+    pub const fn from_le_bytes(bytes: [u8; size_of::<usize>()]) -> Self {
+        Self {
+            num_of_pages: usize::from_le_bytes(bytes),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new instance from its representation as a byte array in big-endian byte order.
+    #[must_use]
+    pub const fn from_be_bytes(bytes: [u8; size_of::<usize>()]) -> Self {
+        Self {
+            num_of_pages: usize::from_be_bytes(bytes),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new instance from its representation as a byte array in native byte order.
+    #[must_use]
+    pub const fn from_ne_bytes(bytes: [u8; size_of::<usize>()]) -> Self {
+        Self {
+            num_of_pages: usize::from_ne_bytes(bytes),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the value as a little-endian `u64`, which has the same width regardless of the
+    /// target.
+    #[must_use]
+    pub const fn to_u64_le_bytes(self) -> [u8; 8] {
+        (self.num_of_pages as u64).to_le_bytes()
+    }
+
+    /// Creates a new instance from a little-endian `u64`.
+    ///
+    /// # Errors
+    ///
+    /// This method returns [`ConversionError::Overflow`] if the value does not fit in `usize`.
+    pub const fn from_u64_le_bytes(bytes: [u8; 8]) -> Result<Self, ConversionError> {
+        let v = u64::from_le_bytes(bytes);
+
+        if v > usize::MAX as u64 {
+            Err(ConversionError::Overflow)
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            Ok(Self {
+                num_of_pages: v as usize,
+                _marker: PhantomData,
+            })
+        }
+    }
+
     /// Converts the number of physical pages to bytes.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the bytes do not fit in `usize` and the overflow checks or the
+    /// `overflow-checks` feature are enabled. If `T::SIZE` does not fit in `usize`, e.g.
+    /// [`Size2MiB`] on a target with 16-bit `usize`, it panics for any number of pages but zero
+    /// regardless of them, as the page size cannot be a `usize` operand. Use
+    /// [`NumOfPages::checked_as_bytes`] to get [`None`] instead.
+    #[cfg(not(feature = "no-panic"))]
     #[must_use]
     pub fn as_bytes(self) -> Bytes {
+        match paging::page_size::<T>() {
+            Some(page_size) => Bytes::new(overflow::mul(
+                "NumOfPages to Bytes",
+                self.num_of_pages,
+                page_size,
+            )),
+            None => self
+                .checked_as_bytes()
+                .expect("NumOfPages to Bytes overflowed: the page size does not fit in `usize`"),
+        }
+    }
+
+    /// Returns the largest number of pages whose bytes fit in `usize`, i.e. the largest value for
+    /// which [`NumOfPages::checked_as_bytes`] returns [`Some`].
+    ///
+    /// This depends on the pointer width of the target. For example, it is `0xf_ffff` pages of
+    /// [`Size4KiB`] on a 32-bit target, so a count from a 64-bit firmware table can be capped with
+    /// it before being converted.
+    ///
+    /// ```rust
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// const MAX: NumOfPages<Size4KiB> = NumOfPages::max_convertible();
+    ///
+    /// assert!(MAX.checked_as_bytes().is_some());
+    /// assert_eq!(NumOfPages::<Size4KiB>::new(MAX.as_usize() + 1).checked_as_bytes(), None);
+    /// ```
+    #[must_use]
+    pub const fn max_convertible() -> Self {
+        // Zero if the page size does not fit in `usize`.
         #[allow(clippy::cast_possible_truncation)]
-        Bytes::new(self.num_of_pages * T::SIZE as usize)
+        Self::new((usize::MAX as u64 >> T::SHIFT) as usize)
+    }
+
+    /// Converts the number of physical pages to bytes, or returns [`None`] if the bytes do not
+    /// fit in `usize`.
+    ///
+    /// ```rust
+    /// use os_units::{Bytes, NumOfPages};
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// assert_eq!(
+    ///     NumOfPages::<Size4KiB>::new(3).checked_as_bytes(),
+    ///     Some(Bytes::new(0x3000))
+    /// );
+    /// assert_eq!(NumOfPages::<Size4KiB>::new(usize::MAX).checked_as_bytes(), None);
+    /// ```
+    #[must_use]
+    pub fn checked_as_bytes(self) -> Option<Bytes> {
+        (self.num_of_pages as u64)
+            .checked_mul(T::SIZE)
+            .and_then(|b| usize::try_from(b).ok())
+            .map(Bytes::new)
+    }
+
+    /// Like [`NumOfPages::as_bytes`], but available with the `no-panic` feature for the internal
+    /// uses, where the bytes always fit in `usize`.
+    pub(crate) fn to_bytes(self) -> Bytes {
+        // In `u64` so that a page size which does not fit in `usize` is not truncated, and
+        // `wrapping_mul` so that no overflow check is emitted under `no-panic`. The callers only
+        // pass the pages below a size in bytes, e.g. the indices of the pages covering `Bytes` in
+        // `Bytes::iter_page_offsets`, whose bytes are smaller than that size and so fit in `usize`.
+        #[allow(clippy::cast_possible_truncation)]
+        Bytes::new((self.num_of_pages as u64).wrapping_mul(T::SIZE) as usize)
+    }
+
+    /// Returns the number of pages the buffer `addr..addr + len` touches, taking the offset of
+    /// `addr` in its page into account.
+    ///
+    /// ```rust
+    /// use os_units::{Bytes, NumOfPages};
+    /// use x86_64::structures::paging::Size4KiB;
+    /// use x86_64::VirtAddr;
+    ///
+    /// let n = NumOfPages::<Size4KiB>::spanning(VirtAddr::new(0x1ff0), Bytes::new(0x20));
+    ///
+    /// assert_eq!(n, NumOfPages::new(2));
+    /// ```
+    #[must_use]
+    pub fn spanning(addr: VirtAddr, len: Bytes) -> Self {
+        // In `u64` so that a page size which does not fit in `usize` is not truncated. The result
+        // is at most `len / T::SIZE + 2`, so it fits in `usize`.
+        let offset = addr.as_u64() & T::MASK;
+        let len = len.as_usize() as u64;
+
+        if len == 0 {
+            Self::zero()
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            Self::new(((len >> T::SHIFT) + (offset + (len & T::MASK)).div_ceil(T::SIZE)) as usize)
+        }
+    }
+
+    /// Returns the range of the pages the buffer `addr..addr + len` touches.
+    ///
+    /// This method returns [`None`] if the end of the range is not a valid address. See
+    /// [`page_range`](crate::page_range) for details.
+    #[must_use]
+    pub fn spanning_range(addr: VirtAddr, len: Bytes) -> Option<PageRange<T>> {
+        crate::page_range(Page::containing_address(addr), Self::spanning(addr, len))
+    }
+
+    /// Returns an iterator over the indices `0, 1, ..., self - 1` of the pages.
+    ///
+    /// ```rust
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// let indices: Vec<_> = NumOfPages::<Size4KiB>::new(2).indices().collect();
+    ///
+    /// assert_eq!(indices, [NumOfPages::new(0), NumOfPages::new(1)]);
+    /// ```
+    #[must_use]
+    pub fn indices(self) -> impl ExactSizeIterator<Item = Self> + DoubleEndedIterator {
+        NumOfPagesRange::new(Self::zero(), self)
+    }
+
+    /// Returns the buddy allocator order of the pages, i.e. the smallest `k` such that `2^k` pages
+    /// hold `self` pages.
+    ///
+    /// Both zero and one page have order 0.
+    #[must_use]
+    pub fn order(self) -> u32 {
+        self.num_of_pages
+            .checked_next_power_of_two()
+            .map_or(usize::BITS, usize::trailing_zeros)
+    }
+
+    /// Returns an iterator which splits the pages into runs of power-of-two pages in descending
+    /// order, e.g. 13 pages into 8, 4, and 1 pages. The runs sum to `self`.
+    ///
+    /// ```rust
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// let v: Vec<_> = NumOfPages::<Size4KiB>::new(13).split_power_of_two().collect();
+    ///
+    /// assert_eq!(v, [NumOfPages::new(8), NumOfPages::new(4), NumOfPages::new(1)]);
+    /// ```
+    pub fn split_power_of_two(self) -> impl Iterator<Item = Self> {
+        self.split_power_of_two_max_order(usize::BITS - 1)
+    }
+
+    /// Like [`NumOfPages::split_power_of_two`], but no run is larger than `2^max_order` pages.
+    /// The runs larger than that are split into multiple runs of `2^max_order` pages.
+    ///
+    /// ```rust
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// let v: Vec<_> = NumOfPages::<Size4KiB>::new(13)
+    ///     .split_power_of_two_max_order(2)
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     v,
+    ///     [NumOfPages::new(4), NumOfPages::new(4), NumOfPages::new(4), NumOfPages::new(1)]
+    /// );
+    /// ```
+    pub fn split_power_of_two_max_order(self, max_order: u32) -> impl Iterator<Item = Self> {
+        pow2::components(self.num_of_pages, 1 << max_order.min(usize::BITS - 1)).map(Self::new)
+    }
+
+    /// Returns an iterator which splits the pages into `n` counts which differ by at most one.
+    /// The remainder of `self / n` is distributed one page each to the first counts.
+    ///
+    /// ```rust
+    /// use core::num::NonZeroUsize;
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// let n = NonZeroUsize::new(3).unwrap();
+    /// let counts: Vec<_> = NumOfPages::<Size4KiB>::new(5).split_evenly(n).collect();
+    ///
+    /// assert_eq!(counts, [NumOfPages::new(2), NumOfPages::new(2), NumOfPages::new(1)]);
+    /// ```
+    #[must_use]
+    pub fn split_evenly(
+        self,
+        n: NonZeroUsize,
+    ) -> impl ExactSizeIterator<Item = Self> + DoubleEndedIterator {
+        let n = n.get();
+        let (quotient, remainder) = (self.num_of_pages / n, self.num_of_pages % n);
+
+        (0..n).map(move |i| Self::new(quotient + usize::from(i < remainder)))
+    }
+
+    /// Returns the layout of `self` pages aligned to the page size.
+    ///
+    /// This method returns [`None`] if the size does not fit in `isize`.
+    ///
+    /// ```rust
+    /// use os_units::NumOfPages;
+    /// use x86_64::structures::paging::Size2MiB;
+    ///
+    /// let layout = NumOfPages::<Size2MiB>::new(3).as_layout().unwrap();
+    ///
+    /// assert_eq!(layout.size(), 0x60_0000);
+    /// assert_eq!(layout.align(), 0x20_0000);
+    /// ```
+    #[must_use]
+    pub fn as_layout(self) -> Option<Layout> {
+        let page_size = paging::page_size::<T>()?;
+        let size = self.num_of_pages.checked_mul(page_size)?;
+
+        Layout::from_size_align(size, page_size).ok()
+    }
+
+    /// Returns the size of a bitmap with one bit per page, rounded up to whole `u64` words as
+    /// [`PageBitmap`](crate::PageBitmap) requires.
+    ///
+    /// ```rust
+    /// use os_units::{Bytes, NumOfPages};
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// assert_eq!(NumOfPages::<Size4KiB>::new(65).bitmap_bytes(), Bytes::new(16));
+    /// ```
+    #[must_use]
+    pub fn bitmap_bytes(self) -> Bytes {
+        Bytes::new(self.num_of_pages.div_ceil(u64::BITS as usize) * size_of::<u64>())
     }
 }
-impl<T: PageSize> Add for NumOfPages<T> {
-    type Output = NumOfPages<T>;
+#[cfg(not(feature = "no-panic"))]
+const_op_impl! {
+    impl[T: PageSize] Add for NumOfPages<T> {
+        type Output = NumOfPages<T>;
 
-    fn add(self, rhs: NumOfPages<T>) -> Self {
-        Self::new(self.num_of_pages + rhs.num_of_pages)
+        fn add(self, rhs: NumOfPages<T>) -> Self {
+            Self::new(overflow::add("NumOfPages", self.num_of_pages, rhs.num_of_pages))
+        }
     }
 }
-impl<T: PageSize> Add<usize> for NumOfPages<T> {
-    type Output = NumOfPages<T>;
+#[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
+const_op_impl! {
+    impl[T: PageSize] Add<usize> for NumOfPages<T> {
+        type Output = NumOfPages<T>;
 
-    fn add(self, rhs: usize) -> Self::Output {
-        Self::new(self.num_of_pages + rhs)
+        fn add(self, rhs: usize) -> Self::Output {
+            Self::new(overflow::add("NumOfPages", self.num_of_pages, rhs))
+        }
     }
 }
+#[cfg(not(feature = "no-panic"))]
 impl<T: PageSize> AddAssign for NumOfPages<T> {
     fn add_assign(&mut self, rhs: NumOfPages<T>) {
-        self.num_of_pages += rhs.num_of_pages;
+        self.num_of_pages = overflow::add("NumOfPages", self.num_of_pages, rhs.num_of_pages);
     }
 }
+#[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
 impl<T: PageSize> AddAssign<usize> for NumOfPages<T> {
     fn add_assign(&mut self, rhs: usize) {
-        self.num_of_pages += rhs;
+        self.num_of_pages = overflow::add("NumOfPages", self.num_of_pages, rhs);
     }
 }
-impl<T: PageSize> Sub for NumOfPages<T> {
-    type Output = NumOfPages<T>;
+#[cfg(not(feature = "no-panic"))]
+const_op_impl! {
+    impl[T: PageSize] Sub for NumOfPages<T> {
+        type Output = NumOfPages<T>;
 
-    fn sub(self, rhs: NumOfPages<T>) -> Self {
-        Self::new(self.num_of_pages - rhs.num_of_pages)
+        fn sub(self, rhs: NumOfPages<T>) -> Self {
+            Self::new(overflow::sub("NumOfPages", self.num_of_pages, rhs.num_of_pages))
+        }
     }
 }
-impl<T: PageSize> Sub<usize> for NumOfPages<T> {
-    type Output = NumOfPages<T>;
+#[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
+const_op_impl! {
+    impl[T: PageSize] Sub<usize> for NumOfPages<T> {
+        type Output = NumOfPages<T>;
 
-    fn sub(self, rhs: usize) -> Self::Output {
-        Self::new(self.num_of_pages - rhs)
+        fn sub(self, rhs: usize) -> Self::Output {
+            Self::new(overflow::sub("NumOfPages", self.num_of_pages, rhs))
+        }
     }
 }
+#[cfg(not(feature = "no-panic"))]
 impl<T: PageSize> SubAssign for NumOfPages<T> {
     fn sub_assign(&mut self, rhs: NumOfPages<T>) {
-        self.num_of_pages -= rhs.num_of_pages;
+        self.num_of_pages = overflow::sub("NumOfPages", self.num_of_pages, rhs.num_of_pages);
     }
 }
+#[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
 impl<T: PageSize> SubAssign<usize> for NumOfPages<T> {
     fn sub_assign(&mut self, rhs: usize) {
         *self -= Self::new(rhs);
     }
 }
-impl<T: PageSize> Mul<usize> for NumOfPages<T> {
-    type Output = NumOfPages<T>;
-    fn mul(self, rhs: usize) -> Self::Output {
-        Self {
-            num_of_pages: self.num_of_pages * rhs,
-            ..self
+#[cfg(not(feature = "no-panic"))]
+const_op_impl! {
+    impl[T: PageSize] Mul<usize> for NumOfPages<T> {
+        type Output = NumOfPages<T>;
+        fn mul(self, rhs: usize) -> Self::Output {
+            Self {
+                num_of_pages: overflow::mul("NumOfPages", self.num_of_pages, rhs),
+                ..self
+            }
         }
     }
 }
+#[cfg(not(feature = "no-panic"))]
 impl<T: PageSize> MulAssign<usize> for NumOfPages<T> {
     fn mul_assign(&mut self, rhs: usize) {
         *self = *self * rhs;
     }
 }
-impl<T: PageSize> Div<usize> for NumOfPages<T> {
-    type Output = NumOfPages<T>;
+#[cfg(not(feature = "no-panic"))]
+const_op_impl! {
+    impl[T: PageSize] Div<usize> for NumOfPages<T> {
+        type Output = NumOfPages<T>;
 
-    fn div(self, rhs: usize) -> Self::Output {
-        Self {
-            num_of_pages: self.num_of_pages / rhs,
-            ..self
+        fn div(self, rhs: usize) -> Self::Output {
+            Self {
+                num_of_pages: self.num_of_pages / rhs,
+                ..self
+            }
         }
     }
 }
+#[cfg(not(feature = "no-panic"))]
 impl<T: PageSize> DivAssign<usize> for NumOfPages<T> {
     fn div_assign(&mut self, rhs: usize) {
         *self = *self / rhs;
     }
 }
+#[cfg(not(feature = "no-panic"))]
+/// Sums the numbers of pages with the `+` operator. Like [`Add`], this panics on overflow if the
+/// overflow checks are enabled, and wraps around otherwise.
+impl<T: PageSize> Sum for NumOfPages<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+/// Sums the numbers of pages with the `+` operator. Like [`Add`], this panics on overflow if the
+/// overflow checks are enabled, and wraps around otherwise.
+impl<'a, T: PageSize> Sum<&'a NumOfPages<T>> for NumOfPages<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
 impl<T: PageSize> From<usize> for NumOfPages<T> {
     fn from(n: usize) -> Self {
         Self::new(n)
     }
 }
+/// Compares with a number of pages held as `usize`, e.g. `pages == 0`.
+#[cfg(not(feature = "strict-operands"))]
+impl<T: PageSize> PartialEq<usize> for NumOfPages<T> {
+    fn eq(&self, other: &usize) -> bool {
+        self.num_of_pages == *other
+    }
+}
+#[cfg(not(feature = "strict-operands"))]
+impl<T: PageSize> PartialEq<NumOfPages<T>> for usize {
+    fn eq(&self, other: &NumOfPages<T>) -> bool {
+        *self == other.num_of_pages
+    }
+}
+#[cfg(not(feature = "strict-operands"))]
+impl<T: PageSize> PartialOrd<usize> for NumOfPages<T> {
+    fn partial_cmp(&self, other: &usize) -> Option<Ordering> {
+        self.num_of_pages.partial_cmp(other)
+    }
+}
+#[cfg(not(feature = "strict-operands"))]
+impl<T: PageSize> PartialOrd<NumOfPages<T>> for usize {
+    fn partial_cmp(&self, other: &NumOfPages<T>) -> Option<Ordering> {
+        self.partial_cmp(&other.num_of_pages)
+    }
+}
+/// Implemented manually because the derived impl requires `T: Hash`, which the page sizes of
+/// `x86_64` do not implement.
+impl<T: PageSize> Hash for NumOfPages<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.num_of_pages.hash(state);
+    }
+}
 impl<T: PageSize> fmt::Debug for NumOfPages<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -159,9 +919,16 @@ impl<T: PageSize> fmt::Display for NumOfPages<T> {
 #[cfg(test)]
 mod tests {
     use super::NumOfPages;
+    use crate::Bytes;
+    use core::marker::PhantomData;
+    use core::num::NonZeroUsize;
+    use core::panic::{RefUnwindSafe, UnwindSafe};
+    use x86_64::structures::paging::Page;
+    use x86_64::structures::paging::PageSize;
     use x86_64::structures::paging::Size1GiB;
     use x86_64::structures::paging::Size2MiB;
     use x86_64::structures::paging::Size4KiB;
+    use x86_64::VirtAddr;
 
     #[test]
     fn get_value_from_num_of_pages() {
@@ -170,24 +937,597 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn const_items() {
+        const PAGES: NumOfPages<Size4KiB> = NumOfPages::new(3);
+        const ZERO: NumOfPages<Size2MiB> = NumOfPages::zero();
+        const RAW: usize = PAGES.as_usize();
+        const BYTES: Bytes = Bytes::new(0x3000);
+        const BYTES_RAW: usize = BYTES.as_usize();
+
+        assert_eq!(PAGES.as_bytes(), BYTES);
+        assert_eq!(ZERO, NumOfPages::new(0));
+        assert_eq!(RAW, 3);
+        assert_eq!(BYTES_RAW, 0x3000);
+        assert_eq!(Bytes::zero(), ZERO.as_bytes());
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn const_ops_agree_with_operators() {
+        let a = NumOfPages::<Size4KiB>::new(6);
+        let b = NumOfPages::new(2);
+
+        assert_eq!(a.const_add(b), a + b);
+        assert_eq!(a.const_sub(b), a - b);
+        assert_eq!(a.const_mul(3), a * 3);
+        assert_eq!(a.const_div(4), a / 4);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn const_ops_in_const_items() {
+        const A: NumOfPages<Size2MiB> = NumOfPages::new(4);
+        const B: NumOfPages<Size2MiB> = A.const_add(A).const_div(2).const_mul(5).const_sub(A);
+
+        assert_eq!(B, NumOfPages::new(16));
+    }
+
+    /// A page size which is neither `Send` nor `Sync`.
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    struct NonSendSize(PhantomData<*const ()>);
+    impl PageSize for NonSendSize {
+        const SIZE: u64 = 0x1000;
+        const SIZE_AS_DEBUG_STR: &'static str = "4KiB";
+    }
+
+    /// A page size with a lifetime, to check the variance.
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    struct BorrowedSize<'a>(PhantomData<&'a ()>);
+    impl PageSize for BorrowedSize<'_> {
+        const SIZE: u64 = 0x1000;
+        const SIZE_AS_DEBUG_STR: &'static str = "4KiB";
+    }
+
+    fn assert_auto_traits<T: Send + Sync + Unpin + UnwindSafe + RefUnwindSafe + 'static>() {}
+
+    #[test]
+    fn auto_traits_regardless_of_page_size() {
+        assert_auto_traits::<NumOfPages<Size4KiB>>();
+        assert_auto_traits::<NumOfPages<Size2MiB>>();
+        assert_auto_traits::<NumOfPages<Size1GiB>>();
+        assert_auto_traits::<NumOfPages<NonSendSize>>();
+    }
+
+    #[test]
+    fn shared_static() {
+        static PAGES: NumOfPages<Size4KiB> = NumOfPages::new(3);
+
+        let handle = std::thread::spawn(|| PAGES.as_usize());
+
+        assert_eq!(handle.join().unwrap(), 3);
+    }
+
+    #[test]
+    fn covariant_in_page_size() {
+        fn shorten<'a>(
+            n: NumOfPages<BorrowedSize<'static>>,
+            _: &'a (),
+        ) -> NumOfPages<BorrowedSize<'a>> {
+            n
+        }
+
+        let n = NumOfPages::<BorrowedSize<'static>>::new(2);
+
+        assert_eq!(shorten(n, &()).as_usize(), 2);
+    }
+
+    #[test]
+    fn exact_div() {
+        let n = NumOfPages::<Size4KiB>::new(12);
+
+        assert_eq!(n.exact_div(3), Some(NumOfPages::new(4)));
+        assert_eq!(n.exact_div(12), Some(NumOfPages::new(1)));
+        assert_eq!(
+            NumOfPages::<Size2MiB>::zero().exact_div(7),
+            Some(NumOfPages::zero())
+        );
+        assert_eq!(n.exact_div(5), None);
+        assert_eq!(n.exact_div(13), None);
+        assert_eq!(n.exact_div(0), None);
+        assert_eq!(NumOfPages::<Size2MiB>::zero().exact_div(0), None);
+    }
+
+    #[test]
+    fn checked_add_sub() {
+        let max = NumOfPages::<Size4KiB>::new(usize::MAX);
+
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(3).checked_add(NumOfPages::new(4)),
+            Some(NumOfPages::new(7))
+        );
+        assert_eq!(max.checked_add(NumOfPages::new(1)), None);
+        assert_eq!(max.checked_add(NumOfPages::zero()), Some(max));
+        assert_eq!(
+            NumOfPages::<Size1GiB>::new(4).checked_sub(NumOfPages::new(3)),
+            Some(NumOfPages::new(1))
+        );
+        assert_eq!(
+            NumOfPages::<Size1GiB>::new(3).checked_sub(NumOfPages::new(4)),
+            None
+        );
+    }
+
+    #[test]
+    fn checked_mul_div() {
+        let half = NumOfPages::<Size4KiB>::new(usize::MAX / 2);
+
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(usize::MAX / 2 + 1).checked_mul(2),
+            None
+        );
+        assert_eq!(half.checked_mul(2), Some(NumOfPages::new(usize::MAX - 1)));
+        assert_eq!(half.checked_mul(0), Some(NumOfPages::zero()));
+        assert_eq!(
+            NumOfPages::<Size1GiB>::new(3).checked_mul(4),
+            Some(NumOfPages::new(12))
+        );
+        assert_eq!(
+            NumOfPages::<Size1GiB>::new(13).checked_div(4),
+            Some(NumOfPages::new(3))
+        );
+        assert_eq!(NumOfPages::<Size1GiB>::new(13).checked_div(0), None);
+        assert_eq!(NumOfPages::<Size4KiB>::zero().checked_div(0), None);
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict-operands"))]
+    fn checked_add_sub_usize() {
+        assert_eq!(
+            NumOfPages::<Size1GiB>::new(3).checked_add_usize(4),
+            Some(NumOfPages::new(7))
+        );
+        assert_eq!(
+            NumOfPages::<Size1GiB>::new(usize::MAX).checked_add_usize(1),
+            None
+        );
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(4).checked_sub_usize(4),
+            Some(NumOfPages::zero())
+        );
+        assert_eq!(NumOfPages::<Size4KiB>::new(4).checked_sub_usize(5), None);
+    }
+
+    fn check_overflowing<T: PageSize>() {
+        let max = NumOfPages::<T>::new(usize::MAX);
+        let one = NumOfPages::<T>::new(1);
+
+        assert_eq!(max.overflowing_add(one), (NumOfPages::zero(), true));
+        assert_eq!(max.overflowing_add(NumOfPages::zero()), (max, false));
+        assert_eq!(one.overflowing_add(one), (NumOfPages::new(2), false));
+        assert_eq!(NumOfPages::<T>::zero().overflowing_sub(one), (max, true));
+        assert_eq!(one.overflowing_sub(one), (NumOfPages::zero(), false));
+        assert_eq!(
+            NumOfPages::<T>::new(usize::MAX / 2 + 1).overflowing_mul(2),
+            (NumOfPages::zero(), true)
+        );
+        assert_eq!(
+            NumOfPages::<T>::new(3).overflowing_mul(4),
+            (NumOfPages::new(12), false)
+        );
+    }
+
+    fn check_wrapping<T: PageSize>() {
+        let max = NumOfPages::<T>::new(usize::MAX);
+        let one = NumOfPages::<T>::new(1);
+
+        assert_eq!(max.wrapping_add(one), NumOfPages::zero());
+        assert_eq!(max.wrapping_add(max), NumOfPages::new(usize::MAX - 1));
+        assert_eq!(NumOfPages::<T>::zero().wrapping_sub(one), max);
+        assert_eq!(
+            NumOfPages::<T>::new(5).wrapping_sub(one),
+            NumOfPages::new(4)
+        );
+        assert_eq!(max.wrapping_mul(usize::MAX), one);
+        assert_eq!(NumOfPages::<T>::new(3).wrapping_mul(4), NumOfPages::new(12));
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "NumOfPages addition overflowed")]
+    fn add_overflow_panics() {
+        let _ = NumOfPages::<Size4KiB>::new(usize::MAX) + NumOfPages::new(1);
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "overflow-checks",
+        not(any(feature = "no-panic", feature = "strict-operands"))
+    ))]
+    #[should_panic(expected = "NumOfPages subtraction overflowed: 1 - 2")]
+    fn sub_usize_overflow_panics() {
+        let _ = NumOfPages::<Size2MiB>::new(1) - 2;
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "NumOfPages subtraction overflowed: 1 - 2")]
+    fn sub_assign_overflow_panics() {
+        let mut n = NumOfPages::<Size2MiB>::new(1);
+        n -= NumOfPages::new(2);
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "NumOfPages multiplication overflowed")]
+    fn mul_overflow_panics() {
+        let _ = NumOfPages::<Size4KiB>::new(usize::MAX / 2 + 1) * 2;
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "overflow-checks",
+        not(feature = "no-panic"),
+        target_pointer_width = "64"
+    ))]
+    #[should_panic(
+        expected = "NumOfPages to Bytes multiplication overflowed: 4503599627370496 * 4096"
+    )]
+    fn as_bytes_overflow_panics() {
+        let _ = NumOfPages::<Size4KiB>::new(1 << 52).as_bytes();
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn strict_arithmetic() {
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(3).strict_add(NumOfPages::new(4)),
+            NumOfPages::new(7)
+        );
+        assert_eq!(
+            NumOfPages::<Size2MiB>::new(4).strict_sub(NumOfPages::new(3)),
+            NumOfPages::new(1)
+        );
+        assert_eq!(
+            NumOfPages::<Size1GiB>::new(3).strict_mul(4),
+            NumOfPages::new(12)
+        );
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(13).strict_div(4),
+            NumOfPages::new(3)
+        );
+    }
+
+    #[test]
+    #[cfg(all(not(feature = "no-panic"), target_pointer_width = "64"))]
+    #[should_panic(
+        expected = "NumOfPages::strict_add overflowed: 18446744073709551615 pages of 4KiB + 1 pages of 4KiB"
+    )]
+    fn strict_add_overflow() {
+        let _ = NumOfPages::<Size4KiB>::new(usize::MAX).strict_add(NumOfPages::new(1));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic(
+        expected = "NumOfPages::strict_sub overflowed: 1 pages of 2MiB - 2 pages of 2MiB"
+    )]
+    fn strict_sub_overflow() {
+        let _ = NumOfPages::<Size2MiB>::new(1).strict_sub(NumOfPages::new(2));
+    }
+
+    #[test]
+    #[cfg(all(not(feature = "no-panic"), target_pointer_width = "64"))]
+    #[should_panic(
+        expected = "NumOfPages::strict_mul overflowed: 9223372036854775808 pages of 1GiB * 2"
+    )]
+    fn strict_mul_overflow() {
+        let _ = NumOfPages::<Size1GiB>::new(usize::MAX / 2 + 1).strict_mul(2);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    #[should_panic(expected = "NumOfPages::strict_div divided by zero: 5 pages of 4KiB / 0")]
+    fn strict_div_by_zero() {
+        let _ = NumOfPages::<Size4KiB>::new(5).strict_div(0);
+    }
+
+    #[test]
+    fn overflowing_arithmetic() {
+        check_overflowing::<Size4KiB>();
+        check_overflowing::<Size2MiB>();
+    }
+
+    #[test]
+    fn wrapping_arithmetic() {
+        check_wrapping::<Size4KiB>();
+        check_wrapping::<Size2MiB>();
+    }
+
+    #[test]
+    fn wrapping_matches_overflowing() {
+        let a = NumOfPages::<Size2MiB>::new(usize::MAX - 3);
+        let b = NumOfPages::<Size2MiB>::new(10);
+
+        assert_eq!(a.wrapping_add(b), a.overflowing_add(b).0);
+        assert_eq!(b.wrapping_sub(a), b.overflowing_sub(a).0);
+        assert_eq!(a.wrapping_mul(10), a.overflowing_mul(10).0);
+    }
+
+    #[test]
+    fn checked_in_const_items() {
+        const FRAMES: Option<NumOfPages<Size1GiB>> =
+            NumOfPages::new(1).checked_add(NumOfPages::new(2));
+        const LEFT: Option<NumOfPages<Size4KiB>> =
+            NumOfPages::new(1).checked_sub(NumOfPages::new(2));
+
+        const SCALED: Option<NumOfPages<Size4KiB>> = NumOfPages::new(3).checked_mul(2);
+        const SPLIT: Option<NumOfPages<Size4KiB>> = NumOfPages::new(3).checked_div(0);
+
+        assert_eq!(FRAMES, Some(NumOfPages::new(3)));
+        assert_eq!(LEFT, None);
+        assert_eq!(SCALED, Some(NumOfPages::new(6)));
+        assert_eq!(SPLIT, None);
+    }
+
+    #[test]
+    fn saturating_arithmetic() {
+        let max = NumOfPages::<Size4KiB>::new(usize::MAX);
+
+        assert_eq!(max.saturating_add(NumOfPages::new(1)), max);
+        assert_eq!(
+            NumOfPages::<Size1GiB>::new(3).saturating_add(NumOfPages::new(4)),
+            NumOfPages::new(7)
+        );
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(3).saturating_sub(NumOfPages::new(4)),
+            NumOfPages::zero()
+        );
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(2).saturating_mul(usize::MAX),
+            max
+        );
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(2).saturating_mul(3),
+            NumOfPages::new(6)
+        );
+    }
+
+    #[test]
+    fn checked_as_bytes() {
+        assert_eq!(
+            NumOfPages::<Size2MiB>::new(2).checked_as_bytes(),
+            Some(Bytes::new(0x40_0000))
+        );
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(usize::MAX >> 12).checked_as_bytes(),
+            Some(Bytes::new(usize::MAX & !0xfff))
+        );
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new((usize::MAX >> 12) + 1).checked_as_bytes(),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "16")]
+    fn page_size_does_not_fit_in_16bit_usize() {
+        assert_eq!(
+            NumOfPages::<Size2MiB>::new(0).checked_as_bytes(),
+            Some(Bytes::zero())
+        );
+        assert_eq!(NumOfPages::<Size2MiB>::new(1).checked_as_bytes(), None);
+        assert_eq!(NumOfPages::<Size1GiB>::new(1).as_layout(), None);
+        assert_eq!(
+            Bytes::new(usize::MAX).as_num_of_pages::<Size2MiB>(),
+            NumOfPages::new(1)
+        );
+        assert_eq!(
+            NumOfPages::<Size2MiB>::spanning(VirtAddr::new(0x1ff0), Bytes::new(0x20)),
+            NumOfPages::new(1)
+        );
+    }
+
+    #[test]
+    #[cfg(all(target_pointer_width = "16", not(feature = "no-panic")))]
+    #[should_panic(
+        expected = "NumOfPages to Bytes overflowed: the page size does not fit in `usize`"
+    )]
+    fn as_bytes_panics_on_16bit() {
+        let _ = NumOfPages::<Size2MiB>::new(1).as_bytes();
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict-operands"))]
+    fn compare_with_usize() {
+        use core::cmp::Ordering;
+
+        let n = NumOfPages::<Size4KiB>::new(3);
+
+        assert_eq!(n, 3);
+        assert_ne!(n, 4);
+        assert!(n < 4);
+        assert!(n <= 3);
+        assert!(n > 2);
+        assert!(n >= 3);
+        assert_eq!(n.partial_cmp(&3_usize), Some(Ordering::Equal));
+        assert_eq!(NumOfPages::<Size1GiB>::zero(), 0);
+        assert!(NumOfPages::<Size1GiB>::new(1) > 0);
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict-operands"))]
+    fn compare_usize_with_num_of_pages() {
+        use core::cmp::Ordering;
+
+        let n = NumOfPages::<Size2MiB>::new(3);
+
+        assert_eq!(3, n);
+        assert_ne!(4, n);
+        assert!(2 < n);
+        assert!(3 <= n);
+        assert!(4 > n);
+        assert!(3 >= n);
+        assert_eq!(3_usize.partial_cmp(&n), Some(Ordering::Equal));
+        assert!(usize::MAX > NumOfPages::<Size4KiB>::new(usize::MAX - 1));
+    }
+
+    fn check_max_convertible<T: PageSize>() {
+        let max = NumOfPages::<T>::max_convertible();
+
+        assert!(max.checked_as_bytes().is_some());
+        assert_eq!(
+            NumOfPages::<T>::new(max.as_usize() + 1).checked_as_bytes(),
+            None
+        );
+        assert_eq!(max.checked_as_bytes(), Some(Bytes::max_for_pages::<T>()));
+    }
+
+    #[test]
+    fn max_convertible() {
+        check_max_convertible::<Size4KiB>();
+        check_max_convertible::<Size2MiB>();
+        check_max_convertible::<Size1GiB>();
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn max_convertible_32bit() {
+        assert_eq!(
+            NumOfPages::<Size4KiB>::max_convertible().as_usize(),
+            0xf_ffff
+        );
+        assert_eq!(NumOfPages::<Size2MiB>::max_convertible().as_usize(), 0x7ff);
+        assert_eq!(NumOfPages::<Size1GiB>::max_convertible().as_usize(), 3);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn max_convertible_64bit() {
+        assert_eq!(
+            NumOfPages::<Size4KiB>::max_convertible().as_usize(),
+            0xf_ffff_ffff_ffff
+        );
+        assert_eq!(
+            NumOfPages::<Size2MiB>::max_convertible().as_usize(),
+            0x7ff_ffff_ffff
+        );
+        assert_eq!(
+            NumOfPages::<Size1GiB>::max_convertible().as_usize(),
+            0x3_ffff_ffff
+        );
+    }
+
+    #[test]
+    fn const_eq_cmp_agree_with_traits() {
+        let values = [0, 1, 0x1000, usize::MAX].map(NumOfPages::<Size4KiB>::new);
+
+        for a in values {
+            for b in values {
+                assert_eq!(a.const_eq(b), a == b);
+                assert_eq!(a.const_cmp(b), a.cmp(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn const_cmp_in_const_items() {
+        const SMALL: NumOfPages<Size4KiB> = NumOfPages::<Size4KiB>::new(1);
+        const LARGE: NumOfPages<Size4KiB> = NumOfPages::<Size4KiB>::new(2);
+        const _: () = assert!(SMALL.const_cmp(LARGE).is_lt());
+        const _: () = assert!(LARGE.const_cmp(SMALL).is_gt());
+        const _: () = assert!(SMALL.const_eq(SMALL));
+        const _: () = assert!(!SMALL.const_eq(LARGE));
+    }
+
+    #[test]
+    fn reexport_is_hash_and_from_usize() {
+        use crate::NumOfPages as Reexported;
+
+        let pages: Reexported<Size4KiB> = 3.into();
+        let set: std::collections::HashSet<_> = [pages, NumOfPages::new(4), NumOfPages::from(3)]
+            .iter()
+            .copied()
+            .collect();
+
+        assert_eq!(pages, NumOfPages::new(3));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&NumOfPages::new(4)));
+    }
+
+    #[test]
+    fn layout() {
+        assert_eq!(size_of::<NumOfPages<Size4KiB>>(), size_of::<usize>());
+        assert_eq!(size_of::<NumOfPages<Size1GiB>>(), size_of::<usize>());
+        assert_eq!(align_of::<NumOfPages<Size2MiB>>(), align_of::<usize>());
+    }
+
+    #[test]
+    fn raw_round_trip() {
+        let n = NumOfPages::<Size4KiB>::from_raw(3);
+
+        assert_eq!(n, NumOfPages::new(3));
+        assert_eq!(n.into_raw(), 3);
+    }
+
+    #[test]
+    fn le_bytes() {
+        let n = NumOfPages::<Size4KiB>::new(0x0102);
+        let raw = n.to_le_bytes();
+
+        assert_eq!(raw[..2], [0x02, 0x01]);
+        assert_eq!(NumOfPages::from_le_bytes(raw), n);
+    }
+
+    #[test]
+    fn be_bytes() {
+        let n = NumOfPages::<Size2MiB>::new(0x0102);
+        let raw = n.to_be_bytes();
+
+        assert_eq!(raw[raw.len() - 2..], [0x01, 0x02]);
+        assert_eq!(NumOfPages::from_be_bytes(raw), n);
+    }
+
+    #[test]
+    fn ne_bytes() {
+        let n = NumOfPages::<Size4KiB>::new(0x0102);
+
+        assert_eq!(n.to_ne_bytes(), 0x0102_usize.to_ne_bytes());
+        assert_eq!(NumOfPages::from_ne_bytes(n.to_ne_bytes()), n);
+    }
+
+    #[test]
+    fn u64_le_bytes() {
+        let n = NumOfPages::<Size1GiB>::new(0x0102);
+        let raw = n.to_u64_le_bytes();
+
+        assert_eq!(raw, [0x02, 0x01, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(NumOfPages::from_u64_le_bytes(raw), Ok(n));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn pages_to_bytes_4k() {
         let num_of_pages = NumOfPages::<Size4KiB>::new(1);
         assert_eq!(num_of_pages.as_bytes().as_usize(), 0x1000);
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn pages_to_bytes_2m() {
         let num_of_pages = NumOfPages::<Size2MiB>::new(1);
-        assert_eq!(num_of_pages.as_bytes().as_usize(), 0x200000);
+        assert_eq!(num_of_pages.as_bytes().as_usize(), 0x20_0000);
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn pages_to_bytes_1g() {
         let num_of_pages = NumOfPages::<Size1GiB>::new(1);
-        assert_eq!(num_of_pages.as_bytes().as_usize(), 0x40000000);
+        assert_eq!(num_of_pages.as_bytes().as_usize(), 0x4000_0000);
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn addition_pages_to_pages() {
         let p1 = NumOfPages::<Size4KiB>::new(3);
         let p2 = NumOfPages::<Size4KiB>::new(1);
@@ -197,6 +1537,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
     fn add_usize_to_num_of_pages() {
         let n = NumOfPages::<Size4KiB>::new(3);
 
@@ -204,6 +1545,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn subtraction_pages_from_pages() {
         let p1 = NumOfPages::<Size4KiB>::new(3);
         let p2 = NumOfPages::<Size4KiB>::new(1);
@@ -213,6 +1555,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
     fn subtract_usize_from_num_of_pages() {
         let n = NumOfPages::<Size4KiB>::new(5);
 
@@ -220,6 +1563,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn add_assign_pages_to_pages() {
         let mut p1 = NumOfPages::<Size4KiB>::new(3);
         p1 += NumOfPages::<Size4KiB>::new(1);
@@ -228,6 +1572,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
     fn add_assign_usize_to_pages() {
         let mut p1 = NumOfPages::<Size4KiB>::new(3);
         p1 += 1;
@@ -236,6 +1581,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn sub_assign_pages_to_pages() {
         let mut p1 = NumOfPages::<Size4KiB>::new(3);
         p1 -= NumOfPages::<Size4KiB>::new(1);
@@ -244,6 +1590,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(any(feature = "no-panic", feature = "strict-operands")))]
     fn sub_assign_usize_to_num_of_pages() {
         let mut p1 = NumOfPages::<Size4KiB>::new(10);
         p1 -= 3;
@@ -252,6 +1599,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn mul_pages_by_usize() {
         let p = NumOfPages::<Size4KiB>::new(3);
         let mul = p * 4;
@@ -260,6 +1608,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn mul_assign_pages_by_usize() {
         let mut p = NumOfPages::<Size4KiB>::new(3);
         p *= 4;
@@ -268,6 +1617,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn div_num_of_pages_by_usize() {
         let p1 = NumOfPages::<Size4KiB>::new(3);
         let div = p1 / 2;
@@ -276,6 +1626,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-panic"))]
     fn divassign_num_of_pages_by_usize() {
         let mut p = NumOfPages::<Size4KiB>::new(3);
         p /= 2;
@@ -290,6 +1641,275 @@ mod tests {
         assert_eq!(n.as_usize(), 0);
     }
 
+    #[test]
+    fn spanning_aligned() {
+        let n = NumOfPages::<Size4KiB>::spanning(VirtAddr::new(0x1000), Bytes::new(0x2000));
+
+        assert_eq!(n, NumOfPages::new(2));
+    }
+
+    #[test]
+    fn spanning_aligned_partial() {
+        let n = NumOfPages::<Size4KiB>::spanning(VirtAddr::new(0x1000), Bytes::new(0x1001));
+
+        assert_eq!(n, NumOfPages::new(2));
+    }
+
+    #[test]
+    fn spanning_misaligned_adds_a_page() {
+        let n = NumOfPages::<Size4KiB>::spanning(VirtAddr::new(0x1800), Bytes::new(0x1000));
+
+        assert_eq!(n, NumOfPages::new(2));
+    }
+
+    #[test]
+    fn spanning_misaligned_within_a_page() {
+        let n = NumOfPages::<Size2MiB>::spanning(VirtAddr::new(0x20_1000), Bytes::new(0x1000));
+
+        assert_eq!(n, NumOfPages::new(1));
+    }
+
+    #[test]
+    fn spanning_zero_length() {
+        let n = NumOfPages::<Size4KiB>::spanning(VirtAddr::new(0x1800), Bytes::zero());
+
+        assert_eq!(n, NumOfPages::zero());
+    }
+
+    #[test]
+    fn spanning_max_length() {
+        let n = NumOfPages::<Size4KiB>::spanning(VirtAddr::new(0xfff), Bytes::new(usize::MAX));
+
+        assert_eq!(n, NumOfPages::new(usize::MAX / 0x1000 + 2));
+    }
+
+    #[test]
+    fn spanning_range() {
+        let r = NumOfPages::<Size4KiB>::spanning_range(VirtAddr::new(0x1800), Bytes::new(0x1000));
+        let start = Page::containing_address(VirtAddr::new(0x1000));
+        let end = Page::containing_address(VirtAddr::new(0x3000));
+
+        assert_eq!(r, Some(Page::range(start, end)));
+    }
+
+    #[test]
+    fn spanning_range_zero_length() {
+        let r = NumOfPages::<Size4KiB>::spanning_range(VirtAddr::new(0x1800), Bytes::zero());
+
+        assert!(r.unwrap().is_empty());
+    }
+
+    #[test]
+    fn indices_zero() {
+        let mut i = NumOfPages::<Size4KiB>::zero().indices();
+
+        assert_eq!(i.len(), 0);
+        assert_eq!(i.next(), None);
+    }
+
+    #[test]
+    fn indices_one() {
+        let v: Vec<_> = NumOfPages::<Size4KiB>::new(1).indices().collect();
+
+        assert_eq!(v, [NumOfPages::new(0)]);
+    }
+
+    #[test]
+    fn indices_several() {
+        let i = NumOfPages::<Size2MiB>::new(3).indices();
+
+        assert_eq!(i.len(), 3);
+        assert_eq!(
+            i.collect::<Vec<_>>(),
+            [NumOfPages::new(0), NumOfPages::new(1), NumOfPages::new(2)]
+        );
+    }
+
+    #[test]
+    fn indices_rev() {
+        let v: Vec<_> = NumOfPages::<Size4KiB>::new(3).indices().rev().collect();
+
+        assert_eq!(
+            v,
+            [NumOfPages::new(2), NumOfPages::new(1), NumOfPages::new(0)]
+        );
+    }
+
+    #[test]
+    fn split_evenly_exact() {
+        let v: Vec<_> = NumOfPages::<Size4KiB>::new(6)
+            .split_evenly(NonZeroUsize::new(3).unwrap())
+            .collect();
+
+        assert_eq!(
+            v,
+            [NumOfPages::new(2), NumOfPages::new(2), NumOfPages::new(2)]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn split_evenly_with_remainder() {
+        let total = NumOfPages::<Size2MiB>::new(1003);
+        let v: Vec<_> = total.split_evenly(NonZeroUsize::new(8).unwrap()).collect();
+        let min = v.iter().min().unwrap().as_usize();
+        let max = v.iter().max().unwrap().as_usize();
+
+        assert_eq!(v.len(), 8);
+        assert_eq!(v.iter().sum::<NumOfPages<_>>(), total);
+        assert!(max - min <= 1);
+        assert_eq!(v[0], NumOfPages::new(126));
+        assert_eq!(v[7], NumOfPages::new(125));
+    }
+
+    #[test]
+    fn split_evenly_fewer_pages_than_workers() {
+        let v: Vec<_> = NumOfPages::<Size4KiB>::new(1)
+            .split_evenly(NonZeroUsize::new(3).unwrap())
+            .collect();
+
+        assert_eq!(
+            v,
+            [NumOfPages::new(1), NumOfPages::zero(), NumOfPages::zero()]
+        );
+    }
+
+    #[test]
+    fn as_layout() {
+        let l = NumOfPages::<Size4KiB>::new(3).as_layout().unwrap();
+
+        assert_eq!(l.size(), 0x3000);
+        assert_eq!(l.align(), 0x1000);
+    }
+
+    #[test]
+    fn as_layout_zero() {
+        let l = NumOfPages::<Size1GiB>::zero().as_layout().unwrap();
+
+        assert_eq!(l.size(), 0);
+        assert_eq!(l.align(), 0x4000_0000);
+    }
+
+    #[test]
+    fn as_layout_overflow() {
+        assert_eq!(NumOfPages::<Size4KiB>::new(usize::MAX).as_layout(), None);
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(isize::MAX as usize / 0x1000 + 1).as_layout(),
+            None
+        );
+    }
+
+    #[test]
+    fn bitmap_bytes() {
+        assert_eq!(NumOfPages::<Size4KiB>::zero().bitmap_bytes(), Bytes::zero());
+        assert_eq!(NumOfPages::<Size4KiB>::new(1).bitmap_bytes(), Bytes::new(8));
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(64).bitmap_bytes(),
+            Bytes::new(8)
+        );
+        assert_eq!(
+            NumOfPages::<Size4KiB>::new(65).bitmap_bytes(),
+            Bytes::new(16)
+        );
+    }
+
+    #[test]
+    fn order() {
+        assert_eq!(NumOfPages::<Size4KiB>::zero().order(), 0);
+        assert_eq!(NumOfPages::<Size4KiB>::new(1).order(), 0);
+        assert_eq!(NumOfPages::<Size4KiB>::new(2).order(), 1);
+        assert_eq!(NumOfPages::<Size4KiB>::new(3).order(), 2);
+        assert_eq!(NumOfPages::<Size4KiB>::new(512).order(), 9);
+        assert_eq!(NumOfPages::<Size4KiB>::new(usize::MAX).order(), usize::BITS);
+    }
+
+    fn assert_power_of_two_runs(runs: &[NumOfPages<Size4KiB>], total: NumOfPages<Size4KiB>) {
+        assert_eq!(
+            runs.iter()
+                .try_fold(NumOfPages::zero(), |a, &b| a.checked_add(b)),
+            Some(total)
+        );
+
+        for r in runs {
+            assert!(r.as_usize().is_power_of_two());
+        }
+
+        for w in runs.windows(2) {
+            assert!(w[0] >= w[1]);
+        }
+    }
+
+    #[test]
+    fn split_power_of_two() {
+        let total = NumOfPages::new(0b1011_0110);
+        let v: Vec<_> = total.split_power_of_two().collect();
+
+        assert_eq!(v.len(), 5);
+        assert_power_of_two_runs(&v, total);
+    }
+
+    #[test]
+    fn split_power_of_two_zero() {
+        assert_eq!(
+            NumOfPages::<Size4KiB>::zero().split_power_of_two().next(),
+            None
+        );
+    }
+
+    #[test]
+    fn split_power_of_two_max() {
+        let total = NumOfPages::new(usize::MAX);
+        let v: Vec<_> = total.split_power_of_two().collect();
+
+        assert_eq!(v.len(), usize::BITS as usize);
+        assert_power_of_two_runs(&v, total);
+    }
+
+    #[test]
+    fn split_power_of_two_max_order() {
+        let total = NumOfPages::new(0b1011_0110);
+        let v: Vec<_> = total.split_power_of_two_max_order(4).collect();
+
+        assert_eq!(v[..11], [NumOfPages::new(16); 11]);
+        assert_eq!(v[11..], [NumOfPages::new(4), NumOfPages::new(2)]);
+        assert_power_of_two_runs(&v, total);
+    }
+
+    #[test]
+    fn split_power_of_two_max_order_zero() {
+        let v: Vec<_> = NumOfPages::<Size4KiB>::new(3)
+            .split_power_of_two_max_order(0)
+            .collect();
+
+        assert_eq!(v, [NumOfPages::new(1); 3]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sum_owned() {
+        let s: NumOfPages<Size4KiB> = vec![NumOfPages::new(3), NumOfPages::new(4)]
+            .into_iter()
+            .sum();
+
+        assert_eq!(s, NumOfPages::new(7));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sum_borrowed() {
+        let s: NumOfPages<Size2MiB> = [NumOfPages::new(3), NumOfPages::new(4)].iter().sum();
+
+        assert_eq!(s, NumOfPages::new(7));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sum_empty() {
+        let s: NumOfPages<Size4KiB> = core::iter::empty::<NumOfPages<_>>().sum();
+
+        assert_eq!(s, NumOfPages::zero());
+    }
+
     #[test]
     fn from() {
         let n = NumOfPages::<Size4KiB>::from(3);
@@ -300,7 +1920,7 @@ mod tests {
     #[test]
     fn debug_4k() {
         let n = NumOfPages::<Size4KiB>::new(3);
-        let f = format!("{:?}", n);
+        let f = format!("{n:?}");
 
         assert_eq!(format!("NumOfPages::<4KiB>(3)"), f);
     }
@@ -308,7 +1928,7 @@ mod tests {
     #[test]
     fn debug_2m() {
         let n = NumOfPages::<Size2MiB>::new(3);
-        let f = format!("{:?}", n);
+        let f = format!("{n:?}");
 
         assert_eq!(format!("NumOfPages::<2MiB>(3)"), f);
     }
@@ -316,7 +1936,7 @@ mod tests {
     #[test]
     fn debug_1g() {
         let n = NumOfPages::<Size1GiB>::new(3);
-        let f = format!("{:?}", n);
+        let f = format!("{n:?}");
 
         assert_eq!(format!("NumOfPages::<1GiB>(3)"), f);
     }
@@ -324,7 +1944,7 @@ mod tests {
     #[test]
     fn display_0() {
         let n = NumOfPages::<Size4KiB>::zero();
-        let f = format!("{}", n);
+        let f = format!("{n}");
 
         assert_eq!(format!("0 pages (4KiB)"), f);
     }
@@ -332,7 +1952,7 @@ mod tests {
     #[test]
     fn display_1() {
         let n = NumOfPages::<Size4KiB>::new(1);
-        let f = format!("{}", n);
+        let f = format!("{n}");
 
         assert_eq!(format!("1 page (4KiB)"), f);
     }
@@ -340,7 +1960,7 @@ mod tests {
     #[test]
     fn display_2() {
         let n = NumOfPages::<Size4KiB>::new(2);
-        let f = format!("{}", n);
+        let f = format!("{n}");
 
         assert_eq!(format!("2 pages (4KiB)"), f);
     }
@@ -348,7 +1968,7 @@ mod tests {
     #[test]
     fn display_2m() {
         let n = NumOfPages::<Size2MiB>::zero();
-        let f = format!("{}", n);
+        let f = format!("{n}");
 
         assert_eq!(format!("0 pages (2MiB)"), f);
     }
@@ -356,7 +1976,7 @@ mod tests {
     #[test]
     fn display_1g() {
         let n = NumOfPages::<Size1GiB>::zero();
-        let f = format!("{}", n);
+        let f = format!("{n}");
 
         assert_eq!(format!("0 pages (1GiB)"), f);
     }
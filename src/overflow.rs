@@ -0,0 +1,104 @@
+//! The arithmetic of the operator impls.
+//!
+//! With the `overflow-checks` feature, these functions panic on overflow with the operands in the
+//! message regardless of the profile. Without it, they behave like the built-in operators, and are
+//! `const` so that they can be called in `impl const` with the `nightly-const-ops` feature.
+//!
+//! This module also has the panic message of the `strict_*` methods, which panic regardless of the
+//! feature.
+
+use core::fmt;
+
+#[cfg(feature = "overflow-checks")]
+#[track_caller]
+pub(crate) fn add(ty: &str, lhs: usize, rhs: usize) -> usize {
+    match lhs.checked_add(rhs) {
+        Some(n) => n,
+        None => overflowed(ty, "addition", lhs, '+', rhs),
+    }
+}
+#[cfg(not(feature = "overflow-checks"))]
+#[track_caller]
+pub(crate) const fn add(_: &str, lhs: usize, rhs: usize) -> usize {
+    lhs + rhs
+}
+
+#[cfg(feature = "overflow-checks")]
+#[track_caller]
+pub(crate) fn sub(ty: &str, lhs: usize, rhs: usize) -> usize {
+    match lhs.checked_sub(rhs) {
+        Some(n) => n,
+        None => overflowed(ty, "subtraction", lhs, '-', rhs),
+    }
+}
+#[cfg(not(feature = "overflow-checks"))]
+#[track_caller]
+pub(crate) const fn sub(_: &str, lhs: usize, rhs: usize) -> usize {
+    lhs - rhs
+}
+
+#[cfg(feature = "overflow-checks")]
+#[track_caller]
+pub(crate) fn mul(ty: &str, lhs: usize, rhs: usize) -> usize {
+    match lhs.checked_mul(rhs) {
+        Some(n) => n,
+        None => overflowed(ty, "multiplication", lhs, '*', rhs),
+    }
+}
+#[cfg(not(feature = "overflow-checks"))]
+#[track_caller]
+pub(crate) const fn mul(_: &str, lhs: usize, rhs: usize) -> usize {
+    lhs * rhs
+}
+
+#[cfg(feature = "overflow-checks")]
+#[cold]
+#[track_caller]
+fn overflowed(ty: &str, op: &str, lhs: usize, sign: char, rhs: usize) -> ! {
+    panic!("{} {} overflowed: {} {} {}", ty, op, lhs, sign, rhs)
+}
+
+/// Panics with the message of the `strict_*` methods, e.g.
+/// `Bytes::strict_sub overflowed: 1 bytes - 2 bytes`. The operands are formatted with their units.
+#[cold]
+#[track_caller]
+pub(crate) fn strict_failed(
+    method: &str,
+    what: &str,
+    lhs: fmt::Arguments<'_>,
+    sign: char,
+    rhs: fmt::Arguments<'_>,
+) -> ! {
+    panic!("{} {}: {} {} {}", method, what, lhs, sign, rhs)
+}
+
+#[cfg(all(test, feature = "overflow-checks"))]
+mod tests {
+    use super::{add, mul, sub};
+
+    #[test]
+    fn in_range() {
+        assert_eq!(add("Bytes", 1, 2), 3);
+        assert_eq!(sub("Bytes", 3, 2), 1);
+        assert_eq!(mul("Bytes", 3, 2), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bytes addition overflowed: 18446744073709551615 + 4096")]
+    #[cfg(target_pointer_width = "64")]
+    fn add_overflow() {
+        let _ = add("Bytes", usize::MAX, 4096);
+    }
+
+    #[test]
+    #[should_panic(expected = "NumOfPages subtraction overflowed: 1 - 2")]
+    fn sub_overflow() {
+        let _ = sub("NumOfPages", 1, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bytes multiplication overflowed")]
+    fn mul_overflow() {
+        let _ = mul("Bytes", usize::MAX, 2);
+    }
+}
@@ -0,0 +1,395 @@
+use crate::NumOfPages;
+use core::marker::PhantomData;
+use x86_64::structures::paging::{PageSize, Size4KiB};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A bitmap with one bit per page, stored in a slice of `u64` words provided by the caller.
+///
+/// Bit `i % 64` of word `i / 64` is the bit of page `i`. The pages are indexed by [`NumOfPages`]
+/// from the first page the bitmap covers. The bits in the last word beyond [`len`](Self::len) are
+/// ignored.
+///
+/// ```rust
+/// use os_units::{NumOfPages, PageBitmap};
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// let len = NumOfPages::<Size4KiB>::new(100);
+/// let mut storage = [0; 2];
+/// assert_eq!(len.bitmap_bytes().as_usize(), size_of_val(&storage));
+///
+/// let mut bitmap = PageBitmap::new(&mut storage, len).unwrap();
+///
+/// bitmap.set_range(NumOfPages::zero(), NumOfPages::new(3));
+/// let run = bitmap.find_first_clear_run(NumOfPages::new(4)).unwrap();
+///
+/// assert_eq!(run, NumOfPages::new(3));
+/// assert_eq!(bitmap.count_set(), NumOfPages::new(3));
+/// ```
+#[derive(Debug)]
+pub struct PageBitmap<'a, T: PageSize = Size4KiB> {
+    words: &'a mut [u64],
+    len: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+impl<'a, T: PageSize> PageBitmap<'a, T> {
+    /// Creates a new bitmap of `len` pages on `words`.
+    ///
+    /// The current contents of `words` are used as they are. Call
+    /// [`clear_range`](Self::clear_range) first to start with every page clear.
+    ///
+    /// This method returns [`None`] if `words` is shorter than
+    /// [`NumOfPages::bitmap_bytes`] of `len`.
+    #[must_use]
+    pub fn new(words: &'a mut [u64], len: NumOfPages<T>) -> Option<Self> {
+        if words.len() < len.as_usize().div_ceil(BITS) {
+            return None;
+        }
+
+        Some(Self {
+            words,
+            len: len.as_usize(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the number of the pages the bitmap covers.
+    #[must_use]
+    pub fn len(&self) -> NumOfPages<T> {
+        NumOfPages::new(self.len)
+    }
+
+    /// Returns `true` if the bitmap covers no pages.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Sets the bit of page `index`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `index >= self.len()`.
+    pub fn set(&mut self, index: NumOfPages<T>) {
+        let i = self.checked_index(index);
+
+        self.words[i / BITS] |= 1 << (i % BITS);
+    }
+
+    /// Clears the bit of page `index`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `index >= self.len()`.
+    pub fn clear(&mut self, index: NumOfPages<T>) {
+        let i = self.checked_index(index);
+
+        self.words[i / BITS] &= !(1 << (i % BITS));
+    }
+
+    /// Returns `true` if the bit of page `index` is set.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `index >= self.len()`.
+    #[must_use]
+    pub fn is_set(&self, index: NumOfPages<T>) -> bool {
+        let i = self.checked_index(index);
+
+        self.bit(i)
+    }
+
+    /// Sets the bits of `count` pages from page `start`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the range goes past the end of the bitmap.
+    pub fn set_range(&mut self, start: NumOfPages<T>, count: NumOfPages<T>) {
+        self.fill(start, count, true);
+    }
+
+    /// Clears the bits of `count` pages from page `start`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the range goes past the end of the bitmap.
+    pub fn clear_range(&mut self, start: NumOfPages<T>, count: NumOfPages<T>) {
+        self.fill(start, count, false);
+    }
+
+    /// Returns the index of the first page of the first run of `len` clear pages.
+    ///
+    /// This method returns [`None`] if `len` is zero or there is no such run.
+    #[must_use]
+    pub fn find_first_clear_run(&self, len: NumOfPages<T>) -> Option<NumOfPages<T>> {
+        let len = len.as_usize();
+
+        if len == 0 || len > self.len {
+            return None;
+        }
+
+        let mut start = 0;
+        let mut i = 0;
+
+        while i < self.len {
+            let word = self.words[i / BITS];
+
+            if i.is_multiple_of(BITS) && word == u64::MAX {
+                i += BITS;
+                start = i;
+            } else if i.is_multiple_of(BITS) && word == 0 {
+                i = (i + BITS).min(self.len);
+            } else {
+                if self.bit(i) {
+                    start = i + 1;
+                }
+                i += 1;
+            }
+
+            if i >= start + len {
+                return Some(NumOfPages::new(start));
+            }
+        }
+
+        None
+    }
+
+    /// Returns the number of the set pages.
+    #[must_use]
+    pub fn count_set(&self) -> NumOfPages<T> {
+        let full = self.len / BITS;
+        let rest = self.len % BITS;
+
+        let mut count: usize = self.words[..full]
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum();
+
+        if rest > 0 {
+            count += (self.words[full] & ((1 << rest) - 1)).count_ones() as usize;
+        }
+
+        NumOfPages::new(count)
+    }
+
+    fn bit(&self, i: usize) -> bool {
+        self.words[i / BITS] & (1 << (i % BITS)) != 0
+    }
+
+    fn checked_index(&self, index: NumOfPages<T>) -> usize {
+        let i = index.as_usize();
+
+        assert!(
+            i < self.len,
+            "page index out of bounds: the length is {} but the index is {}",
+            self.len,
+            i
+        );
+
+        i
+    }
+
+    fn fill(&mut self, start: NumOfPages<T>, count: NumOfPages<T>, value: bool) {
+        let mut i = start.as_usize();
+        let end = i
+            .checked_add(count.as_usize())
+            .filter(|end| *end <= self.len)
+            .expect("page range out of bounds");
+
+        while i < end {
+            let word = &mut self.words[i / BITS];
+
+            if i.is_multiple_of(BITS) && end - i >= BITS {
+                *word = if value { u64::MAX } else { 0 };
+                i += BITS;
+            } else {
+                if value {
+                    *word |= 1 << (i % BITS);
+                } else {
+                    *word &= !(1 << (i % BITS));
+                }
+                i += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PageBitmap;
+    use crate::NumOfPages;
+    use x86_64::structures::paging::Size4KiB;
+
+    fn n(n: usize) -> NumOfPages<Size4KiB> {
+        NumOfPages::new(n)
+    }
+
+    #[test]
+    fn new_storage_too_small() {
+        let mut storage = [0; 1];
+
+        assert!(PageBitmap::new(&mut storage, n(65)).is_none());
+        assert!(PageBitmap::new(&mut storage, n(64)).is_some());
+    }
+
+    #[test]
+    fn new_empty() {
+        let mut storage = [];
+        let b = PageBitmap::new(&mut storage, n(0)).unwrap();
+
+        assert!(b.is_empty());
+        assert_eq!(b.count_set(), n(0));
+        assert_eq!(b.find_first_clear_run(n(1)), None);
+    }
+
+    #[test]
+    fn set_clear() {
+        let mut storage = [0; 2];
+        let mut b = PageBitmap::new(&mut storage, n(100)).unwrap();
+
+        b.set(n(0));
+        b.set(n(63));
+        b.set(n(64));
+        b.set(n(99));
+
+        assert!(b.is_set(n(0)));
+        assert!(!b.is_set(n(1)));
+        assert!(b.is_set(n(63)));
+        assert!(b.is_set(n(64)));
+        assert!(b.is_set(n(99)));
+        assert_eq!(b.count_set(), n(4));
+
+        b.clear(n(63));
+
+        assert!(!b.is_set(n(63)));
+        assert_eq!(b.count_set(), n(3));
+        assert_eq!(storage, [1, 1 | 1 << 35]);
+    }
+
+    #[test]
+    #[should_panic(expected = "page index out of bounds: the length is 100 but the index is 100")]
+    fn set_out_of_bounds() {
+        let mut storage = [0; 2];
+        let mut b = PageBitmap::new(&mut storage, n(100)).unwrap();
+
+        b.set(n(100));
+    }
+
+    #[test]
+    fn set_range_across_words() {
+        let mut storage = [0; 3];
+        let mut b = PageBitmap::new(&mut storage, n(192)).unwrap();
+
+        b.set_range(n(60), n(70));
+
+        assert!(!b.is_set(n(59)));
+        assert!(b.is_set(n(60)));
+        assert!(b.is_set(n(129)));
+        assert!(!b.is_set(n(130)));
+        assert_eq!(b.count_set(), n(70));
+
+        b.clear_range(n(64), n(64));
+
+        assert_eq!(b.count_set(), n(6));
+        assert_eq!(storage, [0xf << 60, 0, 0b11]);
+    }
+
+    #[test]
+    #[should_panic(expected = "page range out of bounds")]
+    fn set_range_out_of_bounds() {
+        let mut storage = [0; 2];
+        let mut b = PageBitmap::new(&mut storage, n(100)).unwrap();
+
+        b.set_range(n(90), n(11));
+    }
+
+    #[test]
+    fn count_set_ignores_bits_beyond_len() {
+        let mut storage = [u64::MAX; 2];
+        let b = PageBitmap::new(&mut storage, n(100)).unwrap();
+
+        assert_eq!(b.count_set(), n(100));
+    }
+
+    #[test]
+    fn find_in_empty_bitmap() {
+        let mut storage = [0; 2];
+        let b = PageBitmap::new(&mut storage, n(100)).unwrap();
+
+        assert_eq!(b.find_first_clear_run(n(1)), Some(n(0)));
+        assert_eq!(b.find_first_clear_run(n(100)), Some(n(0)));
+        assert_eq!(b.find_first_clear_run(n(101)), None);
+    }
+
+    #[test]
+    fn find_in_full_bitmap() {
+        let mut storage = [0; 2];
+        let mut b = PageBitmap::new(&mut storage, n(100)).unwrap();
+
+        b.set_range(n(0), n(100));
+
+        assert_eq!(b.count_set(), n(100));
+        assert_eq!(b.find_first_clear_run(n(1)), None);
+    }
+
+    #[test]
+    fn find_zero_len() {
+        let mut storage = [0; 1];
+        let b = PageBitmap::new(&mut storage, n(64)).unwrap();
+
+        assert_eq!(b.find_first_clear_run(n(0)), None);
+    }
+
+    #[test]
+    fn find_run_across_word_boundary() {
+        let mut storage = [0; 3];
+        let mut b = PageBitmap::new(&mut storage, n(192)).unwrap();
+
+        b.set_range(n(0), n(60));
+        b.set(n(62));
+        b.set_range(n(70), n(122));
+
+        assert_eq!(b.find_first_clear_run(n(2)), Some(n(60)));
+        assert_eq!(b.find_first_clear_run(n(7)), Some(n(63)));
+        assert_eq!(b.find_first_clear_run(n(8)), None);
+    }
+
+    #[test]
+    fn find_run_skips_full_words() {
+        let mut storage = [u64::MAX, u64::MAX, 0];
+        let b = PageBitmap::new(&mut storage, n(192)).unwrap();
+
+        assert_eq!(b.find_first_clear_run(n(64)), Some(n(128)));
+    }
+
+    #[test]
+    fn find_run_spanning_whole_words() {
+        let mut storage = [0; 4];
+        let mut b = PageBitmap::new(&mut storage, n(256)).unwrap();
+
+        b.set(n(10));
+        b.set(n(200));
+
+        assert_eq!(b.find_first_clear_run(n(189)), Some(n(11)));
+        assert_eq!(b.find_first_clear_run(n(190)), None);
+    }
+
+    #[test]
+    fn find_run_at_end() {
+        let mut storage = [0; 2];
+        let mut b = PageBitmap::new(&mut storage, n(100)).unwrap();
+
+        b.set_range(n(0), n(97));
+
+        assert_eq!(b.find_first_clear_run(n(3)), Some(n(97)));
+        assert_eq!(b.find_first_clear_run(n(4)), None);
+    }
+
+    #[test]
+    fn find_run_ignores_bits_beyond_len() {
+        let mut storage = [0, u64::MAX << 36];
+        let b = PageBitmap::new(&mut storage, n(100)).unwrap();
+
+        assert_eq!(b.find_first_clear_run(n(100)), Some(n(0)));
+    }
+}
@@ -0,0 +1,357 @@
+#[cfg(not(feature = "no-panic"))]
+use crate::overflow;
+use crate::paging;
+use crate::Bytes;
+use crate::NumOfPages;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+#[cfg(not(feature = "no-panic"))]
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use x86_64::structures::paging::{PageSize, Size4KiB};
+
+/// The index of a page, i.e. a byte offset divided by the page size, like a page frame number.
+///
+/// Unlike [`NumOfPages`], which is a count, this type is a position. Adding a count to an index
+/// gives an index, and subtracting an index from another gives a count.
+///
+/// ```rust
+/// use os_units::{Bytes, NumOfPages, PageIndex};
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// let (index, offset) = PageIndex::<Size4KiB>::from_byte_offset(Bytes::new(0x3123));
+///
+/// assert_eq!(index, PageIndex::new(3));
+/// assert_eq!(offset.as_bytes(), Bytes::new(0x123));
+/// # #[cfg(not(feature = "no-panic"))]
+/// assert_eq!(index.start_offset() + offset.as_bytes(), Bytes::new(0x3123));
+///
+/// # #[cfg(not(feature = "no-panic"))]
+/// assert_eq!(index + NumOfPages::new(2), PageIndex::new(5));
+/// # #[cfg(not(feature = "no-panic"))]
+/// assert_eq!(PageIndex::new(5) - index, NumOfPages::new(2));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PageIndex<T: PageSize = Size4KiB> {
+    index: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+impl<T: PageSize> PageIndex<T> {
+    /// Creates a new instance with the given index.
+    #[must_use]
+    pub const fn new(index: usize) -> Self {
+        Self {
+            index,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the index as `usize`.
+    #[must_use]
+    pub const fn as_usize(self) -> usize {
+        self.index
+    }
+
+    /// Splits `offset` into the index of the page containing it and the offset in the page.
+    #[must_use]
+    pub fn from_byte_offset(offset: Bytes) -> (Self, PageOffset<T>) {
+        let (index, offset) = paging::div_rem_page_size::<T>(offset.as_usize());
+
+        (Self::new(index), PageOffset::from_usize(offset))
+    }
+
+    /// Returns the byte offset of the start of the page.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the offset does not fit in `usize` and the overflow checks or the
+    /// `overflow-checks` feature are enabled. If `T::SIZE` does not fit in `usize`, it panics for
+    /// any index but zero regardless of them, as the page size cannot be a `usize` operand. Use
+    /// [`PageIndex::checked_start_offset`] to get [`None`] instead. This method is not available
+    /// with the `no-panic` feature.
+    #[cfg(not(feature = "no-panic"))]
+    #[must_use]
+    pub fn start_offset(self) -> Bytes {
+        match paging::page_size::<T>() {
+            Some(page_size) => {
+                Bytes::new(overflow::mul("PageIndex to Bytes", self.index, page_size))
+            }
+            None => self
+                .checked_start_offset()
+                .expect("PageIndex to Bytes overflowed: the page size does not fit in `usize`"),
+        }
+    }
+
+    /// Returns the byte offset of the start of the page, or [`None`] if it does not fit in
+    /// `usize`.
+    ///
+    /// ```rust
+    /// use os_units::{Bytes, PageIndex};
+    /// use x86_64::structures::paging::Size4KiB;
+    ///
+    /// assert_eq!(
+    ///     PageIndex::<Size4KiB>::new(3).checked_start_offset(),
+    ///     Some(Bytes::new(0x3000))
+    /// );
+    /// assert_eq!(PageIndex::<Size4KiB>::new(usize::MAX).checked_start_offset(), None);
+    /// ```
+    #[must_use]
+    pub fn checked_start_offset(self) -> Option<Bytes> {
+        NumOfPages::<T>::new(self.index).checked_as_bytes()
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl<T: PageSize> Add<NumOfPages<T>> for PageIndex<T> {
+    type Output = Self;
+
+    fn add(self, rhs: NumOfPages<T>) -> Self {
+        Self::new(overflow::add("PageIndex", self.index, rhs.as_usize()))
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl<T: PageSize> AddAssign<NumOfPages<T>> for PageIndex<T> {
+    fn add_assign(&mut self, rhs: NumOfPages<T>) {
+        *self = *self + rhs;
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl<T: PageSize> Sub<NumOfPages<T>> for PageIndex<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: NumOfPages<T>) -> Self {
+        Self::new(overflow::sub("PageIndex", self.index, rhs.as_usize()))
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl<T: PageSize> SubAssign<NumOfPages<T>> for PageIndex<T> {
+    fn sub_assign(&mut self, rhs: NumOfPages<T>) {
+        *self = *self - rhs;
+    }
+}
+#[cfg(not(feature = "no-panic"))]
+impl<T: PageSize> Sub for PageIndex<T> {
+    type Output = NumOfPages<T>;
+
+    /// Returns the number of the pages from `rhs` to `self`.
+    fn sub(self, rhs: Self) -> NumOfPages<T> {
+        NumOfPages::new(overflow::sub("PageIndex", self.index, rhs.index))
+    }
+}
+/// Implemented manually so that the indices can be hash keys even though the page sizes of
+/// `x86_64` do not implement [`Hash`].
+impl<T: PageSize> Hash for PageIndex<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+impl<T: PageSize> fmt::Debug for PageIndex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PageIndex::<{}>({})", T::SIZE_AS_DEBUG_STR, self.index)
+    }
+}
+
+/// The offset of a byte in a page, which is always smaller than the page size.
+///
+/// This type is returned by [`PageIndex::from_byte_offset`].
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PageOffset<T: PageSize = Size4KiB> {
+    offset: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+impl<T: PageSize> PageOffset<T> {
+    /// Creates a new instance of `offset`.
+    ///
+    /// This method returns [`None`] if `offset` is not smaller than the page size.
+    #[must_use]
+    pub fn new(offset: Bytes) -> Option<Self> {
+        if (offset.as_usize() as u64) < T::SIZE {
+            Some(Self::from_usize(offset.as_usize()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the offset as [`Bytes`].
+    #[must_use]
+    pub fn as_bytes(self) -> Bytes {
+        Bytes::new(self.offset)
+    }
+
+    /// Returns the offset as `usize`.
+    #[must_use]
+    pub fn as_usize(self) -> usize {
+        self.offset
+    }
+
+    fn from_usize(offset: usize) -> Self {
+        Self {
+            offset,
+            _marker: PhantomData,
+        }
+    }
+}
+impl<T: PageSize> fmt::Debug for PageOffset<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "PageOffset::<{}>({:#x})",
+            T::SIZE_AS_DEBUG_STR,
+            self.offset
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PageIndex, PageOffset};
+    use crate::Bytes;
+    #[cfg(not(feature = "no-panic"))]
+    use crate::NumOfPages;
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    fn i(i: usize) -> PageIndex<Size4KiB> {
+        PageIndex::new(i)
+    }
+
+    #[cfg(not(feature = "no-panic"))]
+    fn n(n: usize) -> NumOfPages<Size4KiB> {
+        NumOfPages::new(n)
+    }
+
+    #[test]
+    fn from_byte_offset() {
+        let (index, offset) = PageIndex::<Size4KiB>::from_byte_offset(Bytes::new(0x5fff));
+
+        assert_eq!(index, i(5));
+        assert_eq!(offset.as_usize(), 0xfff);
+
+        let (index, offset) = PageIndex::<Size2MiB>::from_byte_offset(Bytes::new(0x40_0001));
+
+        assert_eq!(index.as_usize(), 2);
+        assert_eq!(offset.as_bytes(), Bytes::new(1));
+    }
+
+    #[test]
+    fn from_byte_offset_aligned() {
+        let (index, offset) = PageIndex::<Size4KiB>::from_byte_offset(Bytes::new(0x3000));
+
+        assert_eq!(index, i(3));
+        assert_eq!(offset.as_bytes(), Bytes::zero());
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn split_recompose() {
+        for b in [0, 1, 0xfff, 0x1000, 0x1234_5678, usize::MAX] {
+            let (index, offset) = PageIndex::<Size4KiB>::from_byte_offset(Bytes::new(b));
+
+            assert_eq!(index.start_offset() + offset.as_bytes(), Bytes::new(b));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn start_offset() {
+        assert_eq!(i(0).start_offset(), Bytes::zero());
+        assert_eq!(i(3).start_offset(), Bytes::new(0x3000));
+        assert_eq!(
+            PageIndex::<Size2MiB>::new(3).start_offset(),
+            Bytes::new(0x60_0000)
+        );
+    }
+
+    #[test]
+    fn checked_start_offset() {
+        assert_eq!(i(3).checked_start_offset(), Some(Bytes::new(0x3000)));
+        assert_eq!(
+            i(usize::MAX >> 12).checked_start_offset(),
+            Some(Bytes::new(usize::MAX & !0xfff))
+        );
+        assert_eq!(i((usize::MAX >> 12) + 1).checked_start_offset(), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "PageIndex to Bytes multiplication overflowed")]
+    fn start_offset_overflow_panics() {
+        let _ = i(usize::MAX).start_offset();
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "PageIndex addition overflowed")]
+    fn add_overflow_panics() {
+        let _ = i(usize::MAX) + n(1);
+    }
+
+    #[test]
+    #[cfg(all(feature = "overflow-checks", not(feature = "no-panic")))]
+    #[should_panic(expected = "PageIndex subtraction overflowed")]
+    fn sub_overflow_panics() {
+        let _ = i(1) - i(2);
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn add_count() {
+        let mut index = i(3) + n(4);
+
+        assert_eq!(index, i(7));
+
+        index += n(1);
+
+        assert_eq!(index, i(8));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sub_count() {
+        let mut index = i(7) - n(4);
+
+        assert_eq!(index, i(3));
+
+        index -= n(3);
+
+        assert_eq!(index, i(0));
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-panic"))]
+    fn sub_index() {
+        assert_eq!(i(7) - i(3), n(4));
+        assert_eq!(i(3) - i(3), n(0));
+        assert_eq!(i(3) + (i(7) - i(3)), i(7));
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(i(3) < i(4));
+        assert!(i(4) >= i(4));
+        assert_eq!(i(9).max(i(2)), i(9));
+    }
+
+    #[test]
+    fn hash_key() {
+        let set: std::collections::HashSet<_> = [i(1), i(2), i(1)].iter().copied().collect();
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&i(2)));
+    }
+
+    #[test]
+    fn offset_new() {
+        assert_eq!(
+            PageOffset::<Size4KiB>::new(Bytes::new(0xfff)).map(PageOffset::as_usize),
+            Some(0xfff)
+        );
+        assert_eq!(PageOffset::<Size4KiB>::new(Bytes::new(0x1000)), None);
+    }
+
+    #[test]
+    fn debug() {
+        let (index, offset) = PageIndex::<Size4KiB>::from_byte_offset(Bytes::new(0x3123));
+
+        assert_eq!(format!("{index:?}"), "PageIndex::<4KiB>(3)");
+        assert_eq!(format!("{offset:?}"), "PageOffset::<4KiB>(0x123)");
+    }
+}
@@ -0,0 +1,734 @@
+use crate::AddrExt;
+use crate::Bytes;
+use crate::ConversionError;
+use crate::NumOfPages;
+use core::convert::TryFrom;
+use x86_64::structures::paging::frame::{PhysFrameRange, PhysFrameRangeInclusive};
+use x86_64::structures::paging::page::{PageRange, PageRangeInclusive};
+use x86_64::structures::paging::{Page, PageSize, PhysFrame};
+use x86_64::{PhysAddr, VirtAddr};
+
+/// Returns the range of `count` pages starting at `start`.
+///
+/// The end page is computed like [`AddrExt::checked_add_bytes`], so a range which goes past the
+/// end of the lower half continues in the higher half.
+///
+/// This function returns [`None`] if the end of the range is not a valid address. Note that this
+/// is the case for a range containing the last page of the address space, for which
+/// [`page_range_inclusive`] can be used instead.
+///
+/// ```rust
+/// use os_units::NumOfPages;
+/// use x86_64::structures::paging::{Page, Size4KiB};
+/// use x86_64::VirtAddr;
+///
+/// let start = Page::<Size4KiB>::containing_address(VirtAddr::new(0x1000));
+/// let r = os_units::page_range(start, NumOfPages::new(3)).unwrap();
+///
+/// assert_eq!(r.end.start_address(), VirtAddr::new(0x4000));
+/// ```
+#[must_use]
+pub fn page_range<T: PageSize>(start: Page<T>, count: NumOfPages<T>) -> Option<PageRange<T>> {
+    let end = start
+        .start_address()
+        .checked_add_bytes(count.checked_as_bytes()?)?;
+
+    Page::from_start_address(end)
+        .ok()
+        .map(|end| Page::range(start, end))
+}
+
+/// Returns the inclusive range of `count` pages starting at `start`.
+///
+/// This function returns [`None`] if `count` is zero, as an inclusive range has at least one
+/// page, or if the last page is not a valid address.
+#[must_use]
+pub fn page_range_inclusive<T: PageSize>(
+    start: Page<T>,
+    count: NumOfPages<T>,
+) -> Option<PageRangeInclusive<T>> {
+    let last = count.as_usize().checked_sub(1)?;
+    let last = start
+        .start_address()
+        .checked_add_bytes(NumOfPages::<T>::new(last).checked_as_bytes()?)?;
+
+    Page::from_start_address(last)
+        .ok()
+        .map(|last| Page::range_inclusive(start, last))
+}
+
+/// Returns the range of `count` frames starting at `start`.
+///
+/// This function returns [`None`] if the end of the range is not a valid physical address, i.e.
+/// if it does not fit in 52 bits. Note that this is the case for a range containing the last
+/// frame of the physical address space, for which [`frame_range_inclusive`] can be used instead.
+///
+/// ```rust
+/// use os_units::NumOfPages;
+/// use x86_64::structures::paging::{PhysFrame, Size4KiB};
+/// use x86_64::PhysAddr;
+///
+/// let start = PhysFrame::<Size4KiB>::containing_address(PhysAddr::new(0x1000));
+/// let r = os_units::frame_range(start, NumOfPages::new(3)).unwrap();
+///
+/// assert_eq!(r.end.start_address(), PhysAddr::new(0x4000));
+/// ```
+#[must_use]
+pub fn frame_range<T: PageSize>(
+    start: PhysFrame<T>,
+    count: NumOfPages<T>,
+) -> Option<PhysFrameRange<T>> {
+    let end = start
+        .start_address()
+        .checked_add_bytes(count.checked_as_bytes()?)?;
+
+    PhysFrame::from_start_address(end)
+        .ok()
+        .map(|end| PhysFrame::range(start, end))
+}
+
+/// Returns the inclusive range of `count` frames starting at `start`.
+///
+/// This function returns [`None`] if `count` is zero, as an inclusive range has at least one
+/// frame, or if the last frame is not a valid physical address.
+#[must_use]
+pub fn frame_range_inclusive<T: PageSize>(
+    start: PhysFrame<T>,
+    count: NumOfPages<T>,
+) -> Option<PhysFrameRangeInclusive<T>> {
+    let last = count.as_usize().checked_sub(1)?;
+    let last = start
+        .start_address()
+        .checked_add_bytes(NumOfPages::<T>::new(last).checked_as_bytes()?)?;
+
+    PhysFrame::from_start_address(last)
+        .ok()
+        .map(|last| PhysFrame::range_inclusive(start, last))
+}
+
+/// Counts the pages in the range. A range which goes past the end of the lower half of the
+/// address space does not count the pages in the non-canonical gap.
+///
+/// Fails with [`ConversionError::Overflow`] if the number does not fit in `usize`.
+impl<T: PageSize> TryFrom<PageRange<T>> for NumOfPages<T> {
+    type Error = ConversionError;
+
+    fn try_from(r: PageRange<T>) -> Result<Self, Self::Error> {
+        if r.is_empty() {
+            Ok(Self::zero())
+        } else {
+            pages_between(virt_distance(
+                r.start.start_address(),
+                r.end.start_address(),
+            ))
+        }
+    }
+}
+/// Counts the pages in the range. A range which goes past the end of the lower half of the
+/// address space does not count the pages in the non-canonical gap.
+///
+/// Fails with [`ConversionError::Overflow`] if the number does not fit in `usize`.
+impl<T: PageSize> TryFrom<PageRangeInclusive<T>> for NumOfPages<T> {
+    type Error = ConversionError;
+
+    fn try_from(r: PageRangeInclusive<T>) -> Result<Self, Self::Error> {
+        if r.is_empty() {
+            Ok(Self::zero())
+        } else {
+            pages_between::<T>(virt_distance(
+                r.start.start_address(),
+                r.end.start_address(),
+            ))?
+            .checked_add(NumOfPages::new(1))
+            .ok_or(ConversionError::Overflow)
+        }
+    }
+}
+/// Fails with [`ConversionError::Overflow`] if the number does not fit in `usize`.
+impl<T: PageSize> TryFrom<PhysFrameRange<T>> for NumOfPages<T> {
+    type Error = ConversionError;
+
+    fn try_from(r: PhysFrameRange<T>) -> Result<Self, Self::Error> {
+        if r.is_empty() {
+            Ok(Self::zero())
+        } else {
+            pages_between(phys_distance(
+                r.start.start_address(),
+                r.end.start_address(),
+            ))
+        }
+    }
+}
+/// Fails with [`ConversionError::Overflow`] if the number does not fit in `usize`.
+impl<T: PageSize> TryFrom<PhysFrameRangeInclusive<T>> for NumOfPages<T> {
+    type Error = ConversionError;
+
+    fn try_from(r: PhysFrameRangeInclusive<T>) -> Result<Self, Self::Error> {
+        if r.is_empty() {
+            Ok(Self::zero())
+        } else {
+            pages_between::<T>(phys_distance(
+                r.start.start_address(),
+                r.end.start_address(),
+            ))?
+            .checked_add(NumOfPages::new(1))
+            .ok_or(ConversionError::Overflow)
+        }
+    }
+}
+/// Returns the size of the pages in the range.
+///
+/// Fails with [`ConversionError::Overflow`] if the size does not fit in `usize`.
+impl<T: PageSize> TryFrom<PageRange<T>> for Bytes {
+    type Error = ConversionError;
+
+    fn try_from(r: PageRange<T>) -> Result<Self, Self::Error> {
+        pages_to_bytes(NumOfPages::try_from(r)?)
+    }
+}
+/// Returns the size of the pages in the range.
+///
+/// Fails with [`ConversionError::Overflow`] if the size does not fit in `usize`.
+impl<T: PageSize> TryFrom<PageRangeInclusive<T>> for Bytes {
+    type Error = ConversionError;
+
+    fn try_from(r: PageRangeInclusive<T>) -> Result<Self, Self::Error> {
+        pages_to_bytes(NumOfPages::try_from(r)?)
+    }
+}
+/// Returns the size of the frames in the range.
+///
+/// Fails with [`ConversionError::Overflow`] if the size does not fit in `usize`.
+impl<T: PageSize> TryFrom<PhysFrameRange<T>> for Bytes {
+    type Error = ConversionError;
+
+    fn try_from(r: PhysFrameRange<T>) -> Result<Self, Self::Error> {
+        pages_to_bytes(NumOfPages::try_from(r)?)
+    }
+}
+/// Returns the size of the frames in the range.
+///
+/// Fails with [`ConversionError::Overflow`] if the size does not fit in `usize`.
+impl<T: PageSize> TryFrom<PhysFrameRangeInclusive<T>> for Bytes {
+    type Error = ConversionError;
+
+    fn try_from(r: PhysFrameRangeInclusive<T>) -> Result<Self, Self::Error> {
+        pages_to_bytes(NumOfPages::try_from(r)?)
+    }
+}
+
+/// Returns `end - start`, where `start <= end`, skipping the non-canonical gap.
+pub(crate) fn virt_distance(start: VirtAddr, end: VirtAddr) -> u64 {
+    const GAP: u64 = 0xffff_0000_0000_0000;
+
+    let d = end.as_u64() - start.as_u64();
+
+    if end.as_u64() >= GAP && start.as_u64() < GAP {
+        d - GAP
+    } else {
+        d
+    }
+}
+
+pub(crate) fn phys_distance(start: PhysAddr, end: PhysAddr) -> u64 {
+    end.as_u64() - start.as_u64()
+}
+
+/// The shift and the mask of a page size, evaluated at compile time so that the conversions can
+/// shift and mask instead of dividing by `T::SIZE`.
+pub(crate) trait PageSizeExt: PageSize {
+    /// `log2(Self::SIZE)`.
+    ///
+    /// Using this with a page size which is not a power of two is a compile error.
+    const SHIFT: u32 = {
+        assert!(
+            Self::SIZE.is_power_of_two(),
+            "the page size is not a power of two"
+        );
+        Self::SIZE.trailing_zeros()
+    };
+
+    /// `Self::SIZE - 1`, the mask of the offset in a page.
+    const MASK: u64 = Self::SIZE - 1;
+}
+impl<T: PageSize> PageSizeExt for T {}
+
+/// Returns `T::SIZE` as `usize`, or [`None`] if it does not fit, e.g. [`Size2MiB`] on a target
+/// with 16-bit `usize`.
+///
+/// [`Size2MiB`]: x86_64::structures::paging::Size2MiB
+pub(crate) fn page_size<T: PageSize>() -> Option<usize> {
+    page_size_as::<T, usize>()
+}
+
+/// Returns `bytes / T::SIZE` and `bytes % T::SIZE`.
+///
+/// These are computed in `u64` so that a page size which does not fit in `usize` is not truncated.
+/// Both are at most `bytes`, so they fit in `usize`.
+pub(crate) fn div_rem_page_size<T: PageSize>(bytes: usize) -> (usize, usize) {
+    let b = bytes as u64;
+
+    #[allow(clippy::cast_possible_truncation)]
+    ((b >> T::SHIFT) as usize, (b & T::MASK) as usize)
+}
+
+// Generic over the integer type so that the narrow targets can be simulated in the tests.
+fn page_size_as<T: PageSize, U: TryFrom<u64>>() -> Option<U> {
+    U::try_from(T::SIZE).ok()
+}
+
+fn pages_between<T: PageSize>(distance: u64) -> Result<NumOfPages<T>, ConversionError> {
+    Ok(NumOfPages::new(usize::try_from(distance / T::SIZE)?))
+}
+
+fn pages_to_bytes<T: PageSize>(n: NumOfPages<T>) -> Result<Bytes, ConversionError> {
+    n.checked_as_bytes().ok_or(ConversionError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        div_rem_page_size, frame_range, frame_range_inclusive, page_range, page_range_inclusive,
+        page_size, page_size_as,
+    };
+    use crate::Bytes;
+    #[cfg(not(target_pointer_width = "64"))]
+    use crate::ConversionError;
+    use crate::NumOfPages;
+    #[cfg(not(target_pointer_width = "64"))]
+    use crate::PageIndex;
+    use core::convert::TryFrom;
+    #[cfg(not(target_pointer_width = "64"))]
+    use core::num::NonZeroUsize;
+    use x86_64::structures::paging::{Page, PageSize, PhysFrame, Size1GiB, Size2MiB, Size4KiB};
+    use x86_64::{PhysAddr, VirtAddr};
+
+    /// A page size which does not fit in `usize`, like [`Size2MiB`] on a 16-bit target.
+    #[cfg(not(target_pointer_width = "64"))]
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    enum Unrepresentable {}
+    #[cfg(not(target_pointer_width = "64"))]
+    impl PageSize for Unrepresentable {
+        const SIZE: u64 = usize::MAX as u64 + 1;
+        const SIZE_AS_DEBUG_STR: &'static str = "Unrepresentable";
+    }
+
+    fn page(addr: u64) -> Page<Size4KiB> {
+        Page::from_start_address(VirtAddr::new(addr)).unwrap()
+    }
+
+    fn frame(addr: u64) -> PhysFrame<Size4KiB> {
+        PhysFrame::from_start_address(PhysAddr::new(addr)).unwrap()
+    }
+
+    #[test]
+    fn page_range_of_pages() {
+        let r = page_range(page(0x1000), NumOfPages::new(3)).unwrap();
+
+        assert_eq!(r, Page::range(page(0x1000), page(0x4000)));
+        assert_eq!(r.count(), 3);
+    }
+
+    #[test]
+    fn page_range_zero_count() {
+        let r = page_range(page(0x1000), NumOfPages::zero()).unwrap();
+
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn page_range_of_huge_pages() {
+        let start = Page::<Size2MiB>::from_start_address(VirtAddr::new(0x20_0000)).unwrap();
+        let r = page_range(start, NumOfPages::new(2)).unwrap();
+
+        assert_eq!(r.end.start_address(), VirtAddr::new(0x60_0000));
+    }
+
+    #[test]
+    fn page_range_across_canonical_gap() {
+        let r = page_range(page(0x7fff_ffff_f000), NumOfPages::new(2)).unwrap();
+
+        assert_eq!(r.end, page(0xffff_8000_0000_1000));
+    }
+
+    #[test]
+    fn page_range_overflows_address_space() {
+        let r = page_range(page(0xffff_ffff_ffff_f000), NumOfPages::new(1));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn page_range_count_overflows() {
+        let r = page_range(page(0x1000), NumOfPages::new(usize::MAX));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn page_range_inclusive_of_pages() {
+        let r = page_range_inclusive(page(0x1000), NumOfPages::new(3)).unwrap();
+
+        assert_eq!(r, Page::range_inclusive(page(0x1000), page(0x3000)));
+        assert_eq!(r.count(), 3);
+    }
+
+    #[test]
+    fn page_range_inclusive_last_page() {
+        let r = page_range_inclusive(page(0xffff_ffff_ffff_f000), NumOfPages::new(1)).unwrap();
+
+        assert_eq!(r.end, page(0xffff_ffff_ffff_f000));
+    }
+
+    #[test]
+    fn page_range_inclusive_zero_count() {
+        let r = page_range_inclusive(page(0x1000), NumOfPages::zero());
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn page_range_inclusive_overflows_address_space() {
+        let r = page_range_inclusive(page(0xffff_ffff_ffff_f000), NumOfPages::new(2));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn frame_range_of_frames() {
+        let r = frame_range(frame(0x1000), NumOfPages::new(3)).unwrap();
+
+        assert_eq!(r, PhysFrame::range(frame(0x1000), frame(0x4000)));
+        assert_eq!(r.count(), 3);
+    }
+
+    #[test]
+    fn frame_range_zero_count() {
+        let r = frame_range(frame(0x1000), NumOfPages::zero()).unwrap();
+
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn frame_range_up_to_last_frame() {
+        let r = frame_range(frame(0xf_ffff_ffff_e000), NumOfPages::new(1)).unwrap();
+
+        assert_eq!(r.end, frame(0xf_ffff_ffff_f000));
+    }
+
+    #[test]
+    fn frame_range_past_physical_address_width() {
+        let r = frame_range(frame(0xf_ffff_ffff_f000), NumOfPages::new(1));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn frame_range_of_huge_frames_past_physical_address_width() {
+        let start = PhysFrame::<Size1GiB>::from_start_address(PhysAddr::new(0xf_ffff_c000_0000));
+        let r = frame_range(start.unwrap(), NumOfPages::new(1));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn frame_range_inclusive_of_frames() {
+        let r = frame_range_inclusive(frame(0x1000), NumOfPages::new(3)).unwrap();
+
+        assert_eq!(r, PhysFrame::range_inclusive(frame(0x1000), frame(0x3000)));
+        assert_eq!(r.count(), 3);
+    }
+
+    #[test]
+    fn frame_range_inclusive_last_frame() {
+        let r = frame_range_inclusive(frame(0xf_ffff_ffff_f000), NumOfPages::new(1)).unwrap();
+
+        assert_eq!(r.end, frame(0xf_ffff_ffff_f000));
+    }
+
+    #[test]
+    fn frame_range_inclusive_zero_count() {
+        let r = frame_range_inclusive(frame(0x1000), NumOfPages::zero());
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn frame_range_inclusive_past_physical_address_width() {
+        let r = frame_range_inclusive(frame(0xf_ffff_ffff_f000), NumOfPages::new(2));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn pages_from_page_range() {
+        let r = Page::range(page(0x1000), page(0x4000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::new(3)));
+        assert_eq!(Bytes::try_from(r), Ok(Bytes::new(0x3000)));
+    }
+
+    #[test]
+    fn pages_from_empty_page_range() {
+        let r = Page::range(page(0x4000), page(0x4000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::zero()));
+    }
+
+    #[test]
+    fn pages_from_reversed_page_range() {
+        let r = Page::range(page(0x4000), page(0x1000));
+
+        assert_eq!(Bytes::try_from(r), Ok(Bytes::zero()));
+    }
+
+    #[test]
+    fn pages_from_page_range_across_canonical_gap() {
+        let r = Page::range(page(0x7fff_ffff_f000), page(0xffff_8000_0000_1000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::new(2)));
+        assert_eq!(
+            NumOfPages::try_from(r).map(NumOfPages::as_usize),
+            Ok(r.count())
+        );
+    }
+
+    #[test]
+    fn pages_from_page_range_inclusive() {
+        let r = Page::range_inclusive(page(0x1000), page(0x1000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::new(1)));
+        assert_eq!(Bytes::try_from(r), Ok(Bytes::new(0x1000)));
+    }
+
+    #[test]
+    fn pages_from_empty_page_range_inclusive() {
+        let r = Page::range_inclusive(page(0x2000), page(0x1000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::zero()));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn pages_from_whole_address_space() {
+        let r = Page::range_inclusive(page(0), page(0xffff_ffff_ffff_f000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::new(1 << 36)));
+        assert_eq!(Bytes::try_from(r), Ok(Bytes::new(1 << 48)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn pages_from_whole_address_space() {
+        let r = Page::range_inclusive(page(0), page(0xffff_ffff_ffff_f000));
+
+        assert_eq!(NumOfPages::try_from(r), Err(ConversionError::Overflow));
+        assert_eq!(Bytes::try_from(r), Err(ConversionError::Overflow));
+    }
+
+    #[test]
+    fn pages_from_frame_range() {
+        let r = PhysFrame::range(frame(0x1000), frame(0x2000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::new(1)));
+        assert_eq!(Bytes::try_from(r), Ok(Bytes::new(0x1000)));
+    }
+
+    #[test]
+    fn pages_from_empty_frame_range() {
+        let r = PhysFrame::range(frame(0x1000), frame(0x1000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::zero()));
+        assert_eq!(Bytes::try_from(r), Ok(Bytes::zero()));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn pages_from_large_frame_range() {
+        let r = PhysFrame::range(frame(0), frame(0xf_ffff_ffff_f000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::new((1 << 40) - 1)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn pages_from_frame_range_inclusive() {
+        let r = PhysFrame::range_inclusive(frame(0), frame(0xf_ffff_ffff_f000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::new(1 << 40)));
+        assert_eq!(Bytes::try_from(r), Ok(Bytes::new(1 << 52)));
+    }
+
+    #[test]
+    fn pages_from_empty_frame_range_inclusive() {
+        let r = PhysFrame::range_inclusive(frame(0x2000), frame(0x1000));
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::zero()));
+    }
+
+    #[test]
+    fn huge_pages_from_frame_range_inclusive() {
+        let start = PhysFrame::<Size1GiB>::from_start_address(PhysAddr::new(0)).unwrap();
+        let end = PhysFrame::from_start_address(PhysAddr::new(0x1_4000_0000)).unwrap();
+        let r = PhysFrame::range_inclusive(start, end);
+
+        assert_eq!(NumOfPages::try_from(r), Ok(NumOfPages::new(6)));
+    }
+
+    #[test]
+    #[cfg(not(target_pointer_width = "16"))]
+    fn page_size_on_this_target() {
+        assert_eq!(page_size::<Size4KiB>(), Some(0x1000));
+        assert_eq!(page_size::<Size2MiB>(), Some(0x20_0000));
+        assert_eq!(page_size::<Size1GiB>(), Some(0x4000_0000));
+    }
+
+    // `u16` and `u32` stand in for `usize` of the 16-bit and 32-bit targets.
+    #[test]
+    fn page_size_on_narrow_targets() {
+        assert_eq!(page_size_as::<Size4KiB, u16>(), Some(0x1000));
+        assert_eq!(page_size_as::<Size2MiB, u16>(), None);
+        assert_eq!(page_size_as::<Size1GiB, u16>(), None);
+        assert_eq!(page_size_as::<Size1GiB, u32>(), Some(0x4000_0000));
+    }
+
+    #[test]
+    fn div_rem_by_page_size() {
+        assert_eq!(div_rem_page_size::<Size4KiB>(0x3123), (3, 0x123));
+        assert_eq!(div_rem_page_size::<Size4KiB>(0), (0, 0));
+        assert_eq!(
+            div_rem_page_size::<Size4KiB>(usize::MAX),
+            (usize::MAX >> 12, 0xfff)
+        );
+    }
+
+    // This is what `T::SIZE as usize` did on a 16-bit target.
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn truncating_cast_is_wrong_on_16bit() {
+        assert_eq!(Size2MiB::SIZE as u16, 0);
+        assert_eq!(Size1GiB::SIZE as u16, 0);
+    }
+
+    #[test]
+    #[cfg(not(target_pointer_width = "64"))]
+    fn unrepresentable_page_size() {
+        assert_eq!(page_size::<Unrepresentable>(), None);
+        assert_eq!(
+            div_rem_page_size::<Unrepresentable>(usize::MAX),
+            (0, usize::MAX)
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_pointer_width = "64"))]
+    fn bytes_to_unrepresentable_pages() {
+        assert_eq!(
+            Bytes::zero().as_num_of_pages::<Unrepresentable>(),
+            NumOfPages::zero()
+        );
+        assert_eq!(
+            Bytes::new(usize::MAX).as_num_of_pages::<Unrepresentable>(),
+            NumOfPages::new(1)
+        );
+        assert_eq!(
+            Bytes::new(0x1000).as_num_of_pages_exact::<Unrepresentable>(),
+            Err(ConversionError::NotAMultiple {
+                remainder: Bytes::new(0x1000)
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_pointer_width = "64"))]
+    fn unrepresentable_pages_to_bytes() {
+        let zero = NumOfPages::<Unrepresentable>::zero();
+        let one = NumOfPages::<Unrepresentable>::new(1);
+
+        assert_eq!(zero.checked_as_bytes(), Some(Bytes::zero()));
+        assert_eq!(one.checked_as_bytes(), None);
+        assert_eq!(one.as_layout(), None);
+        assert_eq!(NumOfPages::<Unrepresentable>::max_convertible(), zero);
+    }
+
+    #[test]
+    #[cfg(all(not(target_pointer_width = "64"), not(feature = "no-panic")))]
+    fn zero_unrepresentable_pages_as_bytes() {
+        assert_eq!(
+            NumOfPages::<Unrepresentable>::zero().as_bytes(),
+            Bytes::zero()
+        );
+        assert_eq!(
+            PageIndex::<Unrepresentable>::new(0).start_offset(),
+            Bytes::zero()
+        );
+    }
+
+    #[test]
+    #[cfg(all(not(target_pointer_width = "64"), not(feature = "no-panic")))]
+    #[should_panic(
+        expected = "NumOfPages to Bytes overflowed: the page size does not fit in `usize`"
+    )]
+    fn unrepresentable_page_as_bytes() {
+        let _ = NumOfPages::<Unrepresentable>::new(1).as_bytes();
+    }
+
+    #[test]
+    #[cfg(all(not(target_pointer_width = "64"), not(feature = "no-panic")))]
+    #[should_panic(
+        expected = "PageIndex to Bytes overflowed: the page size does not fit in `usize`"
+    )]
+    fn unrepresentable_page_start_offset() {
+        let _ = PageIndex::<Unrepresentable>::new(1).start_offset();
+    }
+
+    #[test]
+    #[cfg(not(target_pointer_width = "64"))]
+    fn unrepresentable_pages_spanning() {
+        let max = VirtAddr::new(usize::MAX as u64);
+
+        assert_eq!(
+            NumOfPages::<Unrepresentable>::spanning(VirtAddr::new(0x1ff0), Bytes::new(0x20)),
+            NumOfPages::new(1)
+        );
+        assert_eq!(
+            NumOfPages::<Unrepresentable>::spanning(max, Bytes::new(1)),
+            NumOfPages::new(1)
+        );
+        assert_eq!(
+            NumOfPages::<Unrepresentable>::spanning(max, Bytes::new(2)),
+            NumOfPages::new(2)
+        );
+        assert_eq!(
+            NumOfPages::<Unrepresentable>::spanning(max, Bytes::zero()),
+            NumOfPages::zero()
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_pointer_width = "64"))]
+    fn bytes_in_unrepresentable_pages() {
+        let pieces: Vec<_> = Bytes::new(0x3000)
+            .split_into_aligned::<Unrepresentable>(NonZeroUsize::new(3).unwrap())
+            .collect();
+        let offsets: Vec<_> = Bytes::new(0x10)
+            .iter_page_offsets::<Unrepresentable>()
+            .collect();
+        let (index, offset) =
+            PageIndex::<Unrepresentable>::from_byte_offset(Bytes::new(usize::MAX));
+
+        assert_eq!(pieces, [Bytes::zero(), Bytes::zero(), Bytes::new(0x3000)]);
+        assert_eq!(offsets, [Bytes::zero()]);
+        assert_eq!(index, PageIndex::new(0));
+        assert_eq!(offset.as_bytes(), Bytes::new(usize::MAX));
+        assert_eq!(
+            Bytes::new(0x1000).grow_by_factor_pages::<Unrepresentable>(
+                1,
+                NonZeroUsize::new(1).unwrap(),
+                Bytes::zero(),
+                Bytes::new(usize::MAX),
+            ),
+            NumOfPages::new(1)
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use crate::Bytes;
+use core::convert::TryFrom;
+
+impl Bytes {
+    /// Decodes the size of a 32-bit PCI BAR from `mask`, the value read back after writing all
+    /// ones to the BAR.
+    ///
+    /// Both memory and I/O BARs are supported. The upper 16 bits of an I/O BAR may read back as
+    /// zero, as some devices only decode 16-bit I/O addresses.
+    ///
+    /// This method returns [`None`] if the BAR is not implemented, i.e. `mask` has no address bits
+    /// set, or if the address bits are not contiguous from the top.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// // A 16 MiB prefetchable memory BAR.
+    /// assert_eq!(Bytes::from_bar32(0xff00_0008), Some(Bytes::new(0x100_0000)));
+    /// ```
+    #[must_use]
+    pub fn from_bar32(mask: u32) -> Option<Self> {
+        let address = if mask & 1 == 0 {
+            mask & !0xf
+        } else if mask & !0x3 != 0 && mask & 0xffff_0000 == 0 {
+            mask & !0x3 | 0xffff_0000
+        } else {
+            mask & !0x3
+        };
+
+        if address == 0 {
+            None
+        } else {
+            Self::from_bar_address_bits(u64::from(address) | 0xffff_ffff_0000_0000)
+        }
+    }
+
+    /// Decodes the size of a 64-bit memory PCI BAR from `mask`, the value read back from the BAR
+    /// pair after writing all ones to both, with the upper BAR in the upper 32 bits.
+    ///
+    /// This method returns [`None`] if the BAR is not implemented, if `mask` is of an I/O BAR, if
+    /// the address bits are not contiguous from the top, or if the size does not fit in `usize`.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// // A 256 MiB prefetchable memory BAR.
+    /// assert_eq!(
+    ///     Bytes::from_bar64(0xffff_ffff_f000_000c),
+    ///     Some(Bytes::new(0x1000_0000))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_bar64(mask: u64) -> Option<Self> {
+        if mask & 1 == 0 && mask & !0xf != 0 {
+            Self::from_bar_address_bits(mask & !0xf)
+        } else {
+            None
+        }
+    }
+
+    fn from_bar_address_bits(address: u64) -> Option<Self> {
+        let size = (!address).wrapping_add(1);
+
+        if size.is_power_of_two() {
+            Self::try_from(size).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value a memory PCI BAR of `self` bytes reads back after all ones are written to
+    /// it, with the flag bits cleared.
+    ///
+    /// The lower 32 bits are the mask of a 32-bit BAR, or of the lower BAR of a 64-bit BAR pair.
+    /// The upper 32 bits are the mask of the upper BAR.
+    ///
+    /// This method returns [`None`] if `self` is not a power of two or is smaller than 16 bytes,
+    /// the smallest size of a memory BAR.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::new(0x100_0000).to_bar_mask(), Some(0xffff_ffff_ff00_0000));
+    /// ```
+    #[must_use]
+    pub fn to_bar_mask(self) -> Option<u64> {
+        let size = self.as_usize();
+
+        if size.is_power_of_two() && size >= 16 {
+            Some(!(size as u64 - 1))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn from_bar32_memory() {
+        assert_eq!(Bytes::from_bar32(0xff00_0008), Some(Bytes::new(0x100_0000)));
+        assert_eq!(Bytes::from_bar32(0xffff_f000), Some(Bytes::new(0x1000)));
+        assert_eq!(Bytes::from_bar32(0xffff_fff0), Some(Bytes::new(0x10)));
+        assert_eq!(
+            Bytes::from_bar32(0x8000_0000),
+            Some(Bytes::new(0x8000_0000))
+        );
+    }
+
+    #[test]
+    fn from_bar32_io() {
+        assert_eq!(Bytes::from_bar32(0xffff_ff01), Some(Bytes::new(0x100)));
+        assert_eq!(Bytes::from_bar32(0x0000_ffe1), Some(Bytes::new(0x20)));
+        assert_eq!(Bytes::from_bar32(0xffff_fffd), Some(Bytes::new(4)));
+    }
+
+    #[test]
+    fn from_bar32_unimplemented() {
+        assert_eq!(Bytes::from_bar32(0), None);
+        assert_eq!(Bytes::from_bar32(0x8), None);
+        assert_eq!(Bytes::from_bar32(0x1), None);
+    }
+
+    #[test]
+    fn from_bar32_not_contiguous() {
+        assert_eq!(Bytes::from_bar32(0xff0f_0000), None);
+        assert_eq!(Bytes::from_bar32(0x0fff_f000), None);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn from_bar64() {
+        assert_eq!(
+            Bytes::from_bar64(0xffff_ffff_0000_000c),
+            Some(Bytes::new(0x1_0000_0000))
+        );
+        assert_eq!(
+            Bytes::from_bar64(0xffff_ffff_ff00_0004),
+            Some(Bytes::new(0x100_0000))
+        );
+        assert_eq!(
+            Bytes::from_bar64(0xffff_fff0_0000_000c),
+            Some(Bytes::new(0x10_0000_0000))
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn from_bar64_does_not_fit_in_usize() {
+        assert_eq!(Bytes::from_bar64(0xffff_ffff_0000_000c), None);
+        assert_eq!(
+            Bytes::from_bar64(0xffff_ffff_8000_000c),
+            Some(Bytes::new(0x8000_0000))
+        );
+    }
+
+    #[test]
+    fn from_bar64_invalid() {
+        assert_eq!(Bytes::from_bar64(0), None);
+        assert_eq!(Bytes::from_bar64(0xc), None);
+        assert_eq!(Bytes::from_bar64(0xffff_ffff_ffff_ff01), None);
+        assert_eq!(Bytes::from_bar64(0xffff_0fff_0000_000c), None);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn to_bar_mask() {
+        assert_eq!(Bytes::new(0x10).to_bar_mask(), Some(0xffff_ffff_ffff_fff0));
+        assert_eq!(
+            Bytes::new(0x1_0000_0000).to_bar_mask(),
+            Some(0xffff_ffff_0000_0000)
+        );
+        assert_eq!(Bytes::new(0x8).to_bar_mask(), None);
+        assert_eq!(Bytes::new(0x3000).to_bar_mask(), None);
+        assert_eq!(Bytes::zero().to_bar_mask(), None);
+    }
+
+    #[test]
+    fn bar_mask_round_trip() {
+        for shift in 4..usize::BITS.min(40) {
+            let size = Bytes::new(1 << shift);
+            let mask = size.to_bar_mask().unwrap();
+
+            assert_eq!(Bytes::from_bar64(mask | 0xc), Some(size));
+
+            if let Ok(s) = u32::try_from(size.as_usize()) {
+                assert_eq!(Bytes::from_bar32(!(s - 1) | 0x8), Some(size));
+            }
+        }
+    }
+}
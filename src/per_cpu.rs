@@ -0,0 +1,126 @@
+use crate::{Alignment, Bytes};
+use core::num::NonZeroUsize;
+
+impl Bytes {
+    /// Returns the distance between the per-CPU blocks of `self` bytes, i.e. `self` rounded up to
+    /// `align`.
+    ///
+    /// This method returns [`None`] if the stride does not fit in `usize`.
+    ///
+    /// ```rust
+    /// use os_units::{Alignment, Bytes};
+    ///
+    /// let cache_line = Alignment::new(64).unwrap();
+    ///
+    /// assert_eq!(Bytes::new(100).per_cpu_stride(cache_line), Some(Bytes::new(128)));
+    /// ```
+    #[must_use]
+    pub fn per_cpu_stride(self, align: Alignment) -> Option<Self> {
+        align.align_up(self)
+    }
+
+    /// Returns the bytes to reserve for `cpus` per-CPU blocks of `self` bytes, each aligned to
+    /// `align`.
+    ///
+    /// This method returns [`None`] if the total does not fit in `usize`.
+    ///
+    /// ```rust
+    /// use core::num::NonZeroUsize;
+    /// use os_units::{Alignment, Bytes};
+    ///
+    /// let cpus = NonZeroUsize::new(4).unwrap();
+    /// let page = Alignment::new(0x1000).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Bytes::new(0x1800).per_cpu_total(cpus, page),
+    ///     Some(Bytes::new(0x8000))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn per_cpu_total(self, cpus: NonZeroUsize, align: Alignment) -> Option<Self> {
+        self.per_cpu_stride(align)?
+            .as_usize()
+            .checked_mul(cpus.get())
+            .map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Alignment, Bytes};
+    use core::num::NonZeroUsize;
+
+    #[test]
+    fn per_cpu_stride_rounds_up() {
+        let line = Alignment::new(64).unwrap();
+        let page = Alignment::new(0x1000).unwrap();
+
+        assert_eq!(Bytes::new(64).per_cpu_stride(line), Some(Bytes::new(64)));
+        assert_eq!(Bytes::new(65).per_cpu_stride(line), Some(Bytes::new(128)));
+        assert_eq!(
+            Bytes::new(65).per_cpu_stride(page),
+            Some(Bytes::new(0x1000))
+        );
+        assert_eq!(Bytes::zero().per_cpu_stride(page), Some(Bytes::zero()));
+        assert_eq!(
+            Bytes::new(3).per_cpu_stride(Alignment::ONE),
+            Some(Bytes::new(3))
+        );
+    }
+
+    #[test]
+    fn per_cpu_stride_overflow() {
+        let line = Alignment::new(64).unwrap();
+
+        assert_eq!(Bytes::new(usize::MAX).per_cpu_stride(line), None);
+        assert_eq!(
+            Bytes::new(usize::MAX - 63).per_cpu_stride(line),
+            Some(Bytes::new(usize::MAX - 63))
+        );
+    }
+
+    #[test]
+    fn per_cpu_total_padding() {
+        let line = Alignment::new(64).unwrap();
+        let cpus = NonZeroUsize::new(8).unwrap();
+
+        assert_eq!(
+            Bytes::new(200).per_cpu_total(cpus, line),
+            Some(Bytes::new(256 * 8))
+        );
+        assert_eq!(
+            Bytes::new(200).per_cpu_total(NonZeroUsize::new(1).unwrap(), line),
+            Some(Bytes::new(256))
+        );
+    }
+
+    #[test]
+    fn per_cpu_total_many_cpus() {
+        let page = Alignment::new(0x1000).unwrap();
+        let cpus = NonZeroUsize::new(4096).unwrap();
+
+        assert_eq!(
+            Bytes::new(0x2001).per_cpu_total(cpus, page),
+            Some(Bytes::new(0x3000 * 4096))
+        );
+        assert_eq!(
+            Bytes::new(0x1000).per_cpu_total(NonZeroUsize::new(usize::MAX >> 12).unwrap(), page),
+            Some(Bytes::new((usize::MAX >> 12) << 12))
+        );
+    }
+
+    #[test]
+    fn per_cpu_total_overflow() {
+        let page = Alignment::new(0x1000).unwrap();
+
+        assert_eq!(
+            Bytes::new(0x1000)
+                .per_cpu_total(NonZeroUsize::new((usize::MAX >> 12) + 1).unwrap(), page),
+            None
+        );
+        assert_eq!(
+            Bytes::new(usize::MAX).per_cpu_total(NonZeroUsize::new(1).unwrap(), page),
+            None
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use crate::AddrExt;
+use crate::Bytes;
+use crate::RegionError;
+use core::convert::TryFrom;
+use x86_64::structures::paging::frame::PhysFrameRange;
+use x86_64::structures::paging::{PageSize, PhysFrame};
+use x86_64::PhysAddr;
+
+/// A half-open region of physical memory `start..start + size`.
+///
+/// The end of a region is always a valid address. This means a region cannot contain the last
+/// byte of the physical address space.
+///
+/// ```rust
+/// use os_units::{Bytes, PhysRegion};
+/// use x86_64::structures::paging::Size4KiB;
+/// use x86_64::PhysAddr;
+///
+/// let entry = PhysRegion::new(PhysAddr::new(0x1800), Bytes::new(0x3000)).unwrap();
+/// let usable = entry.trim_to_frame_boundaries::<Size4KiB>().unwrap();
+///
+/// assert_eq!(usable.start(), PhysAddr::new(0x2000));
+/// assert_eq!(usable.end(), PhysAddr::new(0x4000));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PhysRegion {
+    start: PhysAddr,
+    size: Bytes,
+}
+impl PhysRegion {
+    /// Creates a new region starting at `start` with `size` bytes.
+    ///
+    /// This method returns [`None`] if the end of the region is not a valid address.
+    #[must_use]
+    pub fn new(start: PhysAddr, size: Bytes) -> Option<Self> {
+        start.checked_add_bytes(size).map(|_| Self { start, size })
+    }
+
+    /// Creates a new region `start..end`.
+    ///
+    /// This method returns [`None`] if `end < start`.
+    #[must_use]
+    pub fn from_start_end(start: PhysAddr, end: PhysAddr) -> Option<Self> {
+        Bytes::between_phys(start, end).map(|size| Self { start, size })
+    }
+
+    /// Returns the first address of the region.
+    #[must_use]
+    pub const fn start(self) -> PhysAddr {
+        self.start
+    }
+
+    /// Returns the size of the region.
+    #[must_use]
+    pub const fn size(self) -> Bytes {
+        self.size
+    }
+
+    /// Returns the exclusive end of the region.
+    #[must_use]
+    pub fn end(self) -> PhysAddr {
+        // The constructors ensure that the sum is a valid address.
+        PhysAddr::new_truncate(self.start.as_u64() + self.size.as_usize() as u64)
+    }
+
+    /// Returns `true` if the region has no bytes.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.size.as_usize() == 0
+    }
+
+    /// Returns `true` if `start <= addr < end`.
+    #[must_use]
+    pub fn contains(self, addr: PhysAddr) -> bool {
+        self.start <= addr && addr < self.end()
+    }
+
+    /// Returns `true` if `self` and `other` share at least one byte.
+    ///
+    /// Touching regions and empty regions never overlap.
+    #[must_use]
+    pub fn overlaps(self, other: Self) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.start < other.end()
+            && other.start < self.end()
+    }
+
+    /// Returns the bytes shared by `self` and `other`, or [`None`] if they do not overlap.
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Option<Self> {
+        if self.overlaps(other) {
+            Self::from_start_end(self.start.max(other.start), self.end().min(other.end()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the range of the frames covering the region, rounding the start down and the end
+    /// up to the frame boundaries.
+    ///
+    /// This method returns [`None`] if the rounded-up end is not a valid address.
+    #[must_use]
+    pub fn frame_range<T: PageSize>(self) -> Option<PhysFrameRange<T>> {
+        let start = PhysFrame::containing_address(self.start);
+        let end = self
+            .end()
+            .as_u64()
+            .checked_add(T::SIZE - 1)
+            .map(|end| end & !(T::SIZE - 1))
+            .and_then(|end| PhysAddr::try_new(end).ok())?;
+
+        PhysFrame::from_start_address(end)
+            .ok()
+            .map(|end| PhysFrame::range(start, end))
+    }
+
+    /// Shrinks the region to the frame-aligned interior by rounding the start up and the end down
+    /// to multiples of `T::SIZE`. This is the region which can be carved out of `self` as whole
+    /// frames.
+    ///
+    /// This method returns [`None`] if no whole frame remains.
+    #[must_use]
+    pub fn trim_to_frame_boundaries<T: PageSize>(self) -> Option<Self> {
+        let start = self.start.as_u64().checked_add(T::SIZE - 1)? & !(T::SIZE - 1);
+        let end = self.end().align_down(T::SIZE).as_u64();
+
+        if start < end {
+            Self::from_start_end(PhysAddr::new(start), PhysAddr::new(end))
+        } else {
+            None
+        }
+    }
+}
+/// Fails with [`RegionError::InvalidAddress`] if the end of the region is not a valid address.
+impl TryFrom<(PhysAddr, Bytes)> for PhysRegion {
+    type Error = RegionError;
+
+    fn try_from((start, size): (PhysAddr, Bytes)) -> Result<Self, Self::Error> {
+        Self::new(start, size).ok_or(RegionError::InvalidAddress)
+    }
+}
+impl From<PhysRegion> for (PhysAddr, Bytes) {
+    fn from(r: PhysRegion) -> Self {
+        (r.start, r.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PhysRegion;
+    use crate::{Bytes, RegionError};
+    use core::convert::TryFrom;
+    use x86_64::structures::paging::{PhysFrame, Size2MiB, Size4KiB};
+    use x86_64::PhysAddr;
+
+    fn region(start: u64, end: u64) -> PhysRegion {
+        PhysRegion::from_start_end(PhysAddr::new(start), PhysAddr::new(end)).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        let r = PhysRegion::new(PhysAddr::new(0x1000), Bytes::new(0x800)).unwrap();
+
+        assert_eq!(r.start(), PhysAddr::new(0x1000));
+        assert_eq!(r.size(), Bytes::new(0x800));
+        assert_eq!(r.end(), PhysAddr::new(0x1800));
+    }
+
+    #[test]
+    fn new_up_to_top() {
+        let r = PhysRegion::new(PhysAddr::new(0xf_ffff_ffff_f000), Bytes::new(0xfff)).unwrap();
+
+        assert_eq!(r.end(), PhysAddr::new(0xf_ffff_ffff_ffff));
+    }
+
+    #[test]
+    fn new_past_top() {
+        let r = PhysRegion::new(PhysAddr::new(0xf_ffff_ffff_f000), Bytes::new(0x1000));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn from_start_end_reversed() {
+        let r = PhysRegion::from_start_end(PhysAddr::new(0x2000), PhysAddr::new(0x1000));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn contains_exclusive_end() {
+        let r = region(0x1000, 0x2000);
+
+        assert!(r.contains(PhysAddr::new(0x1000)));
+        assert!(r.contains(PhysAddr::new(0x1fff)));
+        assert!(!r.contains(PhysAddr::new(0x2000)));
+        assert!(!r.contains(PhysAddr::new(0xfff)));
+    }
+
+    #[test]
+    fn overlaps() {
+        assert!(region(0x1000, 0x3000).overlaps(region(0x2000, 0x4000)));
+        assert!(!region(0x1000, 0x3000).overlaps(region(0x2000, 0x2000)));
+    }
+
+    #[test]
+    fn intersection() {
+        let r = region(0x1000, 0x3000).intersection(region(0x2000, 0x4000));
+
+        assert_eq!(r, Some(region(0x2000, 0x3000)));
+    }
+
+    #[test]
+    fn intersection_contained() {
+        let r = region(0x1000, 0x4000).intersection(region(0x2000, 0x3000));
+
+        assert_eq!(r, Some(region(0x2000, 0x3000)));
+    }
+
+    #[test]
+    fn intersection_at_touching_boundaries() {
+        assert_eq!(
+            region(0x1000, 0x2000).intersection(region(0x2000, 0x3000)),
+            None
+        );
+        assert_eq!(
+            region(0x2000, 0x3000).intersection(region(0x1000, 0x2000)),
+            None
+        );
+    }
+
+    #[test]
+    fn frame_range_expands() {
+        let r = region(0x1800, 0x3001).frame_range::<Size4KiB>().unwrap();
+        let start = PhysFrame::containing_address(PhysAddr::new(0x1000));
+        let end = PhysFrame::containing_address(PhysAddr::new(0x4000));
+
+        assert_eq!(r, PhysFrame::range(start, end));
+    }
+
+    #[test]
+    fn frame_range_past_top() {
+        let r = region(0xf_ffff_ffff_f000, 0xf_ffff_ffff_f001);
+
+        assert_eq!(r.frame_range::<Size4KiB>(), None);
+    }
+
+    #[test]
+    fn trim_misaligned() {
+        let r = region(0x1800, 0x4800).trim_to_frame_boundaries::<Size4KiB>();
+
+        assert_eq!(r, Some(region(0x2000, 0x4000)));
+    }
+
+    #[test]
+    fn trim_aligned() {
+        let r = region(0x1000, 0x4000);
+
+        assert_eq!(r.trim_to_frame_boundaries::<Size4KiB>(), Some(r));
+    }
+
+    #[test]
+    fn trim_without_whole_frame() {
+        let r = region(0x1800, 0x2800).trim_to_frame_boundaries::<Size4KiB>();
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn trim_to_huge_frames() {
+        let r = region(0x1000, 0x60_1000).trim_to_frame_boundaries::<Size2MiB>();
+
+        assert_eq!(r, Some(region(0x20_0000, 0x60_0000)));
+    }
+
+    #[test]
+    fn try_from_tuple() {
+        let r = PhysRegion::try_from((PhysAddr::new(0x1000), Bytes::new(0x1000)));
+
+        assert_eq!(r, Ok(region(0x1000, 0x2000)));
+    }
+
+    #[test]
+    fn try_from_tuple_past_top() {
+        let r = PhysRegion::try_from((PhysAddr::new(0xf_ffff_ffff_f000), Bytes::new(0x1000)));
+
+        assert_eq!(r, Err(RegionError::InvalidAddress));
+    }
+
+    #[test]
+    fn into_tuple() {
+        let t: (PhysAddr, Bytes) = region(0x1000, 0x2000).into();
+
+        assert_eq!(t, (PhysAddr::new(0x1000), Bytes::new(0x1000)));
+    }
+}
@@ -0,0 +1,22 @@
+//! The decomposition of a count into powers of two, shared by [`Bytes`](crate::Bytes) and
+//! [`NumOfPages`](crate::NumOfPages).
+
+use core::iter;
+
+/// Returns an iterator over the power-of-two components of `n` in descending order. A component
+/// larger than `cap`, which must be a power of two, is split into components of `cap`.
+pub(crate) fn components(n: usize, cap: usize) -> impl Iterator<Item = usize> {
+    debug_assert!(cap.is_power_of_two(), "the cap is not a power of two");
+
+    let mut rest = n;
+
+    iter::from_fn(move || {
+        if rest == 0 {
+            None
+        } else {
+            let component = (1 << (usize::BITS - 1 - rest.leading_zeros())).min(cap);
+            rest -= component;
+            Some(component)
+        }
+    })
+}
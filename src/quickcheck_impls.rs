@@ -0,0 +1,122 @@
+use crate::Bytes;
+use crate::NumOfPages;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use quickcheck::Arbitrary;
+use quickcheck::Gen;
+use x86_64::structures::paging::PageSize;
+
+/// Shrinks toward zero by halving the value and by snapping it down to a 4 KiB page boundary.
+impl Arbitrary for Bytes {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(usize::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let v = self.as_usize();
+
+        Box::new(
+            shrink_candidates(
+                &[0, v & !0xfff, v / 2, (v / 2) & !0xfff, v.saturating_sub(1)],
+                v,
+            )
+            .map(Self::new),
+        )
+    }
+}
+
+/// Shrinks toward zero by halving the value.
+impl<T: PageSize + 'static> Arbitrary for NumOfPages<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(usize::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let v = self.as_usize();
+
+        Box::new(shrink_candidates(&[0, v / 2, v.saturating_sub(1)], v).map(Self::new))
+    }
+}
+
+/// Returns the distinct candidates smaller than `v`, keeping their order.
+fn shrink_candidates(candidates: &[usize], v: usize) -> impl Iterator<Item = usize> {
+    let mut distinct: Vec<usize> = Vec::with_capacity(candidates.len());
+
+    for &c in candidates {
+        if c < v && !distinct.contains(&c) {
+            distinct.push(c);
+        }
+    }
+
+    distinct.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use quickcheck::{quickcheck, Arbitrary, TestResult};
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    #[test]
+    fn shrink_bytes() {
+        let shrunk: Vec<_> = Bytes::new(0x3001).shrink().collect();
+
+        assert_eq!(
+            shrunk,
+            [
+                Bytes::zero(),
+                Bytes::new(0x3000),
+                Bytes::new(0x1800),
+                Bytes::new(0x1000),
+            ]
+        );
+    }
+
+    #[test]
+    fn shrink_zero_bytes() {
+        assert_eq!(Bytes::zero().shrink().count(), 0);
+    }
+
+    #[test]
+    fn shrink_num_of_pages() {
+        let shrunk: Vec<_> = NumOfPages::<Size4KiB>::new(10).shrink().collect();
+
+        assert_eq!(
+            shrunk,
+            [NumOfPages::zero(), NumOfPages::new(5), NumOfPages::new(9)]
+        );
+    }
+
+    quickcheck! {
+        fn pages_cover_bytes(bytes: Bytes) -> TestResult {
+            if bytes.as_usize() > usize::MAX - 0x1f_ffff {
+                return TestResult::discard();
+            }
+
+            let pages = bytes.as_num_of_pages::<Size2MiB>();
+
+            TestResult::from_bool(pages.checked_as_bytes().unwrap() >= bytes)
+        }
+
+        fn pages_do_not_over_allocate(bytes: Bytes) -> TestResult {
+            if bytes.as_usize() > usize::MAX - 0xfff {
+                return TestResult::discard();
+            }
+
+            let pages = bytes.as_num_of_pages::<Size4KiB>();
+
+            let slack = pages.checked_as_bytes().unwrap().checked_sub(bytes).unwrap();
+
+            TestResult::from_bool(slack < Bytes::new(0x1000))
+        }
+
+        fn bytes_of_pages_round_trip(pages: NumOfPages<Size4KiB>) -> TestResult {
+            if pages.as_usize() > usize::MAX / 0x1000 {
+                return TestResult::discard();
+            }
+
+            TestResult::from_bool(pages.checked_as_bytes().unwrap().as_num_of_pages() == pages)
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use crate::Alignment;
+use crate::Bytes;
+use crate::NumOfPages;
+use core::marker::PhantomData;
+use rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformInt, UniformSampler};
+use rand::distributions::{Distribution, Standard};
+use rand::{Rng, RngCore};
+use x86_64::structures::paging::PageSize;
+
+/// Samples `Bytes` uniformly from the whole range of `usize`.
+impl Distribution<Bytes> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Bytes {
+        Bytes::new(rng.gen())
+    }
+}
+
+/// Samples `NumOfPages` uniformly from the whole range of `usize`.
+impl<T: PageSize> Distribution<NumOfPages<T>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> NumOfPages<T> {
+        NumOfPages::new(rng.gen())
+    }
+}
+
+/// A sampler of [`Bytes`] uniformly distributed in a range.
+///
+/// This type is used through [`Rng::gen_range`] or
+/// [`Uniform`](rand::distributions::Uniform).
+///
+/// ```rust
+/// use os_units::Bytes;
+/// use rand::{rngs::SmallRng, Rng, SeedableRng};
+///
+/// let mut rng = SmallRng::seed_from_u64(0);
+/// let b = rng.gen_range(Bytes::new(0x1000)..Bytes::new(0x2000));
+///
+/// assert!(b >= Bytes::new(0x1000) && b < Bytes::new(0x2000));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct UniformBytes(UniformInt<usize>);
+impl SampleUniform for Bytes {
+    type Sampler = UniformBytes;
+}
+impl UniformSampler for UniformBytes {
+    type X = Bytes;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self(UniformInt::new(
+            low.borrow().as_usize(),
+            high.borrow().as_usize(),
+        ))
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self(UniformInt::new_inclusive(
+            low.borrow().as_usize(),
+            high.borrow().as_usize(),
+        ))
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        Bytes::new(self.0.sample(rng))
+    }
+}
+
+/// A sampler of [`NumOfPages`] uniformly distributed in a range.
+///
+/// This type is used through [`Rng::gen_range`] or
+/// [`Uniform`](rand::distributions::Uniform).
+#[derive(Copy, Clone, Debug)]
+pub struct UniformNumOfPages<T: PageSize> {
+    inner: UniformInt<usize>,
+    _marker: PhantomData<fn() -> T>,
+}
+impl<T: PageSize> SampleUniform for NumOfPages<T> {
+    type Sampler = UniformNumOfPages<T>;
+}
+impl<T: PageSize> UniformSampler for UniformNumOfPages<T> {
+    type X = NumOfPages<T>;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self {
+            inner: UniformInt::new(low.borrow().as_usize(), high.borrow().as_usize()),
+            _marker: PhantomData,
+        }
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        Self {
+            inner: UniformInt::new_inclusive(low.borrow().as_usize(), high.borrow().as_usize()),
+            _marker: PhantomData,
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        NumOfPages::new(self.inner.sample(rng))
+    }
+}
+
+impl Bytes {
+    /// Returns a multiple of `align` in `0..max` chosen uniformly at random with `rng`, e.g. for
+    /// a KASLR slide.
+    ///
+    /// This method is available with the `rand` feature. The multiple is sampled without bias
+    /// with [`Rng::gen_range`].
+    ///
+    /// This method returns [`None`] if there is no multiple of `align` below `max`, i.e. `max` is
+    /// zero.
+    ///
+    /// ```rust
+    /// use os_units::{Alignment, Bytes};
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    /// let page = Alignment::new(0x20_0000).unwrap();
+    ///
+    /// let slide = Bytes::random_aligned_below(&mut rng, Bytes::new(0x4000_0000), page).unwrap();
+    ///
+    /// assert!(slide < Bytes::new(0x4000_0000));
+    /// assert!(page.is_aligned(slide));
+    /// ```
+    #[must_use]
+    pub fn random_aligned_below<R: RngCore + ?Sized>(
+        rng: &mut R,
+        max: Bytes,
+        align: Alignment,
+    ) -> Option<Bytes> {
+        let count = max.as_usize().div_ceil(align.as_usize());
+
+        if count == 0 {
+            return None;
+        }
+
+        Some(Bytes::new(rng.gen_range(0..count) * align.as_usize()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Alignment;
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use rand::distributions::Uniform;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    #[test]
+    fn standard_bytes() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let values: Vec<Bytes> = (0..16).map(|_| rng.gen()).collect();
+
+        assert!(values.iter().any(|b| *b != values[0]));
+    }
+
+    #[test]
+    fn standard_num_of_pages() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let values: Vec<NumOfPages<Size4KiB>> = (0..16).map(|_| rng.gen()).collect();
+
+        assert!(values.iter().any(|n| *n != values[0]));
+    }
+
+    #[test]
+    fn bytes_in_range() {
+        let mut rng = SmallRng::seed_from_u64(2);
+
+        for _ in 0..1000 {
+            let b = rng.gen_range(Bytes::new(0x1000)..Bytes::new(0x2000));
+
+            assert!(b >= Bytes::new(0x1000));
+            assert!(b < Bytes::new(0x2000));
+        }
+    }
+
+    #[test]
+    fn bytes_in_inclusive_range() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let d = Uniform::new_inclusive(Bytes::new(3), Bytes::new(4));
+        let mut seen = [false; 2];
+
+        for _ in 0..1000 {
+            let b = rng.sample(d);
+
+            assert!(b == Bytes::new(3) || b == Bytes::new(4));
+            seen[b.as_usize() - 3] = true;
+        }
+
+        assert_eq!(seen, [true, true]);
+    }
+
+    #[test]
+    fn num_of_pages_in_range() {
+        let mut rng = SmallRng::seed_from_u64(4);
+
+        for _ in 0..1000 {
+            let n = rng.gen_range(NumOfPages::<Size2MiB>::new(1)..=NumOfPages::new(512));
+
+            assert!(n >= NumOfPages::new(1));
+            assert!(n <= NumOfPages::new(512));
+        }
+    }
+
+    #[test]
+    fn random_aligned_below_is_aligned_and_below_max() {
+        let mut rng = SmallRng::seed_from_u64(5);
+        let align = Alignment::new(0x20_0000).unwrap();
+        let max = Bytes::new(0x4000_0000);
+
+        for _ in 0..1000 {
+            let b = Bytes::random_aligned_below(&mut rng, max, align).unwrap();
+
+            assert!(b < max);
+            assert!(align.is_aligned(b));
+        }
+    }
+
+    #[test]
+    fn random_aligned_below_is_uniform() {
+        let mut rng = SmallRng::seed_from_u64(6);
+        let align = Alignment::new(0x1000).unwrap();
+        let mut counts = [0_u32; 8];
+
+        for _ in 0..8000 {
+            let b = Bytes::random_aligned_below(&mut rng, Bytes::new(0x8000), align).unwrap();
+
+            counts[b.as_usize() >> 12] += 1;
+        }
+
+        assert!(
+            counts.iter().all(|c| (800..1200).contains(c)),
+            "{:?}",
+            counts
+        );
+    }
+
+    #[test]
+    fn random_aligned_below_partial_last_multiple() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let align = Alignment::new(0x1000).unwrap();
+        let mut seen = [false; 3];
+
+        for _ in 0..1000 {
+            let b = Bytes::random_aligned_below(&mut rng, Bytes::new(0x2001), align).unwrap();
+
+            seen[b.as_usize() >> 12] = true;
+        }
+
+        assert_eq!(seen, [true, true, true]);
+    }
+
+    #[test]
+    fn random_aligned_below_max_smaller_than_align() {
+        let mut rng = SmallRng::seed_from_u64(8);
+        let align = Alignment::new(0x1000).unwrap();
+
+        assert_eq!(
+            Bytes::random_aligned_below(&mut rng, Bytes::new(0xfff), align),
+            Some(Bytes::zero())
+        );
+        assert_eq!(
+            Bytes::random_aligned_below(&mut rng, Bytes::zero(), align),
+            None
+        );
+    }
+
+    #[test]
+    fn random_aligned_below_whole_range() {
+        let mut rng = SmallRng::seed_from_u64(9);
+        let align = Alignment::new(1 << (usize::BITS - 1)).unwrap();
+
+        for _ in 0..100 {
+            let b = Bytes::random_aligned_below(&mut rng, Bytes::new(usize::MAX), align).unwrap();
+
+            assert!(b == Bytes::zero() || b == align.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,439 @@
+use crate::Bytes;
+use crate::NumOfPages;
+use core::iter::FusedIterator;
+use core::ops::Range;
+use x86_64::structures::paging::PageSize;
+
+/// An iterator over each byte offset in the half-open range `start..end`.
+///
+/// This is the stable equivalent of iterating `Range<Bytes>`, which requires the unstable
+/// `core::iter::Step` trait.
+///
+/// ```rust
+/// use os_units::{Bytes, BytesRange};
+///
+/// let offsets: Vec<_> = BytesRange::from(Bytes::zero()..Bytes::new(0x3000))
+///     .step_by(0x1000)
+///     .collect();
+///
+/// assert_eq!(offsets, [Bytes::new(0), Bytes::new(0x1000), Bytes::new(0x2000)]);
+/// ```
+// Like `Range`, an iterator should not be `Copy`.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BytesRange {
+    start: Bytes,
+    end: Bytes,
+}
+
+/// An iterator over each number of pages in the half-open range `start..end`.
+///
+/// This is the stable equivalent of iterating `Range<NumOfPages<T>>`. The yielded items keep the
+/// page size `T`.
+///
+/// ```rust
+/// use os_units::{NumOfPages, NumOfPagesRange};
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// let count = NumOfPages::<Size4KiB>::new(3);
+/// let pages: Vec<_> = NumOfPagesRange::from(NumOfPages::zero()..count).collect();
+///
+/// assert_eq!(pages, [NumOfPages::new(0), NumOfPages::new(1), NumOfPages::new(2)]);
+/// ```
+// Like `Range`, an iterator should not be `Copy`.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NumOfPagesRange<T: PageSize> {
+    start: NumOfPages<T>,
+    end: NumOfPages<T>,
+}
+/// Implements the constructor, the accessors, and the iterator traits of a range whose items are
+/// created by `new` and read by `as_usize`.
+///
+/// The generic parameters are written in brackets like `const_op_impl`, e.g.
+/// `impl[T: PageSize] NumOfPagesRange<T> of NumOfPages<T>`.
+macro_rules! range_impl {
+    (impl $([$($g:tt)*])? $range:ident $(<$p:ident>)? of $item:ty) => {
+        impl<$($($g)*)?> $range$(<$p>)? {
+            /// Creates a new instance iterating `start..end`. The range is empty if
+            /// `start >= end`.
+            #[must_use]
+            pub const fn new(start: $item, end: $item) -> Self {
+                Self { start, end }
+            }
+
+            /// Returns the next value to be yielded from the front.
+            #[must_use]
+            pub const fn start(&self) -> $item {
+                self.start
+            }
+
+            /// Returns the exclusive end of the range.
+            #[must_use]
+            pub const fn end(&self) -> $item {
+                self.end
+            }
+
+            fn remaining(&self) -> usize {
+                self.end.as_usize().saturating_sub(self.start.as_usize())
+            }
+        }
+        impl<$($($g)*)?> From<Range<$item>> for $range$(<$p>)? {
+            fn from(r: Range<$item>) -> Self {
+                Self::new(r.start, r.end)
+            }
+        }
+        impl<$($($g)*)?> Iterator for $range$(<$p>)? {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.nth(0)
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                if n < self.remaining() {
+                    let v = self.start.as_usize() + n;
+                    self.start = <$item>::new(v + 1);
+                    Some(<$item>::new(v))
+                } else {
+                    self.start = self.end.max(self.start);
+                    None
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n = self.remaining();
+                (n, Some(n))
+            }
+        }
+        impl<$($($g)*)?> DoubleEndedIterator for $range$(<$p>)? {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.nth_back(0)
+            }
+
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                if n < self.remaining() {
+                    self.end = <$item>::new(self.end.as_usize() - (n + 1));
+                    Some(self.end)
+                } else {
+                    self.end = self.start.min(self.end);
+                    None
+                }
+            }
+        }
+        impl<$($($g)*)?> ExactSizeIterator for $range$(<$p>)? {}
+        impl<$($($g)*)?> FusedIterator for $range$(<$p>)? {}
+    };
+}
+
+range_impl!(impl BytesRange of Bytes);
+range_impl!(impl[T: PageSize] NumOfPagesRange<T> of NumOfPages<T>);
+
+/// Extension methods for `Range<Bytes>`, which has few methods on stable Rust as `Bytes` does not
+/// implement `core::iter::Step`.
+///
+/// A range whose start is greater than its end is treated as empty.
+///
+/// ```rust
+/// use os_units::{ByteRangeExt, Bytes};
+///
+/// let r = Bytes::new(0x1800)..Bytes::new(0x3800);
+///
+/// assert_eq!(r.len(), Bytes::new(0x2000));
+/// assert_eq!(
+///     r.intersect(&(Bytes::new(0x3000)..Bytes::new(0x5000))),
+///     Some(Bytes::new(0x3000)..Bytes::new(0x3800))
+/// );
+/// ```
+// `Range::is_empty` is inherent, so the trait does not need its own, which would be shadowed.
+#[allow(clippy::len_without_is_empty)]
+pub trait ByteRangeExt {
+    /// Returns the number of bytes in the range, or zero if the range is empty.
+    fn len(&self) -> Bytes;
+
+    /// Returns `true` if every byte of `other` is in `self`. An empty `other` is always
+    /// contained.
+    fn contains_range(&self, other: &Range<Bytes>) -> bool;
+
+    /// Returns the bytes shared by `self` and `other`, or [`None`] if there is no such byte.
+    fn intersect(&self, other: &Range<Bytes>) -> Option<Range<Bytes>>;
+
+    /// Returns the range of the page indices covering the range, rounding the start down and the
+    /// end up to the page boundaries.
+    fn pages<T: PageSize>(&self) -> Range<NumOfPages<T>>;
+}
+impl ByteRangeExt for Range<Bytes> {
+    fn len(&self) -> Bytes {
+        Bytes::new(self.end.as_usize().saturating_sub(self.start.as_usize()))
+    }
+
+    fn contains_range(&self, other: &Range<Bytes>) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    fn intersect(&self, other: &Range<Bytes>) -> Option<Range<Bytes>> {
+        let r = self.start.max(other.start)..self.end.min(other.end);
+
+        if r.is_empty() {
+            None
+        } else {
+            Some(r)
+        }
+    }
+
+    fn pages<T: PageSize>(&self) -> Range<NumOfPages<T>> {
+        // Not larger than `self.start`, so it fits in `usize`.
+        #[allow(clippy::cast_possible_truncation)]
+        let start = (self.start.as_usize() as u64 / T::SIZE) as usize;
+
+        NumOfPages::new(start)..self.end.as_num_of_pages()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteRangeExt;
+    use super::BytesRange;
+    use super::NumOfPagesRange;
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    #[test]
+    fn forward() {
+        let r = BytesRange::new(Bytes::new(3), Bytes::new(6));
+        let v: Vec<_> = r.collect();
+
+        assert_eq!(v, [Bytes::new(3), Bytes::new(4), Bytes::new(5)]);
+    }
+
+    #[test]
+    fn backward() {
+        let r = BytesRange::new(Bytes::new(3), Bytes::new(6));
+        let v: Vec<_> = r.rev().collect();
+
+        assert_eq!(v, [Bytes::new(5), Bytes::new(4), Bytes::new(3)]);
+    }
+
+    #[test]
+    fn both_ends() {
+        let mut r = BytesRange::new(Bytes::new(0), Bytes::new(3));
+
+        assert_eq!(r.next(), Some(Bytes::new(0)));
+        assert_eq!(r.next_back(), Some(Bytes::new(2)));
+        assert_eq!(r.next(), Some(Bytes::new(1)));
+        assert_eq!(r.next_back(), None);
+        assert_eq!(r.next(), None);
+    }
+
+    #[test]
+    fn count() {
+        let r = BytesRange::from(Bytes::zero()..Bytes::new(0x1000));
+
+        assert_eq!(r.len(), 0x1000);
+        assert_eq!(r.count(), 0x1000);
+    }
+
+    #[test]
+    fn step_by_page() {
+        let r = BytesRange::from(Bytes::zero()..Bytes::new(0x2001));
+        let v: Vec<_> = r.step_by(0x1000).collect();
+
+        assert_eq!(v, [Bytes::new(0), Bytes::new(0x1000), Bytes::new(0x2000)]);
+    }
+
+    #[test]
+    fn step_by_page_backward() {
+        let r = BytesRange::from(Bytes::zero()..Bytes::new(0x3000));
+        let v: Vec<_> = r.step_by(0x1000).rev().collect();
+
+        assert_eq!(v, [Bytes::new(0x2000), Bytes::new(0x1000), Bytes::new(0)]);
+    }
+
+    #[test]
+    fn empty() {
+        let mut r = BytesRange::new(Bytes::new(3), Bytes::new(3));
+
+        assert_eq!(r.len(), 0);
+        assert_eq!(r.next(), None);
+    }
+
+    #[test]
+    fn reversed_is_empty() {
+        let mut r = BytesRange::new(Bytes::new(5), Bytes::new(3));
+
+        assert_eq!(r.len(), 0);
+        assert_eq!(r.next(), None);
+        assert_eq!(r.next_back(), None);
+    }
+
+    #[test]
+    fn nth_past_end() {
+        let mut r = BytesRange::new(Bytes::new(0), Bytes::new(3));
+
+        assert_eq!(r.nth(5), None);
+        assert_eq!(r.next(), None);
+    }
+
+    #[test]
+    fn up_to_usize_max() {
+        let mut r = BytesRange::new(Bytes::new(usize::MAX - 1), Bytes::new(usize::MAX));
+
+        assert_eq!(r.next(), Some(Bytes::new(usize::MAX - 1)));
+        assert_eq!(r.next(), None);
+    }
+
+    #[test]
+    fn pages_forward() {
+        let r = NumOfPagesRange::<Size4KiB>::new(NumOfPages::new(1), NumOfPages::new(3));
+        let v: Vec<_> = r.collect();
+
+        assert_eq!(v, [NumOfPages::new(1), NumOfPages::new(2)]);
+    }
+
+    #[test]
+    fn pages_rev() {
+        let r = NumOfPagesRange::<Size2MiB>::from(NumOfPages::zero()..NumOfPages::new(3));
+        let v: Vec<_> = r.rev().collect();
+
+        assert_eq!(
+            v,
+            [NumOfPages::new(2), NumOfPages::new(1), NumOfPages::new(0)]
+        );
+    }
+
+    #[test]
+    fn pages_empty() {
+        let mut r = NumOfPagesRange::<Size4KiB>::new(NumOfPages::new(2), NumOfPages::new(2));
+
+        assert_eq!(r.len(), 0);
+        assert_eq!(r.next(), None);
+    }
+
+    #[test]
+    fn pages_single() {
+        let mut r = NumOfPagesRange::<Size4KiB>::new(NumOfPages::new(2), NumOfPages::new(3));
+
+        assert_eq!(r.len(), 1);
+        assert_eq!(r.next(), Some(NumOfPages::new(2)));
+        assert_eq!(r.next(), None);
+    }
+
+    #[test]
+    fn pages_single_rev() {
+        let mut r = NumOfPagesRange::<Size4KiB>::new(NumOfPages::new(2), NumOfPages::new(3));
+
+        assert_eq!(r.next_back(), Some(NumOfPages::new(2)));
+        assert_eq!(r.next_back(), None);
+    }
+
+    #[test]
+    fn pages_reversed_is_empty() {
+        let mut r = NumOfPagesRange::<Size4KiB>::new(NumOfPages::new(3), NumOfPages::new(1));
+
+        assert_eq!(r.len(), 0);
+        assert_eq!(r.next_back(), None);
+    }
+
+    #[test]
+    fn range_len() {
+        let r = Bytes::new(3)..Bytes::new(8);
+
+        assert_eq!(r.len(), Bytes::new(5));
+    }
+
+    #[test]
+    fn range_len_empty() {
+        let r = Bytes::new(3)..Bytes::new(3);
+
+        assert_eq!(r.len(), Bytes::zero());
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn range_len_reversed() {
+        let r = Bytes::new(8)..Bytes::new(3);
+
+        assert_eq!(r.len(), Bytes::zero());
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn range_contains_range() {
+        let r = Bytes::new(4)..Bytes::new(8);
+
+        assert!(r.contains_range(&(Bytes::new(4)..Bytes::new(8))));
+        assert!(r.contains_range(&(Bytes::new(5)..Bytes::new(7))));
+        assert!(!r.contains_range(&(Bytes::new(5)..Bytes::new(9))));
+        assert!(!r.contains_range(&(Bytes::new(3)..Bytes::new(5))));
+    }
+
+    #[test]
+    fn range_contains_empty_range() {
+        let r = Bytes::new(4)..Bytes::new(8);
+
+        assert!(r.contains_range(&(Bytes::new(20)..Bytes::new(20))));
+        assert!(r.contains_range(&(Bytes::new(20)..Bytes::new(10))));
+    }
+
+    #[test]
+    fn empty_range_contains_range() {
+        let r = Bytes::new(8)..Bytes::new(4);
+
+        assert!(!r.contains_range(&(Bytes::new(5)..Bytes::new(6))));
+    }
+
+    #[test]
+    fn range_intersect() {
+        let r = Bytes::new(4)..Bytes::new(8);
+
+        assert_eq!(
+            r.intersect(&(Bytes::new(6)..Bytes::new(10))),
+            Some(Bytes::new(6)..Bytes::new(8))
+        );
+    }
+
+    #[test]
+    fn range_intersect_touching() {
+        let r = Bytes::new(4)..Bytes::new(8);
+
+        assert_eq!(r.intersect(&(Bytes::new(8)..Bytes::new(10))), None);
+    }
+
+    #[test]
+    fn range_intersect_reversed() {
+        let r = Bytes::new(4)..Bytes::new(8);
+
+        assert_eq!(r.intersect(&(Bytes::new(7)..Bytes::new(5))), None);
+    }
+
+    #[test]
+    fn range_pages() {
+        let r = Bytes::new(0x1800)..Bytes::new(0x3001);
+
+        assert_eq!(
+            r.pages::<Size4KiB>(),
+            NumOfPages::new(1)..NumOfPages::new(4)
+        );
+    }
+
+    #[test]
+    fn range_pages_aligned() {
+        let r = Bytes::new(0x20_0000)..Bytes::new(0x60_0000);
+
+        assert_eq!(
+            r.pages::<Size2MiB>(),
+            NumOfPages::new(1)..NumOfPages::new(3)
+        );
+    }
+
+    #[test]
+    fn range_pages_empty() {
+        let r = Bytes::new(0x1000)..Bytes::new(0x1000);
+
+        assert_eq!(
+            r.pages::<Size4KiB>(),
+            NumOfPages::new(1)..NumOfPages::new(1)
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{ByteRegion, Bytes, NumOfPages, PhysRegion};
+use x86_64::structures::paging::Size4KiB;
+
+/// The totals of a memory map, returned by [`summarize_regions`] and
+/// [`summarize_phys_regions`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RegionSummary {
+    /// The sum of the sizes of the regions.
+    pub total: Bytes,
+    /// The size of the largest region, or zero if there are no regions.
+    pub largest: Bytes,
+    /// The number of the regions, including the empty ones.
+    pub count: usize,
+    /// The sum of the numbers of the whole 4 KiB frames in each region.
+    pub frames: NumOfPages<Size4KiB>,
+}
+impl RegionSummary {
+    fn empty() -> Self {
+        Self {
+            total: Bytes::zero(),
+            largest: Bytes::zero(),
+            count: 0,
+            frames: NumOfPages::zero(),
+        }
+    }
+
+    fn checked_add(self, size: Bytes, frames: Bytes) -> Option<Self> {
+        Some(Self {
+            total: self.total.checked_add(size)?,
+            largest: self.largest.max(size),
+            count: self.count.checked_add(1)?,
+            frames: self
+                .frames
+                .checked_add(frames.as_num_of_pages::<Size4KiB>())?,
+        })
+    }
+}
+impl Default for RegionSummary {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Returns the total size, the largest size, the number, and the number of the whole 4 KiB frames
+/// of `regions` in one pass.
+///
+/// The regions are summed as they are. Overlapping regions are counted as many times as they
+/// overlap, so call [`coalesce_regions`](crate::coalesce_regions) first if the map may contain
+/// overlaps. A frame is counted only if it lies entirely in one region, so two touching regions
+/// which share a frame do not count it.
+///
+/// This function returns [`None`] if the total size or the number of the frames does not fit in
+/// `usize`, e.g. if a 32-bit kernel is given more than 4 GiB of memory.
+///
+/// ```rust
+/// use os_units::{summarize_regions, ByteRegion, Bytes, NumOfPages};
+///
+/// let r = |s, e| ByteRegion::from_start_end(Bytes::new(s), Bytes::new(e)).unwrap();
+/// let s = summarize_regions([r(0, 0x9_f000), r(0x10_0000, 0x800_0000)]).unwrap();
+///
+/// assert_eq!(s.total, Bytes::new(0x7f9_f000));
+/// assert_eq!(s.largest, Bytes::new(0x7f0_0000));
+/// assert_eq!(s.count, 2);
+/// assert_eq!(s.frames, NumOfPages::new(0x7f9f));
+/// ```
+#[must_use]
+pub fn summarize_regions(regions: impl IntoIterator<Item = ByteRegion>) -> Option<RegionSummary> {
+    regions
+        .into_iter()
+        .try_fold(RegionSummary::empty(), |s, r| {
+            let frames = r
+                .shrink_to_pages::<Size4KiB>()
+                .map_or(Bytes::zero(), ByteRegion::size);
+
+            s.checked_add(r.size(), frames)
+        })
+}
+
+/// Like [`summarize_regions`], but over [`PhysRegion`]s.
+///
+/// ```rust
+/// use os_units::{summarize_phys_regions, Bytes, NumOfPages, PhysRegion};
+/// use x86_64::PhysAddr;
+///
+/// let r = PhysRegion::new(PhysAddr::new(0x800), Bytes::new(0x2000)).unwrap();
+/// let s = summarize_phys_regions([r]).unwrap();
+///
+/// assert_eq!(s.total, Bytes::new(0x2000));
+/// assert_eq!(s.frames, NumOfPages::new(1));
+/// ```
+#[must_use]
+pub fn summarize_phys_regions(
+    regions: impl IntoIterator<Item = PhysRegion>,
+) -> Option<RegionSummary> {
+    regions
+        .into_iter()
+        .try_fold(RegionSummary::empty(), |s, r| {
+            let frames = r
+                .trim_to_frame_boundaries::<Size4KiB>()
+                .map_or(Bytes::zero(), PhysRegion::size);
+
+            s.checked_add(r.size(), frames)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{summarize_phys_regions, summarize_regions, RegionSummary};
+    use crate::{ByteRegion, Bytes, NumOfPages, PhysRegion};
+    use x86_64::PhysAddr;
+
+    fn r(start: usize, end: usize) -> ByteRegion {
+        ByteRegion::from_start_end(Bytes::new(start), Bytes::new(end)).unwrap()
+    }
+
+    #[test]
+    fn empty_map() {
+        let s = summarize_regions([]).unwrap();
+
+        assert_eq!(s, RegionSummary::default());
+        assert_eq!(s.total, Bytes::zero());
+        assert_eq!(s.largest, Bytes::zero());
+        assert_eq!(s.count, 0);
+        assert_eq!(s.frames, NumOfPages::zero());
+    }
+
+    #[test]
+    fn disjoint() {
+        let s =
+            summarize_regions([r(0x1000, 0x3000), r(0x8000, 0xc000), r(0x4000, 0x5000)]).unwrap();
+
+        assert_eq!(s.total, Bytes::new(0x7000));
+        assert_eq!(s.largest, Bytes::new(0x4000));
+        assert_eq!(s.count, 3);
+        assert_eq!(s.frames, NumOfPages::new(7));
+    }
+
+    #[test]
+    fn partial_frames_are_not_counted() {
+        let s = summarize_regions([r(0x800, 0x2800), r(0x3000, 0x3800)]).unwrap();
+
+        assert_eq!(s.total, Bytes::new(0x2800));
+        assert_eq!(s.largest, Bytes::new(0x2000));
+        assert_eq!(s.frames, NumOfPages::new(1));
+    }
+
+    #[test]
+    fn empty_regions_are_counted() {
+        let s = summarize_regions([r(0x1000, 0x1000), r(0x1000, 0x2000)]).unwrap();
+
+        assert_eq!(s.count, 2);
+        assert_eq!(s.total, Bytes::new(0x1000));
+        assert_eq!(s.frames, NumOfPages::new(1));
+    }
+
+    #[test]
+    fn overlapping_regions_are_counted_twice() {
+        let s = summarize_regions([r(0, 0x3000), r(0x1000, 0x4000)]).unwrap();
+
+        assert_eq!(s.total, Bytes::new(0x6000));
+        assert_eq!(s.count, 2);
+        assert_eq!(s.frames, NumOfPages::new(6));
+    }
+
+    #[test]
+    fn total_up_to_max() {
+        let s = summarize_regions([r(0, usize::MAX - 1), r(0, 1)]).unwrap();
+
+        assert_eq!(s.total, Bytes::new(usize::MAX));
+        assert_eq!(s.largest, Bytes::new(usize::MAX - 1));
+        assert_eq!(s.frames, NumOfPages::new(usize::MAX >> 12));
+    }
+
+    #[test]
+    fn total_overflow() {
+        assert_eq!(summarize_regions([r(0, usize::MAX), r(0, 1)]), None);
+        assert_eq!(
+            summarize_regions([r(0, usize::MAX), r(0, usize::MAX)]),
+            None
+        );
+    }
+
+    #[test]
+    fn from_iterator_adaptor() {
+        let map = [r(0, 0x1000), r(0x2000, 0x2800), r(0x4000, 0x6000)];
+        let s = summarize_regions(
+            map.iter()
+                .copied()
+                .filter(|r| r.size() >= Bytes::new(0x1000)),
+        )
+        .unwrap();
+
+        assert_eq!(s.count, 2);
+        assert_eq!(s.total, Bytes::new(0x3000));
+    }
+
+    #[test]
+    fn phys_regions() {
+        let p = |s, size| PhysRegion::new(PhysAddr::new(s), Bytes::new(size)).unwrap();
+        let s = summarize_phys_regions([p(0x1_0000_0000, 0x2000), p(0x1800, 0x1000)]).unwrap();
+
+        assert_eq!(s.total, Bytes::new(0x3000));
+        assert_eq!(s.largest, Bytes::new(0x2000));
+        assert_eq!(s.count, 2);
+        assert_eq!(s.frames, NumOfPages::new(2));
+    }
+
+    #[test]
+    fn phys_empty_map() {
+        assert_eq!(summarize_phys_regions([]), Some(RegionSummary::default()));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::Bytes;
+use crate::ConversionError;
+use crate::NumOfPages;
+use core::convert::TryFrom;
+use rkyv::primitive::ArchivedU64;
+use rkyv::rancor::Fallible;
+use rkyv::rancor::Source;
+use rkyv::Archive;
+use rkyv::Deserialize;
+use rkyv::Place;
+use rkyv::Serialize;
+use x86_64::structures::paging::PageSize;
+
+/// `Bytes` is archived as a `u64` so that the archive does not depend on the pointer width.
+impl Archive for Bytes {
+    type Archived = ArchivedU64;
+    type Resolver = ();
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        (self.as_usize() as u64).resolve(resolver, out);
+    }
+}
+impl<S: Fallible + ?Sized> Serialize<S> for Bytes {
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+/// Fails if the archived value does not fit in `usize`.
+impl<D> Deserialize<Bytes, D> for ArchivedU64
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<Bytes, D::Error> {
+        Bytes::try_from(self.to_native()).map_err(D::Error::new)
+    }
+}
+
+/// `NumOfPages` is archived as a `u64` so that the archive does not depend on the pointer width.
+/// The page size is not archived.
+impl<T: PageSize> Archive for NumOfPages<T> {
+    type Archived = ArchivedU64;
+    type Resolver = ();
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        (self.as_usize() as u64).resolve(resolver, out);
+    }
+}
+impl<T: PageSize, S: Fallible + ?Sized> Serialize<S> for NumOfPages<T> {
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+/// Fails if the archived value does not fit in `usize`.
+impl<T, D> Deserialize<NumOfPages<T>, D> for ArchivedU64
+where
+    T: PageSize,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<NumOfPages<T>, D::Error> {
+        usize::try_from(self.to_native())
+            .map(NumOfPages::new)
+            .map_err(|e| D::Error::new(ConversionError::from(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use rkyv::rancor::Error;
+    use rkyv::Archived;
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    #[test]
+    fn bytes_round_trip() {
+        let b = Bytes::new(0x4000_0000);
+        let archive = rkyv::to_bytes::<Error>(&b).unwrap();
+
+        assert_eq!(rkyv::from_bytes::<Bytes, Error>(&archive).unwrap(), b);
+    }
+
+    #[test]
+    fn bytes_archived_as_u64() {
+        let archive = rkyv::to_bytes::<Error>(&Bytes::new(3)).unwrap();
+
+        assert_eq!(&archive[..], &rkyv::to_bytes::<Error>(&3_u64).unwrap()[..]);
+    }
+
+    #[test]
+    fn bytes_access_validated() {
+        let archive = rkyv::to_bytes::<Error>(&Bytes::new(0x1000)).unwrap();
+        let archived = rkyv::access::<Archived<Bytes>, Error>(&archive).unwrap();
+
+        assert_eq!(archived.to_native(), 0x1000);
+    }
+
+    #[test]
+    fn bytes_access_too_short() {
+        let archive = rkyv::to_bytes::<Error>(&0_u8).unwrap();
+
+        assert!(rkyv::access::<Archived<Bytes>, Error>(&archive).is_err());
+    }
+
+    #[test]
+    fn num_of_pages_round_trip() {
+        let n = NumOfPages::<Size4KiB>::new(0x4_0000);
+        let archive = rkyv::to_bytes::<Error>(&n).unwrap();
+
+        assert_eq!(
+            rkyv::from_bytes::<NumOfPages<Size4KiB>, Error>(&archive).unwrap(),
+            n
+        );
+    }
+
+    #[test]
+    fn num_of_pages_in_struct() {
+        #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Debug, PartialEq)]
+        #[rkyv(crate = rkyv)]
+        struct Snapshot {
+            memory: Bytes,
+            huge_pages: NumOfPages<Size2MiB>,
+        }
+
+        let s = Snapshot {
+            memory: Bytes::new(0x8000_0000),
+            huge_pages: NumOfPages::new(1024),
+        };
+        let archive = rkyv::to_bytes::<Error>(&s).unwrap();
+
+        assert_eq!(rkyv::from_bytes::<Snapshot, Error>(&archive).unwrap(), s);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::Bytes;
+use crate::NumOfPages;
+use alloc::borrow::Cow;
+use alloc::format;
+use schemars::json_schema;
+use schemars::JsonSchema;
+use schemars::Schema;
+use schemars::SchemaGenerator;
+use x86_64::structures::paging::PageSize;
+
+impl JsonSchema for Bytes {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("Bytes")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("os_units::Bytes")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "integer",
+            "format": "uint",
+            "minimum": 0,
+            "description": "A size in bytes."
+        })
+    }
+}
+
+impl<T: PageSize> JsonSchema for NumOfPages<T> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Owned(format!("NumOfPages_{}", T::SIZE_AS_DEBUG_STR))
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Owned(format!("os_units::NumOfPages<{}>", T::SIZE_AS_DEBUG_STR))
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "integer",
+            "format": "uint",
+            "minimum": 0,
+            "description": format!("A number of {} pages.", T::SIZE_AS_DEBUG_STR)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use schemars::{schema_for, JsonSchema};
+    use serde_json::json;
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    #[test]
+    fn bytes_schema() {
+        let schema = schema_for!(Bytes);
+
+        assert_eq!(
+            schema.as_value(),
+            &json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "Bytes",
+                "type": "integer",
+                "format": "uint",
+                "minimum": 0,
+                "description": "A size in bytes."
+            })
+        );
+    }
+
+    #[test]
+    fn num_of_pages_schema() {
+        let schema = schema_for!(NumOfPages<Size2MiB>);
+
+        assert_eq!(
+            schema.as_value(),
+            &json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "NumOfPages_2MiB",
+                "type": "integer",
+                "format": "uint",
+                "minimum": 0,
+                "description": "A number of 2MiB pages."
+            })
+        );
+    }
+
+    #[test]
+    fn schema_ids_differ_by_page_size() {
+        assert_ne!(
+            NumOfPages::<Size4KiB>::schema_id(),
+            NumOfPages::<Size2MiB>::schema_id()
+        );
+    }
+
+    #[test]
+    fn inlined_in_struct() {
+        #[derive(JsonSchema)]
+        #[allow(dead_code)]
+        struct VmConfig {
+            memory: Bytes,
+            stack: NumOfPages<Size4KiB>,
+        }
+
+        let schema = schema_for!(VmConfig);
+        let properties = &schema.as_value()["properties"];
+
+        assert_eq!(properties["memory"]["type"], "integer");
+        assert_eq!(
+            properties["stack"]["description"],
+            "A number of 4KiB pages."
+        );
+        assert!(schema.get("$defs").is_none());
+    }
+}
@@ -0,0 +1,234 @@
+//! Modules to be used with `#[serde(with = "...")]` to select alternative representations.
+
+pub mod human_bytes {
+    //! Serializes [`Bytes`] as a string like `"16 MiB"` if the format is human-readable, and as a
+    //! plain integer otherwise.
+    //!
+    //! The string is produced by [`Bytes::display_binary`] and parsed by the [`FromStr`]
+    //! implementation of [`Bytes`].
+    //!
+    //! ```rust
+    //! use os_units::Bytes;
+    //! use serde::{Deserialize, Serialize};
+    //!
+    //! #[derive(Serialize, Deserialize)]
+    //! struct Config {
+    //!     #[serde(with = "os_units::serde_helpers::human_bytes")]
+    //!     heap: Bytes,
+    //! }
+    //!
+    //! let config: Config = serde_json::from_str(r#"{ "heap": "16 MiB" }"#).unwrap();
+    //! assert_eq!(config.heap, Bytes::new(16 * 1024 * 1024));
+    //! assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"heap":"16 MiB"}"#);
+    //! ```
+    //!
+    //! [`FromStr`]: core::str::FromStr
+
+    use crate::Bytes;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+
+    /// Serializes `bytes` as a string if the format is human-readable, and as an integer
+    /// otherwise.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the serializer fails.
+    pub fn serialize<S: Serializer>(bytes: &Bytes, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&bytes.display_binary())
+        } else {
+            bytes.serialize(serializer)
+        }
+    }
+
+    /// Deserializes [`Bytes`] from a string or an integer if the format is human-readable, and
+    /// from an integer otherwise. This is the same as the [`Deserialize`] implementation of
+    /// [`Bytes`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the deserializer fails or the string cannot be parsed.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Bytes, D::Error> {
+        Bytes::deserialize(deserializer)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::Bytes;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Config {
+            #[serde(with = "super")]
+            size: Bytes,
+        }
+
+        fn from_json(s: &str) -> Result<Bytes, serde_json::Error> {
+            serde_json::from_str::<Config>(&format!(r#"{{"size":{s}}}"#)).map(|c| c.size)
+        }
+
+        #[test]
+        fn serialize_json() {
+            let c = Config {
+                size: Bytes::new(0x100_0000),
+            };
+
+            assert_eq!(serde_json::to_string(&c).unwrap(), r#"{"size":"16 MiB"}"#);
+        }
+
+        #[test]
+        fn serialize_json_not_a_multiple() {
+            let c = Config {
+                size: Bytes::new(4097),
+            };
+
+            assert_eq!(
+                serde_json::to_string(&c).unwrap(),
+                r#"{"size":"4097 bytes"}"#
+            );
+        }
+
+        #[test]
+        fn deserialize_json() {
+            assert_eq!(from_json(r#""16 MiB""#).unwrap(), Bytes::new(0x100_0000));
+            assert_eq!(from_json(r#""4097""#).unwrap(), Bytes::new(4097));
+        }
+
+        #[test]
+        fn deserialize_json_invalid_unit() {
+            assert_eq!(
+                from_json(r#""16 MB""#).unwrap_err().to_string(),
+                "unknown unit at line 1 column 15"
+            );
+        }
+
+        #[test]
+        fn deserialize_json_integer() {
+            assert_eq!(from_json("4096").unwrap(), Bytes::new(4096));
+        }
+
+        #[test]
+        fn postcard_is_integer() {
+            let c = Config {
+                size: Bytes::new(0x100_0000),
+            };
+            let mut buf = [0; 16];
+            let encoded = postcard::to_slice(&c, &mut buf).unwrap();
+
+            assert_eq!(
+                encoded,
+                postcard::to_slice(&0x100_0000_usize, &mut [0; 16]).unwrap()
+            );
+            assert_eq!(postcard::from_bytes::<Config>(encoded).unwrap(), c);
+        }
+    }
+}
+
+pub mod tagged_pages {
+    //! Serializes [`NumOfPages`] as a `(page_size, count)` tuple, and rejects the input on
+    //! deserialization if the page size differs from that of the target type.
+    //!
+    //! ```rust
+    //! use os_units::NumOfPages;
+    //! use serde::{Deserialize, Serialize};
+    //! use x86_64::structures::paging::Size4KiB;
+    //!
+    //! #[derive(Serialize, Deserialize)]
+    //! struct Config {
+    //!     #[serde(with = "os_units::serde_helpers::tagged_pages")]
+    //!     stack: NumOfPages<Size4KiB>,
+    //! }
+    //! ```
+
+    use crate::NumOfPages;
+    use serde::de::Error;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+    use x86_64::structures::paging::PageSize;
+
+    /// Serializes `pages` as a `(page_size, count)` tuple.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the serializer fails.
+    pub fn serialize<T: PageSize, S: Serializer>(
+        pages: &NumOfPages<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        (T::SIZE, pages.as_usize()).serialize(serializer)
+    }
+
+    /// Deserializes a `(page_size, count)` tuple, checking that the page size is `T::SIZE`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the deserializer fails or the page size does not match.
+    pub fn deserialize<'de, T: PageSize, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<NumOfPages<T>, D::Error> {
+        let (page_size, count) = <(u64, usize)>::deserialize(deserializer)?;
+
+        if page_size == T::SIZE {
+            Ok(NumOfPages::new(count))
+        } else {
+            Err(D::Error::custom(format_args!(
+                "page size mismatch: expected {}, found {}",
+                T::SIZE,
+                page_size
+            )))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::NumOfPages;
+        use x86_64::structures::paging::{PageSize, Size2MiB, Size4KiB};
+
+        fn to_json<T: PageSize>(n: NumOfPages<T>) -> String {
+            let mut s = serde_json::Serializer::new(Vec::new());
+            super::serialize(&n, &mut s).unwrap();
+            String::from_utf8(s.into_inner()).unwrap()
+        }
+
+        #[test]
+        fn serialize_with_page_size() {
+            assert_eq!(to_json(NumOfPages::<Size4KiB>::new(3)), "[4096,3]");
+        }
+
+        #[test]
+        fn json_round_trip() {
+            let s = to_json(NumOfPages::<Size2MiB>::new(3));
+            let n: NumOfPages<Size2MiB> =
+                super::deserialize(&mut serde_json::Deserializer::from_str(&s)).unwrap();
+
+            assert_eq!(n, NumOfPages::new(3));
+        }
+
+        #[test]
+        fn postcard_round_trip() {
+            let mut buf = [0; 16];
+            let encoded = postcard::to_slice(&(4096_u64, 3_usize), &mut buf).unwrap();
+            let n: NumOfPages<Size4KiB> =
+                super::deserialize(&mut postcard::Deserializer::from_bytes(encoded)).unwrap();
+
+            assert_eq!(n, NumOfPages::new(3));
+        }
+
+        #[test]
+        fn page_size_mismatch() {
+            let s = to_json(NumOfPages::<Size4KiB>::new(3));
+            let r: Result<NumOfPages<Size2MiB>, _> =
+                super::deserialize(&mut serde_json::Deserializer::from_str(&s));
+
+            assert_eq!(
+                r.unwrap_err().to_string(),
+                "page size mismatch: expected 2097152, found 4096"
+            );
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::Bytes;
+use crate::NumOfPages;
+use crate::ParseBytesError;
+use core::convert::TryFrom;
+use core::fmt;
+use serde::de;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use x86_64::structures::paging::PageSize;
+
+/// Serializes the value as a plain integer.
+impl Serialize for Bytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_usize().serialize(serializer)
+    }
+}
+/// Deserializes the value from a plain integer.
+///
+/// If the format is human-readable (e.g. JSON), a string like `"16 MiB"` is also accepted and
+/// parsed by the [`FromStr`](core::str::FromStr) implementation. Negative numbers and floats are
+/// rejected.
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(BytesVisitor)
+        } else {
+            usize::deserialize(deserializer).map(Self::new)
+        }
+    }
+}
+
+struct BytesVisitor;
+impl de::Visitor<'_> for BytesVisitor {
+    type Value = Bytes;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a non-negative integer or a string like \"16 MiB\"")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Bytes::try_from(v).map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        match u64::try_from(v) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(|e: ParseBytesError| E::custom(e))
+    }
+}
+/// Serializes the number of pages as a plain integer. The page size is not serialized.
+///
+/// Deserializing the value as `NumOfPages` of a different page size silently changes its meaning,
+/// e.g. 3 pages of 4 KiB become 3 pages of 2 MiB. Use
+/// [`serde_helpers::tagged_pages`](crate::serde_helpers::tagged_pages) to record and validate the
+/// page size.
+impl<T: PageSize> Serialize for NumOfPages<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_usize().serialize(serializer)
+    }
+}
+/// Deserializes the number of pages from a plain integer. The page size is determined by the
+/// type parameter, not by the input.
+impl<'de, T: PageSize> Deserialize<'de> for NumOfPages<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        usize::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use x86_64::structures::paging::{Size2MiB, Size4KiB};
+
+    #[test]
+    fn bytes_to_json() {
+        let s = serde_json::to_string(&Bytes::new(4096)).unwrap();
+
+        assert_eq!(s, "4096");
+    }
+
+    #[test]
+    fn bytes_from_json() {
+        let b: Bytes = serde_json::from_str("4096").unwrap();
+
+        assert_eq!(b, Bytes::new(4096));
+    }
+
+    #[test]
+    fn bytes_json_round_trip() {
+        let b = Bytes::new(0x4000_0000);
+        let s = serde_json::to_string(&b).unwrap();
+
+        assert_eq!(serde_json::from_str::<Bytes>(&s).unwrap(), b);
+    }
+
+    #[test]
+    fn bytes_from_json_string() {
+        let b: Bytes = serde_json::from_str(r#""1 MiB""#).unwrap();
+
+        assert_eq!(b, Bytes::new(1_048_576));
+    }
+
+    #[test]
+    fn bytes_from_json_integer() {
+        let b: Bytes = serde_json::from_str("1048576").unwrap();
+
+        assert_eq!(b, Bytes::new(1_048_576));
+    }
+
+    #[test]
+    fn bytes_from_negative_json() {
+        let e = serde_json::from_str::<Bytes>("-3").unwrap_err();
+
+        assert_eq!(
+            e.to_string(),
+            r#"invalid value: integer `-3`, expected a non-negative integer or a string like "16 MiB" at line 1 column 2"#
+        );
+    }
+
+    #[test]
+    fn bytes_from_negative_json_string() {
+        let e = serde_json::from_str::<Bytes>(r#""-3""#).unwrap_err();
+
+        assert_eq!(
+            e.to_string(),
+            "invalid digit found in string at line 1 column 4"
+        );
+    }
+
+    #[test]
+    fn bytes_from_float_json() {
+        let e = serde_json::from_str::<Bytes>("1.5").unwrap_err();
+
+        assert_eq!(
+            e.to_string(),
+            r#"invalid type: floating point `1.5`, expected a non-negative integer or a string like "16 MiB" at line 1 column 3"#
+        );
+    }
+
+    #[test]
+    fn bytes_postcard_round_trip() {
+        let b = Bytes::new(0x4000_0000);
+        let mut buf = [0; 16];
+        let encoded = postcard::to_slice(&b, &mut buf).unwrap();
+
+        assert_eq!(postcard::from_bytes::<Bytes>(encoded).unwrap(), b);
+    }
+
+    #[test]
+    fn num_of_pages_to_json() {
+        let s = serde_json::to_string(&NumOfPages::<Size4KiB>::new(3)).unwrap();
+
+        assert_eq!(s, "3");
+    }
+
+    #[test]
+    fn num_of_pages_json_round_trip() {
+        let n = NumOfPages::<Size2MiB>::new(512);
+        let s = serde_json::to_string(&n).unwrap();
+
+        assert_eq!(serde_json::from_str::<NumOfPages<Size2MiB>>(&s).unwrap(), n);
+    }
+
+    #[test]
+    fn num_of_pages_postcard_round_trip() {
+        let n = NumOfPages::<Size4KiB>::new(0x4_0000);
+        let mut buf = [0; 16];
+        let encoded = postcard::to_slice(&n, &mut buf).unwrap();
+
+        assert_eq!(
+            postcard::from_bytes::<NumOfPages<Size4KiB>>(encoded).unwrap(),
+            n
+        );
+    }
+}
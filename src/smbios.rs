@@ -0,0 +1,119 @@
+use crate::Bytes;
+use core::convert::TryFrom;
+
+impl Bytes {
+    /// Decodes the Size field of an SMBIOS Memory Device (Type 17) structure.
+    ///
+    /// If bit 15 of `raw` is set, the lower 15 bits are in KiB, otherwise in MiB. If `raw` is
+    /// `0x7fff`, the size is bits 30:0 of `extended_mib`, the Extended Size field, in MiB.
+    /// `extended_mib` is ignored otherwise.
+    ///
+    /// This method returns [`None`] if `raw` is `0`, meaning that no device is installed, if
+    /// `raw` is `0xffff`, meaning that the size is unknown, or if the size does not fit in
+    /// `usize`.
+    ///
+    /// ```rust
+    /// use os_units::Bytes;
+    ///
+    /// assert_eq!(Bytes::from_smbios_size(0x400, 0), Some(Bytes::new(0x4000_0000)));
+    /// assert_eq!(Bytes::from_smbios_size(0x8200, 0), Some(Bytes::new(0x8_0000)));
+    /// assert_eq!(Bytes::from_smbios_size(0, 0), None);
+    ///
+    /// // A 64 GiB device, which does not fit in the 32-bit `usize`.
+    /// # #[cfg(target_pointer_width = "64")]
+    /// assert_eq!(Bytes::from_smbios_size(0x7fff, 0x1_0000), Some(Bytes::new(0x10_0000_0000)));
+    /// # #[cfg(target_pointer_width = "32")]
+    /// assert_eq!(Bytes::from_smbios_size(0x7fff, 0x1_0000), None);
+    /// ```
+    #[must_use]
+    pub fn from_smbios_size(raw: u16, extended_mib: u32) -> Option<Self> {
+        const KIB: usize = 1 << 10;
+        const MIB: usize = 1 << 20;
+
+        let (value, unit) = match raw {
+            0 | 0xffff => return None,
+            0x7fff => (extended_mib & 0x7fff_ffff, MIB),
+            _ if raw & 0x8000 == 0 => (u32::from(raw), MIB),
+            _ => (u32::from(raw & 0x7fff), KIB),
+        };
+
+        usize::try_from(value)
+            .ok()?
+            .checked_mul(unit)
+            .map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn from_smbios_size_mib() {
+        assert_eq!(
+            Bytes::from_smbios_size(0x1000, 0),
+            Some(Bytes::new(0x1_0000_0000))
+        );
+        assert_eq!(
+            Bytes::from_smbios_size(1, 0xdead),
+            Some(Bytes::new(0x10_0000))
+        );
+        assert_eq!(
+            Bytes::from_smbios_size(0x7ffe, 0),
+            Some(Bytes::new(0x7ffe << 20))
+        );
+    }
+
+    #[test]
+    fn from_smbios_size_kib() {
+        assert_eq!(Bytes::from_smbios_size(0x8001, 0), Some(Bytes::new(0x400)));
+        assert_eq!(
+            Bytes::from_smbios_size(0x8200, 0xdead),
+            Some(Bytes::new(0x8_0000))
+        );
+        assert_eq!(
+            Bytes::from_smbios_size(0xfffe, 0),
+            Some(Bytes::new(0x7ffe << 10))
+        );
+    }
+
+    #[test]
+    fn from_smbios_size_zero_kib() {
+        assert_eq!(Bytes::from_smbios_size(0x8000, 0), Some(Bytes::zero()));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn from_smbios_size_extended() {
+        assert_eq!(
+            Bytes::from_smbios_size(0x7fff, 0x8000),
+            Some(Bytes::new(0x8_0000_0000))
+        );
+        assert_eq!(
+            Bytes::from_smbios_size(0x7fff, 0x8000_8000),
+            Some(Bytes::new(0x8_0000_0000))
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn from_smbios_size_does_not_fit_in_usize() {
+        assert_eq!(Bytes::from_smbios_size(0x1000, 0), None);
+        assert_eq!(Bytes::from_smbios_size(0x7fff, 0x8000), None);
+        assert_eq!(
+            Bytes::from_smbios_size(0xfff, 0),
+            Some(Bytes::new(0xfff << 20))
+        );
+    }
+
+    #[test]
+    fn from_smbios_size_not_installed() {
+        assert_eq!(Bytes::from_smbios_size(0, 0x8000), None);
+    }
+
+    #[test]
+    fn from_smbios_size_unknown() {
+        assert_eq!(Bytes::from_smbios_size(0xffff, 0x8000), None);
+    }
+}
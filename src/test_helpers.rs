@@ -0,0 +1,222 @@
+//! Implementation details of [`assert_bytes_eq`](crate::assert_bytes_eq) and
+//! [`assert_pages_eq`](crate::assert_pages_eq).
+
+use crate::Bytes;
+use crate::NumOfPages;
+use core::fmt;
+use x86_64::structures::paging::PageSize;
+
+/// Asserts that two [`Bytes`](crate::Bytes) are equal.
+///
+/// On failure, the panic message shows both values in human-readable units and the signed
+/// difference `left - right`. Like [`assert_eq`], custom messages can be passed after the
+/// operands.
+///
+/// This macro is available with the `test-helpers` feature.
+#[macro_export]
+macro_rules! assert_bytes_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if *left != *right {
+                    $crate::test_helpers::bytes_ne(*left, *right, ::core::option::Option::None);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if *left != *right {
+                    $crate::test_helpers::bytes_ne(
+                        *left,
+                        *right,
+                        ::core::option::Option::Some(::core::format_args!($($arg)+)),
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Asserts that two [`NumOfPages`](crate::NumOfPages) are equal.
+///
+/// On failure, the panic message shows both values and the signed difference `left - right`.
+/// Like [`assert_eq`], custom messages can be passed after the operands.
+///
+/// This macro is available with the `test-helpers` feature.
+#[macro_export]
+macro_rules! assert_pages_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if *left != *right {
+                    $crate::test_helpers::pages_ne(*left, *right, ::core::option::Option::None);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if *left != *right {
+                    $crate::test_helpers::pages_ne(
+                        *left,
+                        *right,
+                        ::core::option::Option::Some(::core::format_args!($($arg)+)),
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[cold]
+#[track_caller]
+pub fn bytes_ne(left: Bytes, right: Bytes, args: Option<fmt::Arguments<'_>>) -> ! {
+    let (sign, diff) = signed_diff(left.as_usize(), right.as_usize());
+
+    panic!(
+        "assertion `left == right` failed{}\n  left: {}\n right: {}\n  diff: {}{}",
+        Message(args),
+        Human(left),
+        Human(right),
+        sign,
+        Human(Bytes::new(diff))
+    );
+}
+
+#[doc(hidden)]
+#[cold]
+#[track_caller]
+pub fn pages_ne<T: PageSize>(
+    left: NumOfPages<T>,
+    right: NumOfPages<T>,
+    args: Option<fmt::Arguments<'_>>,
+) -> ! {
+    let (sign, diff) = signed_diff(left.as_usize(), right.as_usize());
+
+    panic!(
+        "assertion `left == right` failed{}\n  left: {}\n right: {}\n  diff: {}{}",
+        Message(args),
+        left,
+        right,
+        sign,
+        NumOfPages::<T>::new(diff)
+    );
+}
+
+fn signed_diff(left: usize, right: usize) -> (char, usize) {
+    if left >= right {
+        ('+', left - right)
+    } else {
+        ('-', right - left)
+    }
+}
+
+struct Message<'a>(Option<fmt::Arguments<'a>>);
+impl fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(args) => write!(f, ": {args}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Displays bytes with digit grouping, followed by the value in the largest binary unit which
+/// divides it exactly, e.g. `4,202,496 bytes (4104 KiB)`.
+struct Human(Bytes);
+impl fmt::Display for Human {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.display_grouped(','))?;
+
+        if self.0.as_usize() != 0 && self.0.as_usize().trailing_zeros() >= 10 {
+            write!(f, " ({})", self.0.display_binary())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use std::panic;
+    use x86_64::structures::paging::Size4KiB;
+
+    fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+        let payload = panic::catch_unwind(f).unwrap_err();
+
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .expect("The panic payload is not a `String`.")
+    }
+
+    #[test]
+    fn bytes_eq() {
+        assert_bytes_eq!(Bytes::new(3), Bytes::new(3));
+    }
+
+    #[test]
+    fn bytes_ne_message() {
+        let m = panic_message(|| assert_bytes_eq!(Bytes::new(4_202_496), Bytes::new(4_198_400)));
+
+        assert_eq!(
+            m,
+            "assertion `left == right` failed\n  \
+             left: 4,202,496 bytes (4104 KiB)\n \
+             right: 4,198,400 bytes (4100 KiB)\n  \
+             diff: +4,096 bytes (4 KiB)"
+        );
+    }
+
+    #[test]
+    fn bytes_ne_negative_diff() {
+        let m = panic_message(|| assert_bytes_eq!(Bytes::new(1), Bytes::new(0x20_0000)));
+
+        assert_eq!(
+            m,
+            "assertion `left == right` failed\n  \
+             left: 1 byte\n \
+             right: 2,097,152 bytes (2 MiB)\n  \
+             diff: -2,097,151 bytes"
+        );
+    }
+
+    #[test]
+    fn bytes_ne_custom_message() {
+        let m = panic_message(|| assert_bytes_eq!(Bytes::zero(), Bytes::new(2), "region {}", 3));
+
+        assert_eq!(
+            m,
+            "assertion `left == right` failed: region 3\n  \
+             left: 0 bytes\n \
+             right: 2 bytes\n  \
+             diff: -2 bytes"
+        );
+    }
+
+    #[test]
+    fn pages_eq() {
+        assert_pages_eq!(NumOfPages::<Size4KiB>::new(3), NumOfPages::new(3));
+    }
+
+    #[test]
+    fn pages_ne_message() {
+        let m = panic_message(|| {
+            assert_pages_eq!(NumOfPages::<Size4KiB>::new(3), NumOfPages::new(2));
+        });
+
+        assert_eq!(
+            m,
+            "assertion `left == right` failed\n  \
+             left: 3 pages (4KiB)\n \
+             right: 2 pages (4KiB)\n  \
+             diff: +1 page (4KiB)"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{ByteCounter, Bytes};
+use core::alloc::{GlobalAlloc, Layout};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A [`GlobalAlloc`] which forwards to `A` and counts the bytes allocated through it.
+///
+/// ```rust
+/// use os_units::{Bytes, TrackingAllocator};
+/// use std::alloc::System;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: TrackingAllocator<System> = TrackingAllocator::new(System);
+///
+/// let before = ALLOCATOR.stats();
+/// let v = vec![0_u8; 0x1000];
+/// let during = ALLOCATOR.stats();
+///
+/// assert!(during.peak.as_usize() >= 0x1000);
+/// assert!(during.allocations > before.allocations);
+/// # drop(v);
+/// ```
+#[derive(Debug)]
+pub struct TrackingAllocator<A: GlobalAlloc> {
+    inner: A,
+    bytes: ByteCounter,
+    allocations: AtomicUsize,
+}
+impl<A: GlobalAlloc> TrackingAllocator<A> {
+    /// Wraps `inner`.
+    #[must_use]
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            bytes: ByteCounter::new(),
+            allocations: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns a reference to the inner allocator.
+    #[must_use]
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Returns the bytes currently allocated.
+    #[must_use]
+    pub fn current(&self) -> Bytes {
+        self.bytes.current()
+    }
+
+    /// Returns the largest number of the bytes allocated at the same time.
+    #[must_use]
+    pub fn peak(&self) -> Bytes {
+        self.bytes.peak()
+    }
+
+    /// Returns the number of the successful allocations, excluding reallocations.
+    #[must_use]
+    pub fn allocations(&self) -> usize {
+        self.allocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns all the statistics at once.
+    ///
+    /// The values are read one by one, so they may not be consistent with each other while other
+    /// threads are allocating.
+    #[must_use]
+    pub fn stats(&self) -> AllocStats {
+        AllocStats {
+            current: self.current(),
+            peak: self.peak(),
+            allocations: self.allocations(),
+        }
+    }
+
+    fn record_allocation(&self, ptr: *mut u8, size: usize) -> *mut u8 {
+        if !ptr.is_null() {
+            self.bytes.add(Bytes::new(size));
+            self.allocations.fetch_add(1, Ordering::Relaxed);
+        }
+
+        ptr
+    }
+}
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.record_allocation(self.inner.alloc(layout), layout.size())
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        self.record_allocation(self.inner.alloc_zeroed(layout), layout.size())
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        self.bytes.sub(Bytes::new(layout.size()));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                self.bytes.add(Bytes::new(new_size - layout.size()));
+            } else {
+                self.bytes.sub(Bytes::new(layout.size() - new_size));
+            }
+        }
+
+        new_ptr
+    }
+}
+
+/// The statistics of a [`TrackingAllocator`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct AllocStats {
+    /// The bytes currently allocated.
+    pub current: Bytes,
+    /// The largest number of the bytes allocated at the same time.
+    pub peak: Bytes,
+    /// The number of the successful allocations, excluding reallocations.
+    pub allocations: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TrackingAllocator;
+    use crate::Bytes;
+    use core::alloc::{GlobalAlloc, Layout};
+    use std::alloc::System;
+    use std::ptr;
+
+    /// An allocator which always fails.
+    struct Failing;
+    unsafe impl GlobalAlloc for Failing {
+        unsafe fn alloc(&self, _: Layout) -> *mut u8 {
+            ptr::null_mut()
+        }
+
+        unsafe fn dealloc(&self, _: *mut u8, _: Layout) {
+            unreachable!("nothing is allocated");
+        }
+    }
+
+    fn layout(size: usize) -> Layout {
+        Layout::from_size_align(size, 8).unwrap()
+    }
+
+    #[test]
+    fn alloc_dealloc() {
+        let a = TrackingAllocator::new(System);
+
+        let p = unsafe { a.alloc(layout(0x100)) };
+        let q = unsafe { a.alloc_zeroed(layout(0x200)) };
+        assert!(!p.is_null());
+        assert!(!q.is_null());
+
+        assert_eq!(a.current(), Bytes::new(0x300));
+        assert_eq!(a.peak(), Bytes::new(0x300));
+        assert_eq!(a.allocations(), 2);
+
+        unsafe { a.dealloc(p, layout(0x100)) };
+
+        assert_eq!(a.current(), Bytes::new(0x200));
+        assert_eq!(a.peak(), Bytes::new(0x300));
+
+        unsafe { a.dealloc(q, layout(0x200)) };
+
+        let s = a.stats();
+        assert_eq!(s.current, Bytes::zero());
+        assert_eq!(s.peak, Bytes::new(0x300));
+        assert_eq!(s.allocations, 2);
+    }
+
+    #[test]
+    fn realloc() {
+        let a = TrackingAllocator::new(System);
+
+        let p = unsafe { a.alloc(layout(0x100)) };
+        let p = unsafe { a.realloc(p, layout(0x100), 0x400) };
+        assert!(!p.is_null());
+
+        assert_eq!(a.current(), Bytes::new(0x400));
+        assert_eq!(a.peak(), Bytes::new(0x400));
+
+        let p = unsafe { a.realloc(p, layout(0x400), 0x80) };
+        assert!(!p.is_null());
+
+        assert_eq!(a.current(), Bytes::new(0x80));
+        assert_eq!(a.peak(), Bytes::new(0x400));
+        assert_eq!(a.allocations(), 1);
+
+        unsafe { a.dealloc(p, layout(0x80)) };
+
+        assert_eq!(a.current(), Bytes::zero());
+    }
+
+    #[test]
+    fn failed_allocation_is_not_counted() {
+        let a = TrackingAllocator::new(Failing);
+
+        assert!(unsafe { a.alloc(layout(0x100)) }.is_null());
+        assert!(unsafe { a.alloc_zeroed(layout(0x100)) }.is_null());
+
+        assert_eq!(a.current(), Bytes::zero());
+        assert_eq!(a.peak(), Bytes::zero());
+        assert_eq!(a.allocations(), 0);
+    }
+
+    #[test]
+    fn static_allocator() {
+        static A: TrackingAllocator<System> = TrackingAllocator::new(System);
+
+        let p = unsafe { A.alloc(layout(0x80)) };
+        unsafe { p.write_bytes(7, 0x80) };
+
+        assert_eq!(unsafe { *p.add(0x7f) }, 7);
+        assert_eq!(A.current(), Bytes::new(0x80));
+
+        unsafe { A.dealloc(p, layout(0x80)) };
+
+        assert_eq!(A.current(), Bytes::zero());
+    }
+}
@@ -0,0 +1,172 @@
+//! Conversions from the memory map of the [`uefi`](https://docs.rs/uefi) crate.
+//!
+//! The size of a [`MemoryDescriptor`] is given by `page_count` in 4 KiB units, which is a `u64`
+//! even on 32-bit targets. The conversions here never truncate it silently, and fail with
+//! [`ConversionError::Overflow`] or return [`None`] instead.
+//!
+//! ```rust
+//! use core::convert::TryFrom;
+//! use os_units::{uefi_helpers, Bytes, NumOfPages};
+//! use uefi::mem::memory_map::{MemoryDescriptor, MemoryType};
+//! use x86_64::structures::paging::Size4KiB;
+//!
+//! let map = [
+//!     MemoryDescriptor { ty: MemoryType::CONVENTIONAL, page_count: 16, ..Default::default() },
+//!     MemoryDescriptor { ty: MemoryType::LOADER_DATA, page_count: 4, ..Default::default() },
+//! ];
+//!
+//! assert_eq!(NumOfPages::<Size4KiB>::try_from(&map[0]), Ok(NumOfPages::new(16)));
+//! assert_eq!(uefi_helpers::bytes(&map[0]), Some(Bytes::new(0x10000)));
+//! assert_eq!(
+//!     uefi_helpers::sum_bytes(&map, |d| d.ty == MemoryType::CONVENTIONAL),
+//!     Some(Bytes::new(0x10000))
+//! );
+//! ```
+
+use crate::Bytes;
+use crate::ConversionError;
+use crate::NumOfPages;
+use core::convert::TryFrom;
+use uefi::mem::memory_map::MemoryDescriptor;
+use x86_64::structures::paging::{PageSize, Size4KiB};
+
+/// Returns the size of the memory described by `d`.
+///
+/// This function returns [`None`] if the size does not fit in `usize`.
+#[must_use]
+pub fn bytes(d: &MemoryDescriptor) -> Option<Bytes> {
+    let bytes = d.page_count.checked_mul(Size4KiB::SIZE)?;
+
+    usize::try_from(bytes).ok().map(Bytes::new)
+}
+
+/// Returns the sum of the sizes of the descriptors for which `predicate` returns `true`.
+///
+/// This function returns [`None`] if the size of a matching descriptor or the sum does not fit in
+/// `usize`.
+///
+/// `MemoryMap::entries` of the `uefi` crate can be passed directly.
+pub fn sum_bytes<'a, I, P>(descriptors: I, mut predicate: P) -> Option<Bytes>
+where
+    I: IntoIterator<Item = &'a MemoryDescriptor>,
+    P: FnMut(&MemoryDescriptor) -> bool,
+{
+    descriptors
+        .into_iter()
+        .filter(|d| predicate(d))
+        .try_fold(Bytes::zero(), |sum, d| {
+            sum.as_usize()
+                .checked_add(bytes(d)?.as_usize())
+                .map(Bytes::new)
+        })
+}
+
+/// Fails with [`ConversionError::Overflow`] if `page_count` does not fit in `usize`, which can only
+/// happen on targets where `usize` is smaller than `u64`.
+impl TryFrom<&MemoryDescriptor> for NumOfPages<Size4KiB> {
+    type Error = ConversionError;
+
+    fn try_from(d: &MemoryDescriptor) -> Result<Self, Self::Error> {
+        Ok(Self::new(usize::try_from(d.page_count)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bytes, sum_bytes};
+    use crate::{Bytes, NumOfPages};
+    use core::convert::TryFrom;
+    use uefi::mem::memory_map::{MemoryDescriptor, MemoryType};
+    use x86_64::structures::paging::Size4KiB;
+
+    fn descriptor(ty: MemoryType, page_count: u64) -> MemoryDescriptor {
+        MemoryDescriptor {
+            ty,
+            page_count,
+            ..MemoryDescriptor::default()
+        }
+    }
+
+    #[test]
+    fn num_of_pages() {
+        let d = descriptor(MemoryType::CONVENTIONAL, 3);
+
+        assert_eq!(NumOfPages::<Size4KiB>::try_from(&d), Ok(NumOfPages::new(3)));
+    }
+
+    #[test]
+    fn bytes_of_descriptor() {
+        let d = descriptor(MemoryType::CONVENTIONAL, 3);
+
+        assert_eq!(bytes(&d), Some(Bytes::new(0x3000)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn bytes_larger_than_32_bit() {
+        // 8 GiB.
+        let d = descriptor(MemoryType::CONVENTIONAL, 0x20_0000);
+
+        assert_eq!(bytes(&d), Some(Bytes::new(0x2_0000_0000)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn bytes_larger_than_32_bit() {
+        // 8 GiB.
+        let d = descriptor(MemoryType::CONVENTIONAL, 0x20_0000);
+
+        assert_eq!(bytes(&d), None);
+    }
+
+    #[test]
+    fn bytes_overflow() {
+        let d = descriptor(MemoryType::CONVENTIONAL, u64::MAX / 0x1000 + 1);
+
+        assert_eq!(bytes(&d), None);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn sum_conventional() {
+        let map = [
+            descriptor(MemoryType::LOADER_CODE, 0x10),
+            descriptor(MemoryType::CONVENTIONAL, 0x9f),
+            descriptor(MemoryType::RESERVED, 0x60),
+            descriptor(MemoryType::CONVENTIONAL, 0x20_0000),
+        ];
+
+        let sum = sum_bytes(&map, |d| d.ty == MemoryType::CONVENTIONAL);
+
+        assert_eq!(sum, Some(Bytes::new(0x9_f000 + 0x2_0000_0000)));
+    }
+
+    #[test]
+    fn sum_nothing_matches() {
+        let map = [descriptor(MemoryType::RESERVED, 0x10)];
+
+        assert_eq!(sum_bytes(&map, |_| false), Some(Bytes::zero()));
+    }
+
+    #[test]
+    fn sum_overflow() {
+        let map = [
+            descriptor(MemoryType::CONVENTIONAL, u64::MAX / 0x1000),
+            descriptor(MemoryType::CONVENTIONAL, 1),
+        ];
+
+        assert_eq!(sum_bytes(&map, |_| true), None);
+    }
+
+    #[test]
+    fn sum_ignores_overflowing_descriptor_which_does_not_match() {
+        let map = [
+            descriptor(MemoryType::RESERVED, u64::MAX),
+            descriptor(MemoryType::CONVENTIONAL, 1),
+        ];
+
+        let sum = sum_bytes(&map, |d| d.ty == MemoryType::CONVENTIONAL);
+
+        assert_eq!(sum, Some(Bytes::new(0x1000)));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::Bytes;
+use crate::ConversionError;
+use core::convert::TryFrom;
+use uom::si::information::byte;
+
+impl From<Bytes> for uom::si::usize::Information {
+    fn from(b: Bytes) -> Self {
+        Self::new::<byte>(b.as_usize())
+    }
+}
+impl From<uom::si::usize::Information> for Bytes {
+    fn from(i: uom::si::usize::Information) -> Self {
+        Self::new(i.get::<byte>())
+    }
+}
+
+impl From<Bytes> for uom::si::u64::Information {
+    fn from(b: Bytes) -> Self {
+        Self::new::<byte>(b.as_usize() as u64)
+    }
+}
+/// Fails with [`ConversionError::Overflow`] if the bytes do not fit in `usize`.
+///
+/// ```rust
+/// use core::convert::TryFrom;
+/// use os_units::{Bytes, NumOfPages};
+/// use uom::si::information::{byte, mebibyte};
+/// use uom::si::u64::Information;
+/// use x86_64::structures::paging::Size4KiB;
+///
+/// let buffer = Information::new::<mebibyte>(2);
+/// let bytes = Bytes::try_from(buffer).unwrap();
+///
+/// assert_eq!(bytes.as_num_of_pages::<Size4KiB>(), NumOfPages::new(512));
+/// assert_eq!(Information::from(bytes).get::<byte>(), 0x20_0000);
+/// ```
+impl TryFrom<uom::si::u64::Information> for Bytes {
+    type Error = ConversionError;
+
+    fn try_from(i: uom::si::u64::Information) -> Result<Self, Self::Error> {
+        Self::try_from(i.get::<byte>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+    use core::convert::TryFrom;
+    use uom::si::information::{byte, kibibyte};
+
+    #[test]
+    fn usize_round_trip() {
+        for b in [0, 1, 0x1000, usize::MAX] {
+            let i = uom::si::usize::Information::from(Bytes::new(b));
+
+            assert_eq!(i.get::<byte>(), b);
+            assert_eq!(Bytes::from(i), Bytes::new(b));
+        }
+    }
+
+    #[test]
+    fn u64_round_trip() {
+        for b in [0, 1, 0x1000, usize::MAX] {
+            let i = uom::si::u64::Information::from(Bytes::new(b));
+
+            assert_eq!(i.get::<byte>(), b as u64);
+            assert_eq!(Bytes::try_from(i), Ok(Bytes::new(b)));
+        }
+    }
+
+    #[test]
+    fn from_larger_unit() {
+        assert_eq!(
+            Bytes::from(uom::si::usize::Information::new::<kibibyte>(4)),
+            Bytes::new(0x1000)
+        );
+        assert_eq!(
+            Bytes::try_from(uom::si::u64::Information::new::<kibibyte>(4)),
+            Ok(Bytes::new(0x1000))
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn u64_overflow() {
+        assert_eq!(
+            Bytes::try_from(uom::si::u64::Information::new::<byte>(0x1_0000_0000)),
+            Err(crate::ConversionError::Overflow)
+        );
+    }
+}
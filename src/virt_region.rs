@@ -0,0 +1,284 @@
+use crate::AddrExt;
+use crate::Bytes;
+use x86_64::structures::paging::page::PageRange;
+use x86_64::structures::paging::{Page, PageSize};
+use x86_64::VirtAddr;
+
+/// A half-open region of virtual memory `start..start + size`.
+///
+/// The end is computed like [`AddrExt::checked_add_bytes`], so a region which goes past the end of
+/// the lower half of the address space continues in the higher half, and the comparisons of the
+/// addresses work as expected.
+///
+/// The end of a region is always a valid address. This means a region cannot contain the last byte
+/// of the address space.
+///
+/// ```rust
+/// use os_units::{Bytes, VirtRegion};
+/// use x86_64::VirtAddr;
+///
+/// let r = VirtRegion::new(VirtAddr::new(0x1000), Bytes::new(0x1000)).unwrap();
+///
+/// assert_eq!(r.end(), VirtAddr::new(0x2000));
+/// assert!(r.contains(VirtAddr::new(0x1fff)));
+/// assert!(!r.contains(VirtAddr::new(0x2000)));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct VirtRegion {
+    start: VirtAddr,
+    size: Bytes,
+}
+impl VirtRegion {
+    /// Creates a new region starting at `start` with `size` bytes.
+    ///
+    /// This method returns [`None`] if the end of the region is not a valid address.
+    #[must_use]
+    pub fn new(start: VirtAddr, size: Bytes) -> Option<Self> {
+        start.checked_add_bytes(size).map(|_| Self { start, size })
+    }
+
+    /// Creates a new region `start..end`.
+    ///
+    /// This method returns [`None`] if `end < start`.
+    #[must_use]
+    pub fn from_start_end(start: VirtAddr, end: VirtAddr) -> Option<Self> {
+        Bytes::between_virt(start, end).map(|size| Self { start, size })
+    }
+
+    /// Returns the first address of the region.
+    #[must_use]
+    pub const fn start(self) -> VirtAddr {
+        self.start
+    }
+
+    /// Returns the size of the region.
+    #[must_use]
+    pub const fn size(self) -> Bytes {
+        self.size
+    }
+
+    /// Returns the exclusive end of the region.
+    #[must_use]
+    pub fn end(self) -> VirtAddr {
+        // The constructors ensure that the sum is a valid address, which `new_truncate` returns
+        // as is or sign extends like `VirtAddr::try_new`.
+        VirtAddr::new_truncate(self.start.as_u64() + self.size.as_usize() as u64)
+    }
+
+    /// Returns `true` if the region has no bytes.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.size.as_usize() == 0
+    }
+
+    /// Returns `true` if `start <= addr < end`.
+    #[must_use]
+    pub fn contains(self, addr: VirtAddr) -> bool {
+        self.start <= addr && addr < self.end()
+    }
+
+    /// Returns `true` if `self` and `other` share at least one byte.
+    ///
+    /// Touching regions and empty regions never overlap.
+    #[must_use]
+    pub fn overlaps(self, other: Self) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.start < other.end()
+            && other.start < self.end()
+    }
+
+    /// Returns the range of the pages covering the region, rounding the start down and the end
+    /// up to the page boundaries.
+    ///
+    /// This method returns [`None`] if the rounded-up end is not a valid address.
+    #[must_use]
+    pub fn page_range<T: PageSize>(self) -> Option<PageRange<T>> {
+        let start = Page::containing_address(self.start);
+        let end = self
+            .end()
+            .as_u64()
+            .checked_add(T::SIZE - 1)
+            .map(|end| end & !(T::SIZE - 1))
+            .and_then(|end| VirtAddr::try_new(end).ok())?;
+
+        Page::from_start_address(end)
+            .ok()
+            .map(|end| Page::range(start, end))
+    }
+
+    /// Splits the region into `start..start + offset` and `start + offset..end`.
+    ///
+    /// This method returns [`None`] if `offset` is larger than the size of the region.
+    #[must_use]
+    pub fn split_at(self, offset: Bytes) -> Option<(Self, Self)> {
+        if offset > self.size {
+            None
+        } else {
+            let lower = Self {
+                start: self.start,
+                size: offset,
+            };
+            let upper = Self {
+                start: lower.end(),
+                size: Bytes::new(self.size.as_usize() - offset.as_usize()),
+            };
+
+            Some((lower, upper))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VirtRegion;
+    use crate::Bytes;
+    use x86_64::structures::paging::{Page, Size2MiB, Size4KiB};
+    use x86_64::VirtAddr;
+
+    fn region(start: u64, end: u64) -> VirtRegion {
+        VirtRegion::from_start_end(VirtAddr::new(start), VirtAddr::new(end)).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        let r = VirtRegion::new(VirtAddr::new(0x1000), Bytes::new(0x800)).unwrap();
+
+        assert_eq!(r.start(), VirtAddr::new(0x1000));
+        assert_eq!(r.size(), Bytes::new(0x800));
+        assert_eq!(r.end(), VirtAddr::new(0x1800));
+    }
+
+    #[test]
+    fn new_up_to_end_of_lower_half() {
+        let r = VirtRegion::new(VirtAddr::new(0x7fff_ffff_f000), Bytes::new(0x1000)).unwrap();
+
+        assert_eq!(r.end(), VirtAddr::new(0xffff_8000_0000_0000));
+        assert!(r.contains(VirtAddr::new(0x7fff_ffff_ffff)));
+        assert!(!r.contains(VirtAddr::new(0xffff_8000_0000_0000)));
+    }
+
+    #[test]
+    fn new_across_canonical_gap() {
+        let r = VirtRegion::new(VirtAddr::new(0x7fff_ffff_f000), Bytes::new(0x2000)).unwrap();
+
+        assert_eq!(r.end(), VirtAddr::new(0xffff_8000_0000_1000));
+        assert!(r.contains(VirtAddr::new(0xffff_8000_0000_0fff)));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn new_far_past_lower_half() {
+        let r = VirtRegion::new(VirtAddr::new(0x1000), Bytes::new(0x1_0000_0000_0000));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn new_past_top() {
+        let r = VirtRegion::new(VirtAddr::new(0xffff_ffff_ffff_f000), Bytes::new(0x1000));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn from_start_end_reversed() {
+        let r = VirtRegion::from_start_end(VirtAddr::new(0x2000), VirtAddr::new(0x1000));
+
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn from_start_end_across_canonical_gap() {
+        let r = region(0x7fff_ffff_f000, 0xffff_8000_0000_1000);
+
+        assert_eq!(r.size(), Bytes::new(0x2000));
+    }
+
+    #[test]
+    fn contains_exclusive_end() {
+        let r = region(0x1000, 0x2000);
+
+        assert!(r.contains(VirtAddr::new(0x1000)));
+        assert!(r.contains(VirtAddr::new(0x1fff)));
+        assert!(!r.contains(VirtAddr::new(0x2000)));
+        assert!(!r.contains(VirtAddr::new(0xfff)));
+    }
+
+    #[test]
+    fn overlaps() {
+        assert!(region(0x1000, 0x3000).overlaps(region(0x2000, 0x4000)));
+        assert!(region(0x2000, 0x4000).overlaps(region(0x1000, 0x3000)));
+    }
+
+    #[test]
+    fn touching_regions_do_not_overlap() {
+        assert!(!region(0x1000, 0x2000).overlaps(region(0x2000, 0x3000)));
+    }
+
+    #[test]
+    fn empty_region_does_not_overlap() {
+        assert!(!region(0x1000, 0x3000).overlaps(region(0x2000, 0x2000)));
+    }
+
+    #[test]
+    fn page_range_expands() {
+        let r = region(0x1800, 0x3001).page_range::<Size4KiB>().unwrap();
+        let start = Page::containing_address(VirtAddr::new(0x1000));
+        let end = Page::containing_address(VirtAddr::new(0x4000));
+
+        assert_eq!(r, Page::range(start, end));
+    }
+
+    #[test]
+    fn page_range_of_huge_pages() {
+        let r = region(0x20_0000, 0x40_0000)
+            .page_range::<Size2MiB>()
+            .unwrap();
+
+        assert_eq!(r.start.start_address(), VirtAddr::new(0x20_0000));
+        assert_eq!(r.end.start_address(), VirtAddr::new(0x40_0000));
+    }
+
+    #[test]
+    fn page_range_past_top() {
+        let r = region(0xffff_ffff_ffff_f000, 0xffff_ffff_ffff_f001);
+
+        assert_eq!(r.page_range::<Size4KiB>(), None);
+    }
+
+    #[test]
+    fn split_at() {
+        let (lower, upper) = region(0x1000, 0x3000).split_at(Bytes::new(0x800)).unwrap();
+
+        assert_eq!(lower, region(0x1000, 0x1800));
+        assert_eq!(upper, region(0x1800, 0x3000));
+    }
+
+    #[test]
+    fn split_at_ends() {
+        let r = region(0x1000, 0x3000);
+
+        assert_eq!(r.split_at(Bytes::zero()), Some((region(0x1000, 0x1000), r)));
+        assert_eq!(
+            r.split_at(Bytes::new(0x2000)),
+            Some((r, region(0x3000, 0x3000)))
+        );
+    }
+
+    #[test]
+    fn split_at_past_end() {
+        let r = region(0x1000, 0x3000);
+
+        assert_eq!(r.split_at(Bytes::new(0x2001)), None);
+    }
+
+    #[test]
+    fn split_at_canonical_gap() {
+        let r = region(0x7fff_ffff_f000, 0xffff_8000_0000_1000);
+        let (lower, upper) = r.split_at(Bytes::new(0x1000)).unwrap();
+
+        assert_eq!(lower.end(), VirtAddr::new(0xffff_8000_0000_0000));
+        assert_eq!(upper, region(0xffff_8000_0000_0000, 0xffff_8000_0000_1000));
+    }
+}
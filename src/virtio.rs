@@ -0,0 +1,185 @@
+//! Helpers for sizing virtio split virtqueues.
+//!
+//! A split virtqueue consists of the descriptor table, the available ring, and the used ring,
+//! laid out contiguously as in the legacy interface of the virtio specification:
+//!
+//! - The descriptor table of 16 bytes per entry starts at offset 0.
+//! - The available ring of `6 + 2 * queue_size` bytes follows the descriptor table immediately.
+//! - The used ring of `6 + 8 * queue_size` bytes starts at the next multiple of the alignment.
+//!
+//! The total size is also rounded up to the alignment.
+
+use crate::{Alignment, Bytes, NumOfPages};
+use core::convert::TryFrom;
+use x86_64::structures::paging::Size4KiB;
+
+const DESCRIPTOR_SIZE: usize = 16;
+const USED_ELEM_SIZE: usize = 8;
+const RING_HEADER_SIZE: usize = 6;
+
+/// The offsets and the size of a split virtqueue, returned by [`queue_layout`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct QueueLayout {
+    /// The offset of the descriptor table, which is always 0.
+    pub descriptors: Bytes,
+    /// The offset of the available ring.
+    pub available: Bytes,
+    /// The offset of the used ring.
+    pub used: Bytes,
+    /// The total size of the queue.
+    pub size: Bytes,
+}
+
+/// Returns the layout of a split virtqueue of `queue_size` entries aligned to `align`.
+///
+/// This function returns [`None`] if `queue_size` or `align` is not a power of two, or if the
+/// size does not fit in `usize`.
+///
+/// ```rust
+/// use os_units::virtio;
+/// use os_units::Bytes;
+///
+/// let l = virtio::queue_layout(256, Bytes::new(0x1000)).unwrap();
+///
+/// assert_eq!(l.descriptors, Bytes::zero());
+/// assert_eq!(l.available, Bytes::new(0x1000));
+/// assert_eq!(l.used, Bytes::new(0x2000));
+/// assert_eq!(l.size, Bytes::new(0x3000));
+/// ```
+#[must_use]
+pub fn queue_layout(queue_size: u16, align: Bytes) -> Option<QueueLayout> {
+    if !queue_size.is_power_of_two() {
+        return None;
+    }
+
+    let align = Alignment::try_from(align).ok()?;
+    let n = usize::from(queue_size);
+
+    let available = n.checked_mul(DESCRIPTOR_SIZE)?;
+    let available_end = n.checked_mul(2)?.checked_add(RING_HEADER_SIZE)?;
+    let available_end = available.checked_add(available_end)?;
+
+    let used = align.align_up(Bytes::new(available_end))?;
+    let used_len = n
+        .checked_mul(USED_ELEM_SIZE)?
+        .checked_add(RING_HEADER_SIZE)?;
+    let used_len = align.align_up(Bytes::new(used_len))?;
+
+    Some(QueueLayout {
+        descriptors: Bytes::zero(),
+        available: Bytes::new(available),
+        used,
+        size: Bytes::new(used.as_usize().checked_add(used_len.as_usize())?),
+    })
+}
+
+/// Returns the total size of a split virtqueue of `queue_size` entries aligned to `align`.
+///
+/// This is [`queue_layout`] followed by taking the size. See it for the conditions to return
+/// [`None`].
+///
+/// ```rust
+/// use os_units::virtio;
+/// use os_units::Bytes;
+///
+/// assert_eq!(
+///     virtio::queue_bytes(128, Bytes::new(0x1000)),
+///     Some(Bytes::new(0x2000))
+/// );
+/// ```
+#[must_use]
+pub fn queue_bytes(queue_size: u16, align: Bytes) -> Option<Bytes> {
+    queue_layout(queue_size, align).map(|l| l.size)
+}
+
+/// Returns the number of the 4 KiB pages to allocate for a split virtqueue of `queue_size`
+/// entries aligned to `align`.
+///
+/// See [`queue_layout`] for the conditions to return [`None`].
+///
+/// ```rust
+/// use os_units::virtio;
+/// use os_units::{Bytes, NumOfPages};
+///
+/// assert_eq!(
+///     virtio::queue_pages(1024, Bytes::new(0x1000)),
+///     Some(NumOfPages::new(8))
+/// );
+/// ```
+#[must_use]
+pub fn queue_pages(queue_size: u16, align: Bytes) -> Option<NumOfPages<Size4KiB>> {
+    queue_bytes(queue_size, align).map(Bytes::as_num_of_pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{queue_bytes, queue_layout, queue_pages};
+    use crate::{Bytes, NumOfPages};
+
+    const PAGE: Bytes = Bytes::new(0x1000);
+
+    #[test]
+    fn legacy_page_aligned() {
+        // The sizes legacy PCI devices expect with the alignment of 4096.
+        assert_eq!(queue_bytes(1, PAGE), Some(Bytes::new(0x2000)));
+        assert_eq!(queue_bytes(128, PAGE), Some(Bytes::new(0x2000)));
+        assert_eq!(queue_bytes(256, PAGE), Some(Bytes::new(0x3000)));
+        assert_eq!(queue_bytes(1024, PAGE), Some(Bytes::new(0x8000)));
+        assert_eq!(queue_bytes(32768, PAGE), Some(Bytes::new(0xd_2000)));
+    }
+
+    #[test]
+    fn offsets() {
+        let l = queue_layout(1024, PAGE).unwrap();
+
+        assert_eq!(l.descriptors, Bytes::zero());
+        assert_eq!(l.available, Bytes::new(0x4000));
+        // The available ring ends at 0x4000 + 6 + 2 * 1024 = 0x4806.
+        assert_eq!(l.used, Bytes::new(0x5000));
+        // The used ring is 6 + 8 * 1024 = 0x2006 bytes.
+        assert_eq!(l.size, Bytes::new(0x8000));
+    }
+
+    #[test]
+    fn small_align() {
+        let l = queue_layout(8, Bytes::new(4)).unwrap();
+
+        assert_eq!(l.available, Bytes::new(0x80));
+        // 0x80 + 6 + 16 = 0x96
+        assert_eq!(l.used, Bytes::new(0x98));
+        // 0x98 + 6 + 64 = 0xde, rounded up to 0xe0.
+        assert_eq!(l.size, Bytes::new(0xe0));
+    }
+
+    #[test]
+    fn available_ring_fills_alignment() {
+        // 16 * 16 + 6 + 2 * 16 = 0x126 is already a multiple of 2.
+        let l = queue_layout(16, Bytes::new(2)).unwrap();
+
+        assert_eq!(l.used, Bytes::new(0x126));
+    }
+
+    #[test]
+    fn pages() {
+        assert_eq!(queue_pages(256, PAGE), Some(NumOfPages::new(3)));
+        assert_eq!(queue_pages(8, Bytes::new(4)), Some(NumOfPages::new(1)));
+    }
+
+    #[test]
+    fn queue_size_not_power_of_two() {
+        assert_eq!(queue_layout(0, PAGE), None);
+        assert_eq!(queue_layout(100, PAGE), None);
+    }
+
+    #[test]
+    fn align_not_power_of_two() {
+        assert_eq!(queue_layout(256, Bytes::zero()), None);
+        assert_eq!(queue_layout(256, Bytes::new(0x1800)), None);
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(queue_layout(1, Bytes::new(1 << (usize::BITS - 1))), None);
+    }
+}
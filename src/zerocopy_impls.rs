@@ -0,0 +1,58 @@
+//! The traits of `zerocopy` are derived on the type definitions. This module only contains tests.
+
+#[cfg(test)]
+mod tests {
+    use crate::Bytes;
+    use crate::NumOfPages;
+    use x86_64::structures::paging::Size4KiB;
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    #[derive(FromBytes, IntoBytes, KnownLayout, Immutable)]
+    #[repr(C)]
+    struct BootInfo {
+        kernel_size: Bytes,
+        stack_pages: NumOfPages<Size4KiB>,
+    }
+
+    #[test]
+    fn read_bytes_from_buffer() {
+        let raw = 0x1234_usize.to_ne_bytes();
+
+        assert_eq!(Bytes::read_from_bytes(&raw), Ok(Bytes::new(0x1234)));
+    }
+
+    #[test]
+    fn read_from_short_buffer() {
+        let raw = [0_u8; 1];
+
+        assert!(Bytes::read_from_bytes(&raw).is_err());
+    }
+
+    #[test]
+    fn bytes_as_bytes() {
+        let b = Bytes::new(0x1234);
+
+        assert_eq!(b.as_bytes(), 0x1234_usize.to_ne_bytes());
+    }
+
+    #[test]
+    fn parse_struct() {
+        let mut raw = [0_u8; 2 * size_of::<usize>()];
+        raw[..size_of::<usize>()].copy_from_slice(&0x1_0000_usize.to_ne_bytes());
+        raw[size_of::<usize>()..].copy_from_slice(&4_usize.to_ne_bytes());
+
+        let info = BootInfo::read_from_bytes(&raw).unwrap();
+
+        assert_eq!(info.kernel_size, Bytes::new(0x1_0000));
+        assert_eq!(info.stack_pages, NumOfPages::new(4));
+    }
+
+    #[test]
+    fn ref_from_prefix() {
+        let raw = [3_usize, 5];
+        let (pages, rest) = NumOfPages::<Size4KiB>::ref_from_prefix(raw.as_bytes()).unwrap();
+
+        assert_eq!(*pages, NumOfPages::new(3));
+        assert_eq!(rest.len(), size_of::<usize>());
+    }
+}